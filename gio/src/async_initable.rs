@@ -0,0 +1,99 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::Cancellable;
+use glib::object::IsA;
+use glib::translate::*;
+use std::boxed::Box as Box_;
+use std::fmt;
+use std::pin::Pin;
+use std::ptr;
+
+glib::wrapper! {
+    pub struct AsyncInitable(Interface<ffi::GAsyncInitable, ffi::GAsyncInitableIface>);
+
+    match fn {
+        get_type => || ffi::g_async_initable_get_type(),
+    }
+}
+
+pub const NONE_ASYNC_INITABLE: Option<&AsyncInitable> = None;
+
+pub trait AsyncInitableExt: 'static {
+    /// Starts asynchronous initialization of the object.
+    ///
+    /// # Safety
+    ///
+    /// This must only be called once, right after constructing the object and before
+    /// using it in any other way.
+    #[doc(alias = "g_async_initable_init_async")]
+    unsafe fn init_async<P: IsA<Cancellable>, Q: FnOnce(Result<(), glib::Error>) + 'static>(
+        &self,
+        io_priority: glib::Priority,
+        cancellable: Option<&P>,
+        callback: Q,
+    );
+
+    /// Initializes the object asynchronously.
+    ///
+    /// # Safety
+    ///
+    /// See [`init_async`](AsyncInitableExt::init_async).
+    unsafe fn init_future(
+        &self,
+        io_priority: glib::Priority,
+    ) -> Pin<Box_<dyn std::future::Future<Output = Result<(), glib::Error>> + 'static>>;
+}
+
+impl<O: IsA<AsyncInitable>> AsyncInitableExt for O {
+    unsafe fn init_async<P: IsA<Cancellable>, Q: FnOnce(Result<(), glib::Error>) + 'static>(
+        &self,
+        io_priority: glib::Priority,
+        cancellable: Option<&P>,
+        callback: Q,
+    ) {
+        let user_data: Box_<Q> = Box_::new(callback);
+        unsafe extern "C" fn init_async_trampoline<Q: FnOnce(Result<(), glib::Error>) + 'static>(
+            _source_object: *mut glib::gobject_ffi::GObject,
+            res: *mut crate::ffi::GAsyncResult,
+            user_data: glib::ffi::gpointer,
+        ) {
+            let mut error = ptr::null_mut();
+            let _ = ffi::g_async_initable_init_finish(_source_object as *mut _, res, &mut error);
+            let result = if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            };
+            let callback: Box_<Q> = Box_::from_raw(user_data as *mut _);
+            callback(result);
+        }
+        let callback = init_async_trampoline::<Q>;
+        ffi::g_async_initable_init_async(
+            self.as_ref().to_glib_none().0,
+            io_priority.to_glib(),
+            cancellable.map(|p| p.as_ref()).to_glib_none().0,
+            Some(callback),
+            Box_::into_raw(user_data) as *mut _,
+        );
+    }
+
+    unsafe fn init_future(
+        &self,
+        io_priority: glib::Priority,
+    ) -> Pin<Box_<dyn std::future::Future<Output = Result<(), glib::Error>> + 'static>> {
+        Box_::pin(crate::GioFuture::new(self, move |obj, send| {
+            let cancellable = Cancellable::new();
+            obj.init_async(io_priority, Some(&cancellable), move |res| {
+                send.resolve(res);
+            });
+
+            cancellable
+        }))
+    }
+}
+
+impl fmt::Display for AsyncInitable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("AsyncInitable")
+    }
+}
@@ -0,0 +1,282 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::pollable_waker::PollableWaker;
+use crate::prelude::*;
+use crate::read_input_stream::std_error_to_gio_error;
+use crate::subclass::prelude::*;
+use crate::InputStream;
+
+use futures_io::AsyncRead;
+use std::any::Any;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+mod imp {
+    use super::*;
+    use std::cell::RefCell;
+
+    pub struct AsyncReadInputStream {
+        pub(super) read: RefCell<Option<AnyAsyncReader>>,
+        pub(super) waker: PollableWaker,
+    }
+
+    impl Default for AsyncReadInputStream {
+        fn default() -> Self {
+            AsyncReadInputStream {
+                read: RefCell::new(None),
+                waker: PollableWaker::new(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AsyncReadInputStream {
+        const NAME: &'static str = "AsyncReadInputStream";
+        type Type = super::AsyncReadInputStream;
+        type ParentType = InputStream;
+        type Interfaces = (crate::PollableInputStream,);
+    }
+
+    impl ObjectImpl for AsyncReadInputStream {}
+
+    impl AsyncReadInputStream {
+        fn poll_read(
+            &self,
+            cx: &mut Context,
+            buffer: &mut [u8],
+        ) -> Poll<Result<usize, glib::Error>> {
+            let mut read = self.read.borrow_mut();
+            let read = match *read {
+                None => {
+                    return Poll::Ready(Err(glib::Error::new(
+                        crate::IOErrorEnum::Closed,
+                        "Already closed",
+                    )));
+                }
+                Some(ref mut read) => read,
+            };
+
+            loop {
+                match read.poll_read(cx, buffer) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(res) => match std_error_to_gio_error(res) {
+                        None => continue,
+                        Some(res) => return Poll::Ready(res),
+                    },
+                }
+            }
+        }
+    }
+
+    impl InputStreamImpl for AsyncReadInputStream {
+        fn read(
+            &self,
+            _stream: &Self::Type,
+            buffer: &mut [u8],
+            _cancellable: Option<&crate::Cancellable>,
+        ) -> Result<usize, glib::Error> {
+            PollableWaker::block_on(|cx| self.poll_read(cx, buffer))
+        }
+
+        fn close(
+            &self,
+            _stream: &Self::Type,
+            _cancellable: Option<&crate::Cancellable>,
+        ) -> Result<(), glib::Error> {
+            let _ = self.read.borrow_mut().take();
+            Ok(())
+        }
+    }
+
+    impl PollableInputStreamImpl for AsyncReadInputStream {
+        fn can_poll(&self, _stream: &Self::Type) -> bool {
+            true
+        }
+
+        fn is_readable(&self, _stream: &Self::Type) -> bool {
+            self.waker.is_ready()
+        }
+
+        fn create_source(
+            &self,
+            stream: &Self::Type,
+            cancellable: Option<&crate::Cancellable>,
+        ) -> glib::Source {
+            crate::pollable_source_new_full(stream, Some(&self.waker.create_source()), cancellable)
+        }
+
+        fn read_nonblocking(
+            &self,
+            _stream: &Self::Type,
+            buffer: &mut [u8],
+        ) -> Result<usize, glib::Error> {
+            match self.waker.poll(|cx| self.poll_read(cx, buffer)) {
+                Poll::Ready(res) => res,
+                Poll::Pending => Err(glib::Error::new(
+                    crate::IOErrorEnum::WouldBlock,
+                    "Would Block",
+                )),
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// An [`InputStream`] reading from a [`futures_io::AsyncRead`].
+    ///
+    /// The stream implements [`PollableInputStream`](crate::PollableInputStream), so the
+    /// asynchronous operations of the stream never block the main context. The synchronous
+    /// operations block the calling thread until the reader is woken up.
+    pub struct AsyncReadInputStream(ObjectSubclass<imp::AsyncReadInputStream>) @extends crate::InputStream, @implements crate::PollableInputStream;
+}
+
+impl AsyncReadInputStream {
+    pub fn new<R: AsyncRead + Unpin + Send + 'static>(read: R) -> AsyncReadInputStream {
+        let obj = glib::Object::new(&[]).expect("Failed to create async read input stream");
+
+        let imp = imp::AsyncReadInputStream::from_instance(&obj);
+        *imp.read.borrow_mut() = Some(AnyAsyncReader::new(read));
+
+        obj
+    }
+
+    pub fn close_and_take(&self) -> Box<dyn Any + Send + 'static> {
+        let imp = imp::AsyncReadInputStream::from_instance(self);
+        let inner = imp.read.borrow_mut().take();
+
+        let ret = match inner {
+            None => {
+                panic!("Stream already closed or inner taken");
+            }
+            Some(read) => read.reader,
+        };
+
+        let _ = self.close(crate::NONE_CANCELLABLE);
+
+        match ret {
+            AnyOrPanic::Any(r) => r,
+            AnyOrPanic::Panic(p) => std::panic::resume_unwind(p),
+        }
+    }
+}
+
+enum AnyOrPanic {
+    Any(Box<dyn Any + Send + 'static>),
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+// Helper struct for dynamically dispatching to any kind of AsyncRead and
+// catching panics along the way
+struct AnyAsyncReader {
+    reader: AnyOrPanic,
+    poll_read_fn: fn(
+        s: &mut AnyAsyncReader,
+        cx: &mut Context,
+        buffer: &mut [u8],
+    ) -> Poll<std::io::Result<usize>>,
+}
+
+impl AnyAsyncReader {
+    fn new<R: AsyncRead + Unpin + Any + Send + 'static>(r: R) -> Self {
+        AnyAsyncReader {
+            reader: AnyOrPanic::Any(Box::new(r)),
+            poll_read_fn: Self::poll_read_fn::<R>,
+        }
+    }
+
+    fn poll_read_fn<R: AsyncRead + Unpin + 'static>(
+        s: &mut AnyAsyncReader,
+        cx: &mut Context,
+        buffer: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        s.with_inner(|r: &mut R| Pin::new(r).poll_read(cx, buffer))
+    }
+
+    fn with_inner<R: 'static, T, F: FnOnce(&mut R) -> Poll<std::io::Result<T>>>(
+        &mut self,
+        func: F,
+    ) -> Poll<std::io::Result<T>> {
+        match self.reader {
+            AnyOrPanic::Any(ref mut reader) => {
+                let r = reader.downcast_mut::<R>().unwrap();
+                match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| func(r))) {
+                    Ok(res) => res,
+                    Err(panic) => {
+                        self.reader = AnyOrPanic::Panic(panic);
+                        Poll::Ready(Err(std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            "Panicked",
+                        )))
+                    }
+                }
+            }
+            AnyOrPanic::Panic(_) => Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Panicked before",
+            ))),
+        }
+    }
+
+    fn poll_read(&mut self, cx: &mut Context, buffer: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        (self.poll_read_fn)(self, cx, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::io::AsyncReadExt;
+    use futures_util::stream::TryStreamExt;
+
+    #[test]
+    fn test_read_nonblocking() {
+        let (sender, receiver) = futures_channel::mpsc::unbounded::<std::io::Result<Vec<u8>>>();
+        let stream = AsyncReadInputStream::new(receiver.into_async_read());
+
+        let mut buf = [0u8; 16];
+        assert!(stream.is_readable());
+        let err = stream
+            .read_nonblocking(&mut buf[..], crate::NONE_CANCELLABLE)
+            .unwrap_err();
+        assert_eq!(
+            err.kind::<crate::IOErrorEnum>(),
+            Some(crate::IOErrorEnum::WouldBlock)
+        );
+        assert!(!stream.is_readable());
+
+        sender.unbounded_send(Ok(vec![1, 2, 3])).unwrap();
+        assert!(stream.is_readable());
+        assert_eq!(
+            stream.read_nonblocking(&mut buf[..], crate::NONE_CANCELLABLE),
+            Ok(3)
+        );
+        assert_eq!(&buf[..3], &[1, 2, 3][..]);
+
+        drop(sender);
+        assert_eq!(
+            stream.read_nonblocking(&mut buf[..], crate::NONE_CANCELLABLE),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn test_read_async() {
+        let (sender, receiver) = futures_channel::mpsc::unbounded::<std::io::Result<Vec<u8>>>();
+        let stream = AsyncReadInputStream::new(receiver.into_async_read());
+
+        let c = glib::MainContext::new();
+        let buf = c.block_on(async move {
+            glib::MainContext::ref_thread_default().spawn_local(async move {
+                sender.unbounded_send(Ok(vec![1, 2])).unwrap();
+                sender.unbounded_send(Ok(vec![3, 4])).unwrap();
+            });
+
+            let mut read = stream.into_async_read().unwrap();
+            let mut buf = [0u8; 4];
+            read.read_exact(&mut buf).await.unwrap();
+            buf
+        });
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
+}
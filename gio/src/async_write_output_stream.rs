@@ -0,0 +1,340 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::pollable_waker::PollableWaker;
+use crate::prelude::*;
+use crate::read_input_stream::std_error_to_gio_error;
+use crate::subclass::prelude::*;
+use crate::OutputStream;
+
+use futures_io::AsyncWrite;
+use std::any::Any;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+mod imp {
+    use super::*;
+    use std::cell::RefCell;
+
+    pub struct AsyncWriteOutputStream {
+        pub(super) write: RefCell<Option<AnyAsyncWriter>>,
+        pub(super) waker: PollableWaker,
+    }
+
+    impl Default for AsyncWriteOutputStream {
+        fn default() -> Self {
+            AsyncWriteOutputStream {
+                write: RefCell::new(None),
+                waker: PollableWaker::new(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AsyncWriteOutputStream {
+        const NAME: &'static str = "AsyncWriteOutputStream";
+        type Type = super::AsyncWriteOutputStream;
+        type ParentType = OutputStream;
+        type Interfaces = (crate::PollableOutputStream,);
+    }
+
+    impl ObjectImpl for AsyncWriteOutputStream {}
+
+    impl AsyncWriteOutputStream {
+        fn poll_with_writer<T, F: FnMut(&mut AnyAsyncWriter) -> Poll<std::io::Result<T>>>(
+            &self,
+            mut func: F,
+        ) -> Poll<Result<T, glib::Error>> {
+            let mut write = self.write.borrow_mut();
+            let write = match *write {
+                None => {
+                    return Poll::Ready(Err(glib::Error::new(
+                        crate::IOErrorEnum::Closed,
+                        "Already closed",
+                    )));
+                }
+                Some(ref mut write) => write,
+            };
+
+            loop {
+                match func(write) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(res) => match std_error_to_gio_error(res) {
+                        None => continue,
+                        Some(res) => return Poll::Ready(res),
+                    },
+                }
+            }
+        }
+    }
+
+    impl OutputStreamImpl for AsyncWriteOutputStream {
+        fn write(
+            &self,
+            _stream: &Self::Type,
+            buffer: &[u8],
+            _cancellable: Option<&crate::Cancellable>,
+        ) -> Result<usize, glib::Error> {
+            PollableWaker::block_on(|cx| self.poll_with_writer(|w| w.poll_write(cx, buffer)))
+        }
+
+        fn flush(
+            &self,
+            _stream: &Self::Type,
+            _cancellable: Option<&crate::Cancellable>,
+        ) -> Result<(), glib::Error> {
+            PollableWaker::block_on(|cx| self.poll_with_writer(|w| w.poll_flush(cx)))
+        }
+
+        fn close(
+            &self,
+            _stream: &Self::Type,
+            _cancellable: Option<&crate::Cancellable>,
+        ) -> Result<(), glib::Error> {
+            if self.write.borrow().is_none() {
+                return Ok(());
+            }
+
+            let res = PollableWaker::block_on(|cx| self.poll_with_writer(|w| w.poll_close(cx)));
+            let _ = self.write.borrow_mut().take();
+            res
+        }
+    }
+
+    impl PollableOutputStreamImpl for AsyncWriteOutputStream {
+        fn can_poll(&self, _stream: &Self::Type) -> bool {
+            true
+        }
+
+        fn is_writable(&self, _stream: &Self::Type) -> bool {
+            self.waker.is_ready()
+        }
+
+        fn create_source(
+            &self,
+            stream: &Self::Type,
+            cancellable: Option<&crate::Cancellable>,
+        ) -> glib::Source {
+            crate::pollable_source_new_full(stream, Some(&self.waker.create_source()), cancellable)
+        }
+
+        fn write_nonblocking(
+            &self,
+            _stream: &Self::Type,
+            buffer: &[u8],
+        ) -> Result<usize, glib::Error> {
+            match self
+                .waker
+                .poll(|cx| self.poll_with_writer(|w| w.poll_write(cx, buffer)))
+            {
+                Poll::Ready(res) => res,
+                Poll::Pending => Err(glib::Error::new(
+                    crate::IOErrorEnum::WouldBlock,
+                    "Would Block",
+                )),
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// An [`OutputStream`] writing to a [`futures_io::AsyncWrite`].
+    ///
+    /// The stream implements [`PollableOutputStream`](crate::PollableOutputStream), so
+    /// asynchronous writes never block the main context. The synchronous operations, including
+    /// flushing and closing, block the calling thread until the writer is woken up.
+    pub struct AsyncWriteOutputStream(ObjectSubclass<imp::AsyncWriteOutputStream>) @extends crate::OutputStream, @implements crate::PollableOutputStream;
+}
+
+impl AsyncWriteOutputStream {
+    pub fn new<W: AsyncWrite + Unpin + Send + 'static>(write: W) -> AsyncWriteOutputStream {
+        let obj = glib::Object::new(&[]).expect("Failed to create async write output stream");
+
+        let imp = imp::AsyncWriteOutputStream::from_instance(&obj);
+        *imp.write.borrow_mut() = Some(AnyAsyncWriter::new(write));
+
+        obj
+    }
+
+    pub fn close_and_take(&self) -> Box<dyn Any + Send + 'static> {
+        let imp = imp::AsyncWriteOutputStream::from_instance(self);
+        let inner = imp.write.borrow_mut().take();
+
+        let ret = match inner {
+            None => {
+                panic!("Stream already closed or inner taken");
+            }
+            Some(write) => write.writer,
+        };
+
+        let _ = self.close(crate::NONE_CANCELLABLE);
+
+        match ret {
+            AnyOrPanic::Any(w) => w,
+            AnyOrPanic::Panic(p) => std::panic::resume_unwind(p),
+        }
+    }
+}
+
+enum AnyOrPanic {
+    Any(Box<dyn Any + Send + 'static>),
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+// Helper struct for dynamically dispatching to any kind of AsyncWrite and
+// catching panics along the way
+struct AnyAsyncWriter {
+    writer: AnyOrPanic,
+    poll_write_fn:
+        fn(s: &mut AnyAsyncWriter, cx: &mut Context, buffer: &[u8]) -> Poll<std::io::Result<usize>>,
+    poll_flush_fn: fn(s: &mut AnyAsyncWriter, cx: &mut Context) -> Poll<std::io::Result<()>>,
+    poll_close_fn: fn(s: &mut AnyAsyncWriter, cx: &mut Context) -> Poll<std::io::Result<()>>,
+}
+
+impl AnyAsyncWriter {
+    fn new<W: AsyncWrite + Unpin + Any + Send + 'static>(w: W) -> Self {
+        AnyAsyncWriter {
+            writer: AnyOrPanic::Any(Box::new(w)),
+            poll_write_fn: Self::poll_write_fn::<W>,
+            poll_flush_fn: Self::poll_flush_fn::<W>,
+            poll_close_fn: Self::poll_close_fn::<W>,
+        }
+    }
+
+    fn poll_write_fn<W: AsyncWrite + Unpin + 'static>(
+        s: &mut AnyAsyncWriter,
+        cx: &mut Context,
+        buffer: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        s.with_inner(|w: &mut W| Pin::new(w).poll_write(cx, buffer))
+    }
+
+    fn poll_flush_fn<W: AsyncWrite + Unpin + 'static>(
+        s: &mut AnyAsyncWriter,
+        cx: &mut Context,
+    ) -> Poll<std::io::Result<()>> {
+        s.with_inner(|w: &mut W| Pin::new(w).poll_flush(cx))
+    }
+
+    fn poll_close_fn<W: AsyncWrite + Unpin + 'static>(
+        s: &mut AnyAsyncWriter,
+        cx: &mut Context,
+    ) -> Poll<std::io::Result<()>> {
+        s.with_inner(|w: &mut W| Pin::new(w).poll_close(cx))
+    }
+
+    fn with_inner<W: 'static, T, F: FnOnce(&mut W) -> Poll<std::io::Result<T>>>(
+        &mut self,
+        func: F,
+    ) -> Poll<std::io::Result<T>> {
+        match self.writer {
+            AnyOrPanic::Any(ref mut writer) => {
+                let w = writer.downcast_mut::<W>().unwrap();
+                match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| func(w))) {
+                    Ok(res) => res,
+                    Err(panic) => {
+                        self.writer = AnyOrPanic::Panic(panic);
+                        Poll::Ready(Err(std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            "Panicked",
+                        )))
+                    }
+                }
+            }
+            AnyOrPanic::Panic(_) => Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Panicked before",
+            ))),
+        }
+    }
+
+    fn poll_write(&mut self, cx: &mut Context, buffer: &[u8]) -> Poll<std::io::Result<usize>> {
+        (self.poll_write_fn)(self, cx, buffer)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context) -> Poll<std::io::Result<()>> {
+        (self.poll_flush_fn)(self, cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context) -> Poll<std::io::Result<()>> {
+        (self.poll_close_fn)(self, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::io::Cursor;
+
+    #[test]
+    fn test_write_nonblocking() {
+        let stream = AsyncWriteOutputStream::new(Cursor::new(Vec::<u8>::new()));
+
+        assert!(stream.is_writable());
+        assert_eq!(
+            stream.write_nonblocking(&[1, 2, 3], crate::NONE_CANCELLABLE),
+            Ok(3)
+        );
+        assert_eq!(stream.write(&[4, 5], crate::NONE_CANCELLABLE), Ok(2));
+        assert!(stream.is_writable());
+
+        let inner = stream.close_and_take();
+        let inner = inner.downcast_ref::<Cursor<Vec<u8>>>().unwrap();
+        assert_eq!(inner.get_ref(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_write_pending() {
+        let (sender, mut receiver) = futures_channel::mpsc::channel::<Vec<u8>>(0);
+        let stream = AsyncWriteOutputStream::new(ChannelWriter(sender));
+
+        assert_eq!(
+            stream.write_nonblocking(&[1, 2], crate::NONE_CANCELLABLE),
+            Ok(2)
+        );
+        let err = stream
+            .write_nonblocking(&[3, 4], crate::NONE_CANCELLABLE)
+            .unwrap_err();
+        assert_eq!(
+            err.kind::<crate::IOErrorEnum>(),
+            Some(crate::IOErrorEnum::WouldBlock)
+        );
+        assert!(!stream.is_writable());
+
+        assert_eq!(receiver.try_next().unwrap(), Some(vec![1, 2]));
+        assert!(stream.is_writable());
+        assert_eq!(
+            stream.write_nonblocking(&[3, 4], crate::NONE_CANCELLABLE),
+            Ok(2)
+        );
+    }
+
+    // Writer sending each write as a message over a bounded channel
+    struct ChannelWriter(futures_channel::mpsc::Sender<Vec<u8>>);
+
+    impl AsyncWrite for ChannelWriter {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            match self.0.poll_ready(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(Err(_)) => Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into())),
+                Poll::Ready(Ok(())) => {
+                    self.0.start_send(buf.to_vec()).unwrap();
+                    Poll::Ready(Ok(buf.len()))
+                }
+            }
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<std::io::Result<()>> {
+            self.0.close_channel();
+            Poll::Ready(Ok(()))
+        }
+    }
+}
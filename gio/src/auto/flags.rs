@@ -1618,6 +1618,58 @@ impl SetValue for SubprocessFlags {
     }
 }
 
+bitflags! {
+    pub struct TestDBusFlags: u32 {
+        const NONE = 0;
+    }
+}
+
+impl fmt::Display for TestDBusFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <Self as fmt::Debug>::fmt(self, f)
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for TestDBusFlags {
+    type GlibType = ffi::GTestDBusFlags;
+
+    fn to_glib(&self) -> ffi::GTestDBusFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GTestDBusFlags> for TestDBusFlags {
+    unsafe fn from_glib(value: ffi::GTestDBusFlags) -> TestDBusFlags {
+        TestDBusFlags::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for TestDBusFlags {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::g_test_dbus_flags_get_type()) }
+    }
+}
+
+impl<'a> FromValueOptional<'a> for TestDBusFlags {
+    unsafe fn from_value_optional(value: &glib::Value) -> Option<Self> {
+        Some(FromValue::from_value(value))
+    }
+}
+
+impl<'a> FromValue<'a> for TestDBusFlags {
+    unsafe fn from_value(value: &glib::Value) -> Self {
+        from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl SetValue for TestDBusFlags {
+    unsafe fn set_value(value: &mut glib::Value, this: &Self) {
+        glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, this.to_glib())
+    }
+}
+
 bitflags! {
     pub struct TlsCertificateFlags: u32 {
         const UNKNOWN_CA = 1;
@@ -0,0 +1,124 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+//! Conversions between Rust values and `GVariant`s of a fixed type, shared by the code generated
+//! by `generate_dbus_interfaces` and `generate_settings_schema`.
+
+use crate::IOErrorEnum;
+use glib::translate::*;
+use glib::{FromVariant, ToVariant, Variant, VariantTy};
+
+#[doc(hidden)]
+pub fn codegen_to_variant<T: ToVariant + ?Sized>(
+    value: &T,
+    signature: &str,
+) -> Result<Variant, glib::Error> {
+    let value = value.to_variant();
+    coerce_variant(&value, VariantTy::new(signature).unwrap()).ok_or_else(|| {
+        glib::Error::new(
+            IOErrorEnum::InvalidArgument,
+            &format!(
+                "Can't convert a value of type {} to type {}",
+                value.type_(),
+                signature
+            ),
+        )
+    })
+}
+
+#[doc(hidden)]
+pub fn codegen_from_variant<T: FromVariant>(value: &Variant, signature: &str) -> Option<T> {
+    if value.type_().to_str() != signature {
+        return None;
+    }
+    value.get()
+}
+
+// Strings, arrays and dictionaries convert to `GVariant`s of type `s`, `as` and `a{..}` while
+// the signature might ask for object paths, signatures or empty containers of a different
+// element type.
+//
+// Returns `None` if `value` can't be represented as `type_`.
+fn coerce_variant(value: &Variant, type_: &VariantTy) -> Option<Variant> {
+    if value.type_() == type_ {
+        return Some(value.clone());
+    }
+
+    unsafe {
+        match (type_.to_str(), value.get_str()) {
+            ("o", Some(s))
+                if from_glib(glib::ffi::g_variant_is_object_path(s.to_glib_none().0)) =>
+            {
+                Some(from_glib_none(glib::ffi::g_variant_new_object_path(
+                    s.to_glib_none().0,
+                )))
+            }
+            ("g", Some(s)) if from_glib(glib::ffi::g_variant_is_signature(s.to_glib_none().0)) => {
+                Some(from_glib_none(glib::ffi::g_variant_new_signature(
+                    s.to_glib_none().0,
+                )))
+            }
+            _ if type_.is_array() && value.type_().is_array() => {
+                let element = type_.element();
+                let children = value
+                    .iter()
+                    .map(|child| coerce_variant(&child, element))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(from_glib_none(glib::ffi::g_variant_new_array(
+                    element.as_ptr() as *const _,
+                    children.to_glib_none().0,
+                    children.len(),
+                )))
+            }
+            _ if type_.is_dict_entry() && value.type_().is_dict_entry() => {
+                let key = coerce_variant(&value.get_child_value(0), type_.key())?;
+                let val = coerce_variant(&value.get_child_value(1), type_.value())?;
+                Some(from_glib_none(glib::ffi::g_variant_new_dict_entry(
+                    key.to_glib_none().0,
+                    val.to_glib_none().0,
+                )))
+            }
+            _ if type_.is_tuple() && value.type_().is_tuple() && type_.to_str() != "()" => {
+                let types = type_.tuple_types().collect::<Vec<_>>();
+                if types.len() != value.n_children() {
+                    return None;
+                }
+                let children = value
+                    .iter()
+                    .zip(types)
+                    .map(|(child, type_)| coerce_variant(&child, type_))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Variant::tuple(&children))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coerce() {
+        let value = codegen_to_variant(&("/org/example", vec!["/a", "/b"]), "(oao)").unwrap();
+        assert_eq!(value.type_().to_str(), "(oao)");
+
+        let value = codegen_to_variant(&Vec::<String>::new(), "ao").unwrap();
+        assert_eq!(value.type_().to_str(), "ao");
+
+        let err = codegen_to_variant(&"not a path", "o").unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::InvalidArgument));
+        let err = codegen_to_variant(&vec!["/a", "b"], "ao").unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::InvalidArgument));
+    }
+
+    #[test]
+    fn from_variant() {
+        let value = ("/a",).to_variant();
+        assert_eq!(
+            codegen_from_variant::<(String,)>(&value, "(s)"),
+            Some(("/a".to_string(),))
+        );
+        assert_eq!(codegen_from_variant::<(String,)>(&value, "(o)"), None);
+    }
+}
@@ -4,8 +4,12 @@ use crate::BusNameOwnerFlags;
 use crate::BusNameWatcherFlags;
 use crate::BusType;
 use crate::DBusConnection;
+use futures_channel::mpsc;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
 use glib::translate::*;
 use std::num::NonZeroU32;
+use std::pin::Pin;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct OwnerId(NonZeroU32);
@@ -150,3 +154,164 @@ pub fn bus_unwatch_name(watcher_id: WatcherId) {
         ffi::g_bus_unwatch_name(watcher_id.0.into());
     }
 }
+
+/// An event of a [`BusNameWatcherStream`].
+#[derive(Clone, Debug)]
+pub enum BusNameWatcherEvent {
+    /// The name is owned by `name_owner`.
+    Appeared {
+        connection: DBusConnection,
+        name: String,
+        name_owner: String,
+    },
+    /// The name has no owner. `connection` is `None` if connecting to the bus failed.
+    Vanished {
+        connection: Option<DBusConnection>,
+        name: String,
+    },
+}
+
+/// A stream of [`BusNameWatcherEvent`]s, as returned by [`bus_watch_name_stream`].
+///
+/// The name is unwatched when the stream is dropped.
+#[derive(Debug)]
+pub struct BusNameWatcherStream {
+    watcher_id: Option<WatcherId>,
+    receiver: mpsc::UnboundedReceiver<BusNameWatcherEvent>,
+}
+
+impl Stream for BusNameWatcherStream {
+    type Item = BusNameWatcherEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(ctx)
+    }
+}
+
+impl Drop for BusNameWatcherStream {
+    fn drop(&mut self) {
+        if let Some(watcher_id) = self.watcher_id.take() {
+            bus_unwatch_name(watcher_id);
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Like [`bus_watch_name`], but returns a stream of [`BusNameWatcherEvent`]s instead of calling
+/// closures.
+///
+/// ```no_run
+/// # use futures_util::StreamExt;
+/// # async fn run() {
+/// let mut events = gio::bus_watch_name_stream(
+///     gio::BusType::Session,
+///     "org.example.Service",
+///     gio::BusNameWatcherFlags::NONE,
+/// );
+/// while let Some(event) = events.next().await {
+///     println!("{:?}", event);
+/// }
+/// # }
+/// ```
+pub fn bus_watch_name_stream(
+    bus_type: BusType,
+    name: &str,
+    flags: BusNameWatcherFlags,
+) -> BusNameWatcherStream {
+    let (sender, receiver) = mpsc::unbounded();
+    let vanished_sender = sender.clone();
+
+    unsafe {
+        let id = ffi::g_bus_watch_name_with_closures(
+            bus_type.to_glib(),
+            name.to_glib_none().0,
+            flags.to_glib(),
+            glib::Closure::new_local(move |args| {
+                let connection = args[0].get::<DBusConnection>().unwrap().unwrap();
+                let name = args[1].get::<String>().unwrap().unwrap();
+                let name_owner = args[2].get::<String>().unwrap().unwrap();
+                let _ = sender.unbounded_send(BusNameWatcherEvent::Appeared {
+                    connection,
+                    name,
+                    name_owner,
+                });
+                None
+            })
+            .to_glib_none()
+            .0,
+            glib::Closure::new_local(move |args| {
+                let connection = args[0].get::<DBusConnection>().unwrap();
+                let name = args[1].get::<String>().unwrap().unwrap();
+                let _ = vanished_sender
+                    .unbounded_send(BusNameWatcherEvent::Vanished { connection, name });
+                None
+            })
+            .to_glib_none()
+            .0,
+        );
+
+        BusNameWatcherStream {
+            watcher_id: Some(WatcherId(NonZeroU32::new_unchecked(id))),
+            receiver,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_bus, test_bus_connection};
+    use futures_util::StreamExt;
+
+    const NAME: &str = "org.example.Test";
+
+    #[test]
+    #[serial_test::serial]
+    fn watch_name_stream() {
+        let bus = test_bus();
+        let owner = test_bus_connection(&bus);
+        let c = glib::MainContext::new();
+
+        c.with_thread_default(|| {
+            let mut events =
+                bus_watch_name_stream(BusType::Session, NAME, BusNameWatcherFlags::NONE);
+
+            match c.block_on(events.next()).unwrap() {
+                BusNameWatcherEvent::Vanished { connection, name } => {
+                    assert!(connection.is_some());
+                    assert_eq!(name, NAME);
+                }
+                event => panic!("Unexpected event {:?}", event),
+            }
+
+            let owner_id = bus_own_name_on_connection(
+                &owner,
+                NAME,
+                BusNameOwnerFlags::NONE,
+                |_, _| {},
+                |_, _| {},
+            );
+            match c.block_on(events.next()).unwrap() {
+                BusNameWatcherEvent::Appeared {
+                    name, name_owner, ..
+                } => {
+                    assert_eq!(name, NAME);
+                    assert_eq!(name_owner, owner.get_unique_name().unwrap().as_str());
+                }
+                event => panic!("Unexpected event {:?}", event),
+            }
+
+            bus_unown_name(owner_id);
+            match c.block_on(events.next()).unwrap() {
+                BusNameWatcherEvent::Vanished { name, .. } => assert_eq!(name, NAME),
+                event => panic!("Unexpected event {:?}", event),
+            }
+        });
+
+        // The watcher releases its reference to the session bus connection from the main
+        // context, which has to happen before the bus can be taken down
+        while c.iteration(false) {}
+        drop(owner);
+        bus.down();
+    }
+}
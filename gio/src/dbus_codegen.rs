@@ -0,0 +1,1269 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::DBusArgInfo;
+use crate::DBusConnection;
+use crate::DBusInterfaceInfo;
+use crate::DBusMethodInvocation;
+use crate::DBusNodeInfo;
+use crate::DBusProxy;
+use crate::DBusSignalFlags;
+use crate::DBusSignalStream;
+use crate::IOErrorEnum;
+use crate::RegistrationId;
+use glib::object::IsA;
+use glib::translate::*;
+use glib::{FromVariant, ToVariant, Variant, VariantTy};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::ptr;
+
+// rustdoc-stripper-ignore-next
+/// Call from build script to generate typed bindings for the D-Bus interfaces described in the
+/// introspection XML file `xml`. `target` is relative to `OUT_DIR`.
+///
+/// ```no_run
+/// gio::compile_dbus_interfaces("data/org.example.Calculator.xml", "calculator.rs");
+/// ```
+///
+/// The generated code is then pulled into the crate with `include!`:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/calculator.rs"));
+/// ```
+///
+/// See [`generate_dbus_interfaces`] for the items generated for each interface.
+pub fn compile_dbus_interfaces<P: AsRef<Path>>(xml: P, target: &str) {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let xml = xml.as_ref();
+
+    let data = fs::read_to_string(xml)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", xml.display(), err));
+    let code = generate_dbus_interfaces(&data).unwrap_or_else(|err| {
+        panic!(
+            "failed to generate D-Bus bindings for {}: {}",
+            xml.display(),
+            err
+        )
+    });
+    fs::write(Path::new(&out_dir).join(target), code).unwrap();
+
+    println!("cargo:rerun-if-changed={}", xml.display());
+}
+
+// rustdoc-stripper-ignore-next
+/// Generates Rust source code for all interfaces of the D-Bus introspection XML `xml`.
+///
+/// For an interface `org.example.Calculator` this generates:
+///
+/// * a `Calculator` trait with one method per D-Bus method, a getter per readable property and
+///   a `set_` method per writable property, to be implemented by the server side,
+/// * a `register_calculator` function exporting a `Calculator` implementation on a
+///   [`DBusConnection`](crate::DBusConnection) (this needs the `v2_46` feature),
+/// * a `CalculatorEmitter` with one `emit_` method per signal, if the interface has signals,
+/// * a `CalculatorProxy` with one async method per D-Bus method, a getter per property
+///   returning the cached value, an async `set_` method per writable property and a
+///   `receive_` method per signal returning a [`DBusSignalStream`] of its arguments.
+///
+/// Interfaces sharing the same last name segment are told apart by prepending as many of the
+/// preceding segments as needed, e.g. `org.example.audio.Device` becomes `AudioDevice` next to
+/// `org.example.video.Device`. An error is returned if two members or arguments of an interface
+/// would still map to the same Rust name, e.g. a `Name` method and a `Name` property.
+///
+/// D-Bus types are mapped to the Rust types implementing [`FromVariant`] and [`ToVariant`] for
+/// the corresponding `GVariant` type. Object paths and signatures are mapped to `String`, and
+/// Unix file descriptors are not supported.
+pub fn generate_dbus_interfaces(xml: &str) -> Result<String, glib::Error> {
+    let node = DBusNodeInfo::new_for_xml(xml)?;
+    let interfaces = node.interfaces();
+    let bases = interface_bases(
+        &interfaces
+            .iter()
+            .map(|interface| interface.name().to_string())
+            .collect::<Vec<_>>(),
+    )?;
+
+    let mut code = String::new();
+    for (interface, base) in interfaces.iter().zip(bases) {
+        generate_interface(&mut code, interface, &base)?;
+    }
+
+    Ok(code)
+}
+
+struct Arg {
+    name: String,
+    dbus_name: String,
+    signature: String,
+    owned_type: String,
+    borrowed_type: String,
+}
+
+pub(crate) fn unsupported(message: String) -> glib::Error {
+    glib::Error::new(IOErrorEnum::NotSupported, &message)
+}
+
+pub(crate) fn collision(message: String) -> glib::Error {
+    glib::Error::new(IOErrorEnum::InvalidArgument, &message)
+}
+
+// Keeps track of the Rust names generated into one namespace, e.g. the methods of a trait,
+// and fails on the first name that is generated twice.
+pub(crate) struct Names<'a> {
+    scope: &'a str,
+    names: HashMap<String, String>,
+}
+
+impl<'a> Names<'a> {
+    pub(crate) fn new(scope: &'a str) -> Self {
+        Self {
+            scope,
+            names: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, ident: &str, origin: String) -> Result<(), glib::Error> {
+        let key = ident.trim_start_matches("r#").to_string();
+        match self.names.get(&key) {
+            Some(other) => Err(collision(format!(
+                "{} and {} both map to `{}` in {}",
+                other, origin, key, self.scope
+            ))),
+            None => {
+                self.names.insert(key, origin);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn upper_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// Names each of `ids` after its last dot separated segment, prefixed with as many of the
+// preceding segments as needed to tell it apart from the other ids. `name` builds the Rust name
+// from a slice of segments.
+pub(crate) fn unique_suffix_names<F: Fn(&[&str]) -> String>(
+    ids: &[String],
+    name: F,
+) -> Vec<String> {
+    let suffix_name = |id: &str, len: usize| {
+        let segments = id.split('.').collect::<Vec<_>>();
+        name(&segments[segments.len().saturating_sub(len)..])
+    };
+
+    ids.iter()
+        .map(|id| {
+            let max_len = id.split('.').count();
+            (1..=max_len)
+                .map(|len| (len, suffix_name(id, len)))
+                .find(|(len, candidate)| {
+                    ids.iter()
+                        .filter(|other| *other != id)
+                        .all(|other| suffix_name(other, *len) != *candidate)
+                })
+                .map(|(_, candidate)| candidate)
+                .unwrap_or_else(|| suffix_name(id, max_len))
+        })
+        .collect()
+}
+
+// Interfaces are named after the last segment of their D-Bus name, qualified as needed by
+// `unique_suffix_names`.
+fn interface_bases(names: &[String]) -> Result<Vec<String>, glib::Error> {
+    let bases = unique_suffix_names(names, |segments| {
+        segments
+            .iter()
+            .map(|segment| upper_first(segment))
+            .collect()
+    });
+
+    let mut items = Names::new("the generated items");
+    for (name, base) in names.iter().zip(&bases) {
+        items.insert(&to_snake_case(base), format!("D-Bus interface '{}'", name))?;
+    }
+
+    Ok(bases)
+}
+
+pub(crate) fn rust_type(type_: &VariantTy) -> Result<String, glib::Error> {
+    let ret = match type_.to_str() {
+        "b" => "bool".to_string(),
+        "y" => "u8".to_string(),
+        "n" => "i16".to_string(),
+        "q" => "u16".to_string(),
+        "i" => "i32".to_string(),
+        "u" => "u32".to_string(),
+        "x" => "i64".to_string(),
+        "t" => "u64".to_string(),
+        "d" => "f64".to_string(),
+        "s" | "o" | "g" => "String".to_string(),
+        "v" => "gio::glib::Variant".to_string(),
+        _ if type_.is_array() && type_.element().is_dict_entry() => {
+            let entry = type_.element();
+            if entry.key().to_str() == "d" {
+                return Err(unsupported(format!(
+                    "Unsupported dictionary key type in '{}'",
+                    type_.to_str()
+                )));
+            }
+            format!(
+                "std::collections::HashMap<{}, {}>",
+                rust_type(entry.key())?,
+                rust_type(entry.value())?
+            )
+        }
+        _ if type_.is_array() => format!("Vec<{}>", rust_type(type_.element())?),
+        "()" => "()".to_string(),
+        _ if type_.is_tuple() => {
+            let items = type_
+                .tuple_types()
+                .map(rust_type)
+                .collect::<Result<Vec<_>, _>>()?;
+            if items.len() > 16 {
+                return Err(unsupported(format!(
+                    "Unsupported tuple size in '{}'",
+                    type_.to_str()
+                )));
+            }
+            format!("({},)", items.join(", "))
+        }
+        _ => {
+            return Err(unsupported(format!(
+                "Unsupported D-Bus type '{}'",
+                type_.to_str()
+            )))
+        }
+    };
+
+    Ok(ret)
+}
+
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut ret = String::with_capacity(name.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let acronym_end = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).map_or(false, |c| c.is_lowercase());
+            if (after_lower || acronym_end) && !ret.ends_with('_') {
+                ret.push('_');
+            }
+            ret.extend(c.to_lowercase());
+        } else if c.is_alphanumeric() {
+            ret.push(c);
+        } else if !ret.ends_with('_') {
+            ret.push('_');
+        }
+    }
+
+    ret
+}
+
+fn to_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+
+    let name = to_snake_case(name);
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else if name == "self" || name == "super" || name == "crate" {
+        format!("{}_", name)
+    } else if name.starts_with(char::is_numeric) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+fn args(list: &[DBusArgInfo]) -> Result<Vec<Arg>, glib::Error> {
+    list.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            let type_ =
+                VariantTy::new(arg.signature()).map_err(|err| unsupported(err.to_string()))?;
+            let owned_type = rust_type(type_)?;
+            let borrowed_type = match type_.to_str() {
+                "s" | "o" | "g" => "&str".to_string(),
+                _ => owned_type.clone(),
+            };
+            let dbus_name = match arg.name() {
+                Some(name) => name.to_string(),
+                None => format!("arg{}", i),
+            };
+            Ok(Arg {
+                name: to_ident(&dbus_name),
+                dbus_name,
+                signature: arg.signature().to_string(),
+                owned_type,
+                borrowed_type,
+            })
+        })
+        .collect()
+}
+
+fn tuple_signature(args: &[Arg]) -> String {
+    let mut ret = String::from("(");
+    for arg in args {
+        ret.push_str(&arg.signature);
+    }
+    ret.push(')');
+    ret
+}
+
+fn tuple_type(args: &[Arg]) -> String {
+    if args.is_empty() {
+        return "()".to_string();
+    }
+    let types = args
+        .iter()
+        .map(|arg| arg.owned_type.as_str())
+        .collect::<Vec<_>>();
+    format!("({},)", types.join(", "))
+}
+
+fn return_type(args: &[Arg]) -> String {
+    match args.len() {
+        0 => "()".to_string(),
+        1 => args[0].owned_type.clone(),
+        _ => tuple_type(args),
+    }
+}
+
+fn params(args: &[Arg], borrowed: bool) -> String {
+    let mut ret = String::new();
+    for arg in args {
+        let type_ = if borrowed {
+            &arg.borrowed_type
+        } else {
+            &arg.owned_type
+        };
+        write!(ret, ", {}: {}", arg.name, type_).unwrap();
+    }
+    ret
+}
+
+fn names(args: &[Arg]) -> String {
+    let names = args.iter().map(|arg| arg.name.as_str()).collect::<Vec<_>>();
+    format!("({},)", names.join(", "))
+}
+
+// Fails if two D-Bus members of `interface_name` map to the same Rust item or if two arguments
+// of a member map to the same parameter name.
+fn check_names(
+    interface_name: &str,
+    base: &str,
+    methods: &[(String, Vec<Arg>, Vec<Arg>)],
+    signals: &[(String, Vec<Arg>)],
+    properties: &[(String, String, String, bool, bool)],
+) -> Result<(), glib::Error> {
+    let scope = |item: String| format!("{} (D-Bus interface '{}')", item, interface_name);
+    let trait_scope = scope(format!("the `{}` trait", base));
+    let proxy_scope = scope(format!("`{}Proxy`", base));
+    let emitter_scope = scope(format!("`{}Emitter`", base));
+    let mut trait_names = Names::new(&trait_scope);
+    let mut proxy_names = Names::new(&proxy_scope);
+    let mut emitter_names = Names::new(&emitter_scope);
+
+    for ident in &["new", "from_proxy", "proxy"] {
+        proxy_names.insert(ident, format!("the generated `{}` function", ident))?;
+    }
+    emitter_names.insert("new", "the generated `new` function".to_string())?;
+
+    for (name, in_args, out_args) in methods {
+        let origin = format!("method '{}'", name);
+        trait_names.insert(&to_ident(name), origin.clone())?;
+        proxy_names.insert(&to_ident(name), origin)?;
+
+        let arg_scope = scope(format!("the arguments of method '{}'", name));
+        let mut arg_names = Names::new(&arg_scope);
+        for arg in in_args.iter().chain(out_args) {
+            arg_names.insert(&arg.name, format!("argument '{}'", arg.dbus_name))?;
+        }
+    }
+    for (name, args) in signals {
+        let origin = format!("signal '{}'", name);
+        emitter_names.insert(&format!("emit_{}", to_snake_case(name)), origin.clone())?;
+        proxy_names.insert(&format!("receive_{}", to_snake_case(name)), origin)?;
+
+        let arg_scope = scope(format!("the arguments of signal '{}'", name));
+        let mut arg_names = Names::new(&arg_scope);
+        for arg in args {
+            arg_names.insert(&arg.name, format!("argument '{}'", arg.dbus_name))?;
+        }
+    }
+    for (name, _, _, readable, writable) in properties {
+        let origin = format!("property '{}'", name);
+        if *readable {
+            trait_names.insert(&to_ident(name), origin.clone())?;
+        }
+        proxy_names.insert(&to_ident(name), origin.clone())?;
+        if *writable {
+            let setter = format!("set_{}", to_snake_case(name));
+            trait_names.insert(&setter, origin.clone())?;
+            proxy_names.insert(&setter, origin)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_interface(
+    code: &mut String,
+    interface: &DBusInterfaceInfo,
+    base: &str,
+) -> Result<(), glib::Error> {
+    let interface_name = interface.name();
+    let snake = to_snake_case(base);
+
+    let mut xml = glib::String::new("<node>\n");
+    interface.generate_xml(2, &mut xml);
+    xml.append("</node>\n");
+
+    let methods = interface
+        .methods()
+        .into_iter()
+        .map(|method| {
+            Ok((
+                method.name().to_string(),
+                args(&method.in_args())?,
+                args(&method.out_args())?,
+            ))
+        })
+        .collect::<Result<Vec<_>, glib::Error>>()?;
+    let signals = interface
+        .signals()
+        .into_iter()
+        .map(|signal| Ok((signal.name().to_string(), args(&signal.args())?)))
+        .collect::<Result<Vec<_>, glib::Error>>()?;
+    let properties = interface
+        .properties()
+        .into_iter()
+        .map(|property| {
+            let type_ =
+                VariantTy::new(property.signature()).map_err(|err| unsupported(err.to_string()))?;
+            Ok((
+                property.name().to_string(),
+                property.signature().to_string(),
+                rust_type(type_)?,
+                property.is_readable(),
+                property.is_writable(),
+            ))
+        })
+        .collect::<Result<Vec<_>, glib::Error>>()?;
+    check_names(interface_name, base, &methods, &signals, &properties)?;
+
+    writeln!(code, "// D-Bus interface `{}`.", interface_name).unwrap();
+    writeln!(code).unwrap();
+
+    // Introspection data
+    writeln!(
+        code,
+        "fn {snake}_interface_info() -> gio::DBusInterfaceInfo {{
+    gio::DBusNodeInfo::new_for_xml({xml:?})
+        .expect(\"invalid D-Bus introspection data\")
+        .lookup_interface({name:?})
+        .expect(\"missing D-Bus interface\")
+}}
+",
+        snake = snake,
+        xml = xml.to_str().unwrap(),
+        name = interface_name,
+    )
+    .unwrap();
+
+    // Server trait
+    writeln!(
+        code,
+        "/// Server side of the `{}` D-Bus interface.",
+        interface_name
+    )
+    .unwrap();
+    writeln!(code, "pub trait {}: Send + Sync + 'static {{", base).unwrap();
+    for (name, in_args, out_args) in &methods {
+        writeln!(
+            code,
+            "    /// Handles calls of the `{name}` method.
+    fn {ident}(&self{params}) -> Result<{ret}, gio::glib::Error>;",
+            name = name,
+            ident = to_ident(name),
+            params = params(in_args, false),
+            ret = return_type(out_args),
+        )
+        .unwrap();
+    }
+    for (name, _, type_, readable, writable) in &properties {
+        if *readable {
+            writeln!(
+                code,
+                "    /// Returns the value of the `{name}` property.
+    fn {ident}(&self) -> {type_};",
+                name = name,
+                ident = to_ident(name),
+                type_ = type_,
+            )
+            .unwrap();
+        }
+        if *writable {
+            writeln!(
+                code,
+                "    /// Sets the value of the `{name}` property.
+    ///
+    /// Returns `false` if the value was rejected.
+    fn set_{ident}(&self, value: {type_}) -> bool;",
+                name = name,
+                ident = to_snake_case(name),
+                type_ = type_,
+            )
+            .unwrap();
+        }
+    }
+    writeln!(code, "}}\n").unwrap();
+
+    // Registration
+    writeln!(
+        code,
+        "/// Exports `imp` as the `{name}` interface of the object at `object_path`.
+pub fn register_{snake}<T: {base}>(
+    connection: &gio::DBusConnection,
+    object_path: &str,
+    imp: T,
+) -> Result<gio::RegistrationId, gio::glib::Error> {{
+    let imp = std::sync::Arc::new(imp);",
+        name = interface_name,
+        snake = snake,
+        base = base,
+    )
+    .unwrap();
+    if !methods.is_empty() {
+        writeln!(code, "    let method_imp = imp.clone();").unwrap();
+    }
+    if properties.iter().any(|p| p.3) {
+        writeln!(code, "    let get_imp = imp.clone();").unwrap();
+    }
+    if properties.iter().any(|p| p.4) {
+        writeln!(code, "    let set_imp = imp.clone();").unwrap();
+    }
+    writeln!(code, "    drop(imp);").unwrap();
+    writeln!(
+        code,
+        "    gio::dbus_codegen_register_object(
+        connection,
+        object_path,
+        &{}_interface_info(),",
+        snake
+    )
+    .unwrap();
+
+    if methods.is_empty() {
+        writeln!(
+            code,
+            "        |_, _, invocation| {{
+            invocation.return_dbus_error(\"org.freedesktop.DBus.Error.UnknownMethod\", \"Unknown method\")
+        }},"
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            code,
+            "        move |method_name, {}, invocation| match method_name {{",
+            if methods.iter().any(|m| !m.1.is_empty()) {
+                "parameters"
+            } else {
+                "_"
+            }
+        )
+        .unwrap();
+        for (name, in_args, out_args) in &methods {
+            writeln!(code, "            {:?} => {{", name).unwrap();
+            let locals = (0..in_args.len())
+                .map(|i| format!("arg{}", i))
+                .collect::<Vec<_>>();
+            if !in_args.is_empty() {
+                writeln!(
+                    code,
+                    "                let ({locals},) = match gio::codegen_from_variant::<{type_}>(&parameters, {sig:?}) {{
+                    Some(args) => args,
+                    None => {{
+                        return invocation.return_dbus_error(\"org.freedesktop.DBus.Error.InvalidArgs\", \"Invalid arguments\");
+                    }}
+                }};",
+                    locals = locals.join(", "),
+                    type_ = tuple_type(in_args),
+                    sig = tuple_signature(in_args),
+                )
+                .unwrap();
+            }
+            let call = format!("method_imp.{}({})", to_ident(name), locals.join(", "));
+            let (call, reply) = match out_args.len() {
+                0 => (call, "None"),
+                1 => (
+                    format!(
+                        "{}.and_then(|ret| gio::codegen_to_variant(&(ret,), {:?}))",
+                        call,
+                        tuple_signature(out_args)
+                    ),
+                    "Some(&reply)",
+                ),
+                _ => (
+                    format!(
+                        "{}.and_then(|ret| gio::codegen_to_variant(&ret, {:?}))",
+                        call,
+                        tuple_signature(out_args)
+                    ),
+                    "Some(&reply)",
+                ),
+            };
+            writeln!(
+                code,
+                "                match {call} {{
+                    Ok({ret}) => invocation.return_value({reply}),
+                    Err(err) => invocation.return_gerror(err),
+                }}
+            }}",
+                call = call,
+                ret = if out_args.is_empty() { "()" } else { "reply" },
+                reply = reply,
+            )
+            .unwrap();
+        }
+        writeln!(
+            code,
+            "            _ => invocation.return_dbus_error(\"org.freedesktop.DBus.Error.UnknownMethod\", \"Unknown method\"),
+        }},"
+        )
+        .unwrap();
+    }
+
+    if properties.iter().any(|p| p.3) {
+        writeln!(code, "        move |property_name| match property_name {{").unwrap();
+        for (name, sig, _, _, _) in properties.iter().filter(|p| p.3) {
+            writeln!(
+                code,
+                "            {name:?} => gio::codegen_to_variant(&get_imp.{ident}(), {sig:?}),",
+                name = name,
+                ident = to_ident(name),
+                sig = sig,
+            )
+            .unwrap();
+        }
+        writeln!(
+            code,
+            "            _ => Err(gio::dbus_codegen_unknown_property(property_name)),\n        }},"
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            code,
+            "        |property_name| Err(gio::dbus_codegen_unknown_property(property_name)),"
+        )
+        .unwrap();
+    }
+
+    if properties.iter().any(|p| p.4) {
+        writeln!(
+            code,
+            "        move |property_name, value| match property_name {{"
+        )
+        .unwrap();
+        for (name, sig, _, _, _) in properties.iter().filter(|p| p.4) {
+            writeln!(
+                code,
+                "            {name:?} => gio::dbus_codegen_set_property(property_name, &value, {sig:?}, |value| {{
+                set_imp.set_{ident}(value)
+            }}),",
+                name = name,
+                ident = to_snake_case(name),
+                sig = sig,
+            )
+            .unwrap();
+        }
+        writeln!(
+            code,
+            "            _ => Err(gio::dbus_codegen_unknown_property(property_name)),\n        }},"
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            code,
+            "        |property_name, _| Err(gio::dbus_codegen_unknown_property(property_name)),"
+        )
+        .unwrap();
+    }
+    writeln!(code, "    )\n}}\n").unwrap();
+
+    // Signal emitter
+    if !signals.is_empty() {
+        writeln!(
+            code,
+            "/// Emits the signals of the `{name}` interface for an object exported with
+/// [`register_{snake}`].
+#[derive(Clone, Debug)]
+pub struct {base}Emitter {{
+    connection: gio::DBusConnection,
+    object_path: String,
+}}
+
+impl {base}Emitter {{
+    pub fn new(connection: &gio::DBusConnection, object_path: &str) -> Self {{
+        Self {{
+            connection: connection.clone(),
+            object_path: object_path.to_string(),
+        }}
+    }}",
+            name = interface_name,
+            snake = snake,
+            base = base,
+        )
+        .unwrap();
+        for (name, args) in &signals {
+            let parameters = if args.is_empty() {
+                "None".to_string()
+            } else {
+                format!(
+                    "Some(&gio::codegen_to_variant(&{}, {:?})?)",
+                    names(args),
+                    tuple_signature(args)
+                )
+            };
+            writeln!(
+                code,
+                "
+    /// Emits the `{name}` signal.
+    pub fn emit_{snake}(&self{params}) -> Result<(), gio::glib::Error> {{
+        self.connection.emit_signal(
+            None,
+            &self.object_path,
+            {interface:?},
+            {name:?},
+            {parameters},
+        )
+    }}",
+                name = name,
+                snake = to_snake_case(name),
+                params = params(args, true),
+                interface = interface_name,
+                parameters = parameters,
+            )
+            .unwrap();
+        }
+        writeln!(code, "}}\n").unwrap();
+    }
+
+    // Proxy
+    writeln!(
+        code,
+        "/// Client side proxy for the `{name}` D-Bus interface.
+#[derive(Clone, Debug)]
+pub struct {base}Proxy(gio::DBusProxy);
+
+impl {base}Proxy {{
+    pub const INTERFACE_NAME: &'static str = {name:?};
+
+    /// Creates a proxy for the object at `object_path` owned by `name` on `connection`.
+    pub async fn new(
+        connection: &gio::DBusConnection,
+        name: Option<&str>,
+        object_path: &str,
+    ) -> Result<Self, gio::glib::Error> {{
+        let proxy = gio::DBusProxy::new_future(
+            connection,
+            gio::DBusProxyFlags::NONE,
+            Some(&{snake}_interface_info()),
+            name,
+            object_path,
+            Self::INTERFACE_NAME,
+        )
+        .await?;
+        Ok(Self(proxy))
+    }}
+
+    /// Wraps an existing proxy for the `{name}` interface.
+    pub fn from_proxy(proxy: gio::DBusProxy) -> Self {{
+        Self(proxy)
+    }}
+
+    pub fn proxy(&self) -> &gio::DBusProxy {{
+        &self.0
+    }}",
+        name = interface_name,
+        base = base,
+        snake = snake,
+    )
+    .unwrap();
+
+    for (name, in_args, out_args) in &methods {
+        let parameters = if in_args.is_empty() {
+            "None".to_string()
+        } else {
+            format!(
+                "Some(&gio::codegen_to_variant(&{}, {:?})?)",
+                names(in_args),
+                tuple_signature(in_args)
+            )
+        };
+        let ret = match out_args.len() {
+            0 => "let _ = reply;\n        Ok(())".to_string(),
+            1 => format!(
+                "gio::codegen_from_variant::<{}>(&reply, {:?})
+            .map(|ret| ret.0)
+            .ok_or_else(gio::dbus_codegen_invalid_reply)",
+                tuple_type(out_args),
+                tuple_signature(out_args)
+            ),
+            _ => format!(
+                "gio::codegen_from_variant::<{}>(&reply, {:?})
+            .ok_or_else(gio::dbus_codegen_invalid_reply)",
+                tuple_type(out_args),
+                tuple_signature(out_args)
+            ),
+        };
+        writeln!(
+            code,
+            "
+    /// Calls the `{name}` method.
+    pub async fn {ident}(&self{params}) -> Result<{ret_type}, gio::glib::Error> {{
+        let reply = gio::prelude::DBusProxyExt::call_future(
+            &self.0,
+            {name:?},
+            {parameters},
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await?;
+        {ret}
+    }}",
+            name = name,
+            ident = to_ident(name),
+            params = params(in_args, true),
+            ret_type = return_type(out_args),
+            parameters = parameters,
+            ret = ret,
+        )
+        .unwrap();
+    }
+
+    for (name, sig, type_, _, writable) in &properties {
+        writeln!(
+            code,
+            "
+    /// Returns the cached value of the `{name}` property.
+    pub fn {ident}(&self) -> Option<{type_}> {{
+        let value = gio::prelude::DBusProxyExt::get_cached_property(&self.0, {name:?})?;
+        gio::codegen_from_variant(&value, {sig:?})
+    }}",
+            name = name,
+            ident = to_ident(name),
+            type_ = type_,
+            sig = sig,
+        )
+        .unwrap();
+        if *writable {
+            writeln!(
+                code,
+                "
+    /// Sets the `{name}` property.
+    pub async fn set_{ident}(&self, value: {type_}) -> Result<(), gio::glib::Error> {{
+        let connection = gio::prelude::DBusProxyExt::get_connection(&self.0);
+        let bus_name = gio::prelude::DBusProxyExt::get_property_g_name(&self.0);
+        let object_path = gio::prelude::DBusProxyExt::get_object_path(&self.0);
+        let parameters = gio::glib::ToVariant::to_variant(&(
+            Self::INTERFACE_NAME,
+            {name:?},
+            gio::codegen_to_variant(&value, {sig:?})?,
+        ));
+        connection
+            .call_future(
+                bus_name.as_deref(),
+                &object_path,
+                \"org.freedesktop.DBus.Properties\",
+                \"Set\",
+                Some(&parameters),
+                None,
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await?;
+        Ok(())
+    }}",
+                name = name,
+                ident = to_snake_case(name),
+                type_ = type_,
+                sig = sig,
+            )
+            .unwrap();
+        }
+    }
+
+    for (name, args) in &signals {
+        writeln!(
+            code,
+            "
+    /// Returns a stream of the arguments of the `{name}` signal.
+    pub fn receive_{snake}(&self) -> gio::DBusSignalStream<{type_}> {{
+        gio::DBusSignalStream::for_proxy(&self.0, {name:?}, {sig:?})
+    }}",
+            name = name,
+            snake = to_snake_case(name),
+            type_ = tuple_type(args),
+            sig = tuple_signature(args),
+        )
+        .unwrap();
+    }
+    writeln!(code, "}}\n").unwrap();
+
+    Ok(())
+}
+
+impl<T: FromVariant + 'static> DBusSignalStream<T> {
+    #[doc(hidden)]
+    pub fn for_proxy<P: IsA<DBusProxy>>(proxy: &P, signal_name: &str, signature: &str) -> Self {
+        let signature = signature.to_string();
+
+        DBusSignalStream::subscribe(
+            &proxy.get_connection(),
+            proxy.get_property_g_name().as_deref(),
+            Some(proxy.get_interface_name().as_str()),
+            Some(signal_name),
+            Some(proxy.get_object_path().as_str()),
+            None,
+            DBusSignalFlags::NONE,
+            move |_, _, _, _, parameters| crate::codegen_from_variant(parameters, &signature),
+        )
+    }
+}
+
+struct RegisteredObject<M, G, S> {
+    vtable: ffi::GDBusInterfaceVTable,
+    method_call: M,
+    get_property: G,
+    set_property: S,
+}
+
+// Like `DBusConnection::register_object`, but the property handlers can fail with an error that
+// is returned to the caller.
+#[doc(hidden)]
+pub fn dbus_codegen_register_object<M, G, S>(
+    connection: &DBusConnection,
+    object_path: &str,
+    interface_info: &DBusInterfaceInfo,
+    method_call: M,
+    get_property: G,
+    set_property: S,
+) -> Result<RegistrationId, glib::Error>
+where
+    M: Fn(&str, Variant, DBusMethodInvocation) + Send + Sync + 'static,
+    G: Fn(&str) -> Result<Variant, glib::Error> + Send + Sync + 'static,
+    S: Fn(&str, Variant) -> Result<(), glib::Error> + Send + Sync + 'static,
+{
+    unsafe extern "C" fn method_call_trampoline<M, G, S>(
+        _connection: *mut ffi::GDBusConnection,
+        _sender: *const libc::c_char,
+        _object_path: *const libc::c_char,
+        _interface_name: *const libc::c_char,
+        method_name: *const libc::c_char,
+        parameters: *mut glib::ffi::GVariant,
+        invocation: *mut ffi::GDBusMethodInvocation,
+        user_data: glib::ffi::gpointer,
+    ) where
+        M: Fn(&str, Variant, DBusMethodInvocation),
+    {
+        let object = &*(user_data as *const RegisteredObject<M, G, S>);
+        let method_name: Borrowed<glib::GString> = from_glib_borrow(method_name);
+        (object.method_call)(
+            method_name.as_str(),
+            from_glib_none(parameters),
+            from_glib_full(invocation),
+        )
+    }
+
+    unsafe extern "C" fn get_property_trampoline<M, G, S>(
+        _connection: *mut ffi::GDBusConnection,
+        _sender: *const libc::c_char,
+        _object_path: *const libc::c_char,
+        _interface_name: *const libc::c_char,
+        property_name: *const libc::c_char,
+        error: *mut *mut glib::ffi::GError,
+        user_data: glib::ffi::gpointer,
+    ) -> *mut glib::ffi::GVariant
+    where
+        G: Fn(&str) -> Result<Variant, glib::Error>,
+    {
+        let object = &*(user_data as *const RegisteredObject<M, G, S>);
+        let property_name: Borrowed<glib::GString> = from_glib_borrow(property_name);
+        match (object.get_property)(property_name.as_str()) {
+            Ok(value) => value.to_glib_full(),
+            Err(err) => {
+                *error = err.to_glib_full() as *mut _;
+                ptr::null_mut()
+            }
+        }
+    }
+
+    unsafe extern "C" fn set_property_trampoline<M, G, S>(
+        _connection: *mut ffi::GDBusConnection,
+        _sender: *const libc::c_char,
+        _object_path: *const libc::c_char,
+        _interface_name: *const libc::c_char,
+        property_name: *const libc::c_char,
+        value: *mut glib::ffi::GVariant,
+        error: *mut *mut glib::ffi::GError,
+        user_data: glib::ffi::gpointer,
+    ) -> glib::ffi::gboolean
+    where
+        S: Fn(&str, Variant) -> Result<(), glib::Error>,
+    {
+        let object = &*(user_data as *const RegisteredObject<M, G, S>);
+        let property_name: Borrowed<glib::GString> = from_glib_borrow(property_name);
+        match (object.set_property)(property_name.as_str(), from_glib_none(value)) {
+            Ok(()) => glib::ffi::GTRUE,
+            Err(err) => {
+                *error = err.to_glib_full() as *mut _;
+                glib::ffi::GFALSE
+            }
+        }
+    }
+
+    unsafe extern "C" fn destroy<M, G, S>(user_data: glib::ffi::gpointer) {
+        drop(Box::from_raw(user_data as *mut RegisteredObject<M, G, S>));
+    }
+
+    unsafe {
+        // The vtable lives in the user data, which GIO keeps alive until the object is
+        // unregistered
+        let object = Box::into_raw(Box::new(RegisteredObject {
+            vtable: ffi::GDBusInterfaceVTable {
+                method_call: Some(method_call_trampoline::<M, G, S>),
+                get_property: Some(get_property_trampoline::<M, G, S>),
+                set_property: Some(set_property_trampoline::<M, G, S>),
+                padding: [ptr::null_mut(); 8],
+            },
+            method_call,
+            get_property,
+            set_property,
+        }));
+        let mut error = ptr::null_mut();
+        let id = ffi::g_dbus_connection_register_object(
+            connection.to_glib_none().0,
+            object_path.to_glib_none().0,
+            interface_info.to_glib_none().0,
+            &(*object).vtable,
+            object as glib::ffi::gpointer,
+            Some(destroy::<M, G, S>),
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(RegistrationId(NonZeroU32::new_unchecked(id)))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn dbus_codegen_set_property<T: FromVariant>(
+    property_name: &str,
+    value: &Variant,
+    signature: &str,
+    set: impl FnOnce(T) -> bool,
+) -> Result<(), glib::Error> {
+    if crate::codegen_from_variant(value, signature).map_or(false, set) {
+        Ok(())
+    } else {
+        Err(glib::Error::new(
+            IOErrorEnum::InvalidArgument,
+            &format!("Invalid value for property {}", property_name),
+        ))
+    }
+}
+
+#[doc(hidden)]
+pub fn dbus_codegen_unknown_property(property_name: &str) -> glib::Error {
+    glib::Error::new(
+        IOErrorEnum::NotFound,
+        &format!("Unknown property {}", property_name),
+    )
+}
+
+#[doc(hidden)]
+pub fn dbus_codegen_invalid_reply() -> glib::Error {
+    glib::Error::new(IOErrorEnum::InvalidData, "Unexpected D-Bus reply type")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = r#"
+<node>
+  <interface name="org.example.Calculator">
+    <method name="Add">
+      <arg name="a" type="i" direction="in"/>
+      <arg name="b" type="i" direction="in"/>
+      <arg name="result" type="i" direction="out"/>
+    </method>
+    <method name="Reset"/>
+    <method name="GetHistory">
+      <arg name="entries" type="a(so)" direction="out"/>
+      <arg name="meta" type="a{sv}" direction="out"/>
+    </method>
+    <signal name="ResultReady">
+      <arg name="value" type="i"/>
+    </signal>
+    <property name="Precision" type="u" access="readwrite"/>
+    <property name="Name" type="s" access="read"/>
+  </interface>
+</node>
+"#;
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("GetAll"), "get_all");
+        assert_eq!(to_snake_case("HTTPGet"), "http_get");
+        assert_eq!(to_snake_case("value2Set"), "value2_set");
+        assert_eq!(to_ident("Type"), "r#type");
+        assert_eq!(to_ident("self"), "self_");
+    }
+
+    #[test]
+    fn generate() {
+        let code = generate_dbus_interfaces(XML).unwrap();
+
+        assert!(code.contains("pub trait Calculator: Send + Sync + 'static {"));
+        assert!(code.contains("fn add(&self, a: i32, b: i32) -> Result<i32, gio::glib::Error>;"));
+        assert!(code.contains("fn reset(&self) -> Result<(), gio::glib::Error>;"));
+        assert!(code.contains(
+            "fn get_history(&self) -> Result<(Vec<(String, String,)>, std::collections::HashMap<String, gio::glib::Variant>,), gio::glib::Error>;"
+        ));
+        assert!(code.contains("fn precision(&self) -> u32;"));
+        assert!(code.contains("fn set_precision(&self, value: u32) -> bool;"));
+        assert!(code.contains("fn name(&self) -> String;"));
+        assert!(!code.contains("fn set_name("));
+
+        assert!(code.contains("pub fn register_calculator<T: Calculator>("));
+        assert!(code.contains("pub struct CalculatorEmitter {"));
+        assert!(code.contains("pub fn emit_result_ready(&self, value: i32)"));
+
+        assert!(code.contains("pub struct CalculatorProxy(gio::DBusProxy);"));
+        assert!(code.contains(
+            "pub async fn add(&self, a: i32, b: i32) -> Result<i32, gio::glib::Error> {"
+        ));
+        assert!(code.contains("pub fn precision(&self) -> Option<u32> {"));
+        assert!(code.contains("pub async fn set_precision(&self, value: u32)"));
+        assert!(
+            code.contains("pub fn receive_result_ready(&self) -> gio::DBusSignalStream<(i32,)> {")
+        );
+    }
+
+    #[test]
+    fn unsupported_type() {
+        let xml = r#"
+<node>
+  <interface name="org.example.Fd">
+    <method name="Open">
+      <arg name="fd" type="h" direction="out"/>
+    </method>
+  </interface>
+</node>
+"#;
+        assert!(generate_dbus_interfaces(xml).is_err());
+    }
+
+    #[test]
+    fn property_method_collision() {
+        let xml = r#"
+<node>
+  <interface name="org.example.Device">
+    <method name="Name">
+      <arg name="name" type="s" direction="out"/>
+    </method>
+    <property name="Name" type="s" access="read"/>
+  </interface>
+</node>
+"#;
+        let err = generate_dbus_interfaces(xml).unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::InvalidArgument));
+        assert!(err.to_string().contains(
+            "method 'Name' and property 'Name' both map to `name` in the `Device` trait"
+        ));
+
+        let xml = r#"
+<node>
+  <interface name="org.example.Device">
+    <method name="SetLevel">
+      <arg name="level" type="u" direction="in"/>
+    </method>
+    <property name="Level" type="u" access="readwrite"/>
+  </interface>
+</node>
+"#;
+        let err = generate_dbus_interfaces(xml).unwrap_err();
+        assert!(err.to_string().contains(
+            "method 'SetLevel' and property 'Level' both map to `set_level` in the `Device` trait"
+        ));
+    }
+
+    #[test]
+    fn argument_collision() {
+        let xml = r#"
+<node>
+  <interface name="org.example.Device">
+    <method name="Move">
+      <arg name="xPos" type="i" direction="in"/>
+      <arg name="x_pos" type="i" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+        let err = generate_dbus_interfaces(xml).unwrap_err();
+        assert!(err.to_string().contains(
+            "argument 'xPos' and argument 'x_pos' both map to `x_pos` in the arguments of method 'Move'"
+        ));
+    }
+
+    #[test]
+    fn interface_collision() {
+        let xml = r#"
+<node>
+  <interface name="org.example.audio.Device"/>
+  <interface name="org.example.video.Device"/>
+  <interface name="org.example.Player"/>
+</node>
+"#;
+        let code = generate_dbus_interfaces(xml).unwrap();
+        assert!(code.contains("pub trait AudioDevice: Send + Sync + 'static {"));
+        assert!(code.contains("pub fn register_audio_device<T: AudioDevice>("));
+        assert!(code.contains("pub struct AudioDeviceProxy(gio::DBusProxy);"));
+        assert!(code.contains("pub trait VideoDevice: Send + Sync + 'static {"));
+        assert!(code.contains("pub fn register_video_device<T: VideoDevice>("));
+        assert!(code.contains("pub trait Player: Send + Sync + 'static {"));
+
+        let xml = r#"
+<node>
+  <interface name="org.example.Device"/>
+  <interface name="org.Example.device"/>
+</node>
+"#;
+        let err = generate_dbus_interfaces(xml).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("D-Bus interface 'org.example.Device' and D-Bus interface 'org.Example.device' both map to `org_example_device`"));
+    }
+}
@@ -1,6 +1,7 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 use crate::ActionGroup;
+use crate::DBusCallFlags;
 use crate::DBusConnection;
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 use crate::DBusInterfaceInfo;
@@ -8,14 +9,21 @@ use crate::DBusMessage;
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 use crate::DBusMethodInvocation;
 use crate::DBusSignalFlags;
+use crate::IOErrorEnum;
 use crate::MenuModel;
+use futures_channel::mpsc;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
 use glib::object::IsA;
 use glib::translate::*;
+use glib::{FromVariant, StaticVariantType};
 use std::boxed::Box as Box_;
+use std::future::Future;
 use std::num::NonZeroU32;
+use std::pin::Pin;
 
 #[derive(Debug, Eq, PartialEq)]
-pub struct RegistrationId(NonZeroU32);
+pub struct RegistrationId(pub(crate) NonZeroU32);
 #[derive(Debug, Eq, PartialEq)]
 pub struct WatcherId(NonZeroU32);
 #[derive(Debug, Eq, PartialEq)]
@@ -28,6 +36,50 @@ pub struct FilterId(NonZeroU32);
 pub struct SignalSubscriptionId(NonZeroU32);
 
 impl DBusConnection {
+    // rustdoc-stripper-ignore-next
+    /// Like [`call_future`](Self::call_future), but checks that the reply is of type `R` and
+    /// converts it.
+    ///
+    /// ```no_run
+    /// # async fn run(connection: gio::DBusConnection) -> Result<(), glib::Error> {
+    /// let (names,) = connection
+    ///     .call_typed_future::<(Vec<String>,)>(
+    ///         Some("org.freedesktop.DBus"),
+    ///         "/org/freedesktop/DBus",
+    ///         "org.freedesktop.DBus",
+    ///         "ListNames",
+    ///         None,
+    ///         gio::DBusCallFlags::NONE,
+    ///         -1,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn call_typed_future<R: FromVariant + StaticVariantType + 'static>(
+        &self,
+        bus_name: Option<&str>,
+        object_path: &str,
+        interface_name: &str,
+        method_name: &str,
+        parameters: Option<&glib::Variant>,
+        flags: DBusCallFlags,
+        timeout_msec: i32,
+    ) -> Pin<Box_<dyn Future<Output = Result<R, glib::Error>> + 'static>> {
+        let reply_type = R::static_variant_type();
+        let reply = self.call_future(
+            bus_name,
+            object_path,
+            interface_name,
+            method_name,
+            parameters,
+            Some(&reply_type),
+            flags,
+            timeout_msec,
+        );
+        Box_::pin(async move { typed_reply(reply.await?) })
+    }
+
     #[cfg(any(feature = "v2_46", feature = "dox"))]
     #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_46")))]
     pub fn register_object<MethodCall, SetProperty, GetProperty>(
@@ -312,4 +364,223 @@ impl DBusConnection {
             );
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Like [`signal_subscribe`](Self::signal_subscribe), but returns a stream of
+    /// `(sender_name, object_path, interface_name, signal_name, parameters)` instead of calling
+    /// a closure.
+    ///
+    /// The subscription is removed when the stream is dropped.
+    pub fn signal_stream(
+        &self,
+        sender: Option<&str>,
+        interface_name: Option<&str>,
+        member: Option<&str>,
+        object_path: Option<&str>,
+        arg0: Option<&str>,
+        flags: DBusSignalFlags,
+    ) -> DBusSignalStream<(String, String, String, String, glib::Variant)> {
+        DBusSignalStream::subscribe(
+            self,
+            sender,
+            interface_name,
+            member,
+            object_path,
+            arg0,
+            flags,
+            |sender_name, object_path, interface_name, signal_name, parameters| {
+                Some((
+                    sender_name.to_string(),
+                    object_path.to_string(),
+                    interface_name.to_string(),
+                    signal_name.to_string(),
+                    parameters.clone(),
+                ))
+            },
+        )
+    }
+}
+
+pub(crate) fn typed_reply<R: FromVariant + StaticVariantType>(
+    reply: glib::Variant,
+) -> Result<R, glib::Error> {
+    if !reply.is::<R>() {
+        return Err(glib::Error::new(
+            IOErrorEnum::InvalidData,
+            &format!(
+                "Expected D-Bus reply of type '{}', got '{}'",
+                R::static_variant_type().to_str(),
+                reply.type_().to_str()
+            ),
+        ));
+    }
+    reply
+        .get::<R>()
+        .ok_or_else(|| glib::Error::new(IOErrorEnum::InvalidData, "Failed to convert D-Bus reply"))
+}
+
+/// A stream of D-Bus signals.
+///
+/// The signal subscription is removed when the stream is dropped.
+#[derive(Debug)]
+pub struct DBusSignalStream<T> {
+    connection: DBusConnection,
+    subscription_id: Option<SignalSubscriptionId>,
+    receiver: mpsc::UnboundedReceiver<T>,
+}
+
+impl<T: 'static> DBusSignalStream<T> {
+    pub(crate) fn subscribe<F>(
+        connection: &DBusConnection,
+        sender: Option<&str>,
+        interface_name: Option<&str>,
+        member: Option<&str>,
+        object_path: Option<&str>,
+        arg0: Option<&str>,
+        flags: DBusSignalFlags,
+        map: F,
+    ) -> Self
+    where
+        F: Fn(&str, &str, &str, &str, &glib::Variant) -> Option<T> + 'static,
+    {
+        let (item_sender, receiver) = mpsc::unbounded();
+
+        let subscription_id = connection.signal_subscribe(
+            sender,
+            interface_name,
+            member,
+            object_path,
+            arg0,
+            flags,
+            move |_, sender_name, object_path, interface_name, signal_name, parameters| {
+                if let Some(item) = map(
+                    sender_name,
+                    object_path,
+                    interface_name,
+                    signal_name,
+                    parameters,
+                ) {
+                    let _ = item_sender.unbounded_send(item);
+                }
+            },
+        );
+
+        DBusSignalStream {
+            connection: connection.clone(),
+            subscription_id: Some(subscription_id),
+            receiver,
+        }
+    }
+}
+
+impl<T> Stream for DBusSignalStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<T>> {
+        Pin::new(&mut self.receiver).poll_next(ctx)
+    }
+}
+
+impl<T> Drop for DBusSignalStream<T> {
+    fn drop(&mut self) {
+        if let Some(subscription_id) = self.subscription_id.take() {
+            self.connection.signal_unsubscribe(subscription_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_bus, test_bus_connection};
+    use futures_util::StreamExt;
+    use glib::ToVariant;
+
+    fn list_names(connection: &DBusConnection) -> Result<(Vec<String>,), glib::Error> {
+        glib::MainContext::new().block_on(connection.call_typed_future::<(Vec<String>,)>(
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "ListNames",
+            None,
+            DBusCallFlags::NONE,
+            -1,
+        ))
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn call_typed_future() {
+        let bus = test_bus();
+        let connection = test_bus_connection(&bus);
+
+        let (names,) = list_names(&connection).unwrap();
+        let unique_name = connection.get_unique_name().unwrap();
+        assert!(names.iter().any(|name| name == unique_name.as_str()));
+
+        let err = glib::MainContext::new()
+            .block_on(connection.call_typed_future::<(u32,)>(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                "org.freedesktop.DBus",
+                "GetNameOwner",
+                Some(&("org.freedesktop.DBus",).to_variant()),
+                DBusCallFlags::NONE,
+                -1,
+            ))
+            .unwrap_err();
+        assert_eq!(
+            err.kind::<IOErrorEnum>(),
+            Some(IOErrorEnum::InvalidArgument)
+        );
+
+        drop(connection);
+        bus.down();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn signal_stream() {
+        let bus = test_bus();
+        let receiver = test_bus_connection(&bus);
+        let emitter = test_bus_connection(&bus);
+        let c = glib::MainContext::new();
+
+        let mut signals = c.with_thread_default(|| {
+            receiver.signal_stream(
+                None,
+                Some("org.example.Test"),
+                Some("Changed"),
+                Some("/org/example/Test"),
+                None,
+                DBusSignalFlags::NONE,
+            )
+        });
+        // The match rule is added asynchronously, a round trip makes sure that the bus has it
+        list_names(&receiver).unwrap();
+
+        emitter
+            .emit_signal(
+                None,
+                "/org/example/Test",
+                "org.example.Test",
+                "Changed",
+                Some(&(42i32,).to_variant()),
+            )
+            .unwrap();
+
+        let (sender_name, object_path, interface_name, signal_name, parameters) = c
+            .with_thread_default(|| c.block_on(signals.next()))
+            .unwrap();
+        assert_eq!(sender_name, emitter.get_unique_name().unwrap().as_str());
+        assert_eq!(object_path, "/org/example/Test");
+        assert_eq!(interface_name, "org.example.Test");
+        assert_eq!(signal_name, "Changed");
+        assert_eq!(parameters.get::<(i32,)>(), Some((42,)));
+
+        drop(signals);
+        drop(receiver);
+        drop(emitter);
+        bus.down();
+    }
 }
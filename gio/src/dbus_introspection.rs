@@ -0,0 +1,157 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::DBusArgInfo;
+use crate::DBusInterfaceInfo;
+use crate::DBusMethodInfo;
+use crate::DBusNodeInfo;
+use crate::DBusPropertyInfo;
+use crate::DBusSignalInfo;
+use glib::translate::*;
+use std::ffi::CStr;
+
+unsafe fn borrow_str<'a>(ptr: *const libc::c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(ptr).to_str().unwrap())
+    }
+}
+
+impl DBusNodeInfo {
+    pub fn path(&self) -> Option<&str> {
+        unsafe {
+            let ptr: *mut ffi::GDBusNodeInfo = self.to_glib_none().0;
+            borrow_str((*ptr).path)
+        }
+    }
+
+    pub fn interfaces(&self) -> Vec<DBusInterfaceInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusNodeInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).interfaces)
+        }
+    }
+
+    pub fn nodes(&self) -> Vec<DBusNodeInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusNodeInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).nodes)
+        }
+    }
+}
+
+impl DBusInterfaceInfo {
+    pub fn name(&self) -> &str {
+        unsafe {
+            let ptr: *mut ffi::GDBusInterfaceInfo = self.to_glib_none().0;
+            borrow_str((*ptr).name).unwrap()
+        }
+    }
+
+    pub fn methods(&self) -> Vec<DBusMethodInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusInterfaceInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).methods)
+        }
+    }
+
+    pub fn signals(&self) -> Vec<DBusSignalInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusInterfaceInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).signals)
+        }
+    }
+
+    pub fn properties(&self) -> Vec<DBusPropertyInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusInterfaceInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).properties)
+        }
+    }
+}
+
+impl DBusMethodInfo {
+    pub fn name(&self) -> &str {
+        unsafe {
+            let ptr: *mut ffi::GDBusMethodInfo = self.to_glib_none().0;
+            borrow_str((*ptr).name).unwrap()
+        }
+    }
+
+    pub fn in_args(&self) -> Vec<DBusArgInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusMethodInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).in_args)
+        }
+    }
+
+    pub fn out_args(&self) -> Vec<DBusArgInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusMethodInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).out_args)
+        }
+    }
+}
+
+impl DBusSignalInfo {
+    pub fn name(&self) -> &str {
+        unsafe {
+            let ptr: *mut ffi::GDBusSignalInfo = self.to_glib_none().0;
+            borrow_str((*ptr).name).unwrap()
+        }
+    }
+
+    pub fn args(&self) -> Vec<DBusArgInfo> {
+        unsafe {
+            let ptr: *mut ffi::GDBusSignalInfo = self.to_glib_none().0;
+            FromGlibPtrContainer::from_glib_none((*ptr).args)
+        }
+    }
+}
+
+impl DBusPropertyInfo {
+    pub fn name(&self) -> &str {
+        unsafe {
+            let ptr: *mut ffi::GDBusPropertyInfo = self.to_glib_none().0;
+            borrow_str((*ptr).name).unwrap()
+        }
+    }
+
+    pub fn signature(&self) -> &str {
+        unsafe {
+            let ptr: *mut ffi::GDBusPropertyInfo = self.to_glib_none().0;
+            borrow_str((*ptr).signature).unwrap()
+        }
+    }
+
+    pub fn is_readable(&self) -> bool {
+        unsafe {
+            let ptr: *mut ffi::GDBusPropertyInfo = self.to_glib_none().0;
+            (*ptr).flags & ffi::G_DBUS_PROPERTY_INFO_FLAGS_READABLE != 0
+        }
+    }
+
+    pub fn is_writable(&self) -> bool {
+        unsafe {
+            let ptr: *mut ffi::GDBusPropertyInfo = self.to_glib_none().0;
+            (*ptr).flags & ffi::G_DBUS_PROPERTY_INFO_FLAGS_WRITABLE != 0
+        }
+    }
+}
+
+impl DBusArgInfo {
+    /// Returns the name of the argument, if the introspection data provided one.
+    pub fn name(&self) -> Option<&str> {
+        unsafe {
+            let ptr: *mut ffi::GDBusArgInfo = self.to_glib_none().0;
+            borrow_str((*ptr).name)
+        }
+    }
+
+    pub fn signature(&self) -> &str {
+        unsafe {
+            let ptr: *mut ffi::GDBusArgInfo = self.to_glib_none().0;
+            borrow_str((*ptr).signature).unwrap()
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::dbus_connection::typed_reply;
+use crate::prelude::*;
+use crate::DBusCallFlags;
+use crate::DBusProxy;
+use glib::object::IsA;
+use glib::{FromVariant, StaticVariantType};
+use std::boxed::Box as Box_;
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait DBusProxyExtManual: 'static {
+    // rustdoc-stripper-ignore-next
+    /// Like [`call_future`](crate::prelude::DBusProxyExt::call_future), but checks that the
+    /// reply is of type `R` and converts it.
+    fn call_typed_future<R: FromVariant + StaticVariantType + 'static>(
+        &self,
+        method_name: &str,
+        parameters: Option<&glib::Variant>,
+        flags: DBusCallFlags,
+        timeout_msec: i32,
+    ) -> Pin<Box_<dyn Future<Output = Result<R, glib::Error>> + 'static>>;
+}
+
+impl<O: IsA<DBusProxy>> DBusProxyExtManual for O {
+    fn call_typed_future<R: FromVariant + StaticVariantType + 'static>(
+        &self,
+        method_name: &str,
+        parameters: Option<&glib::Variant>,
+        flags: DBusCallFlags,
+        timeout_msec: i32,
+    ) -> Pin<Box_<dyn Future<Output = Result<R, glib::Error>> + 'static>> {
+        let reply = self.call_future(method_name, parameters, flags, timeout_msec);
+        Box_::pin(async move { typed_reply(reply.await?) })
+    }
+}
@@ -23,9 +23,13 @@ impl From<IOErrorEnum> for io::ErrorKind {
     }
 }
 
-pub(crate) fn to_std_io_result<T>(result: Result<T, glib::Error>) -> io::Result<T> {
-    result.map_err(|g_error| match g_error.kind::<IOErrorEnum>() {
+pub(crate) fn to_std_io_error(g_error: glib::Error) -> io::Error {
+    match g_error.kind::<IOErrorEnum>() {
         Some(io_error_enum) => io::Error::new(io_error_enum.into(), g_error),
         None => io::Error::new(io::ErrorKind::Other, g_error),
-    })
+    }
+}
+
+pub(crate) fn to_std_io_result<T>(result: Result<T, glib::Error>) -> io::Result<T> {
+    result.map_err(to_std_io_error)
 }
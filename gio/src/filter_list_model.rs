@@ -0,0 +1,242 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::subclass::prelude::*;
+use crate::ListModel;
+
+use std::cell::RefCell;
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct FilterListModel {
+        pub(super) model: RefCell<Option<ListModel>>,
+        pub(super) handler: RefCell<Option<glib::SignalHandlerId>>,
+        pub(super) filter_func: RefCell<Option<Box<dyn Fn(&glib::Object) -> bool>>>,
+        // Sorted positions of the matching items in the underlying model
+        pub(super) matches: RefCell<Vec<u32>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for FilterListModel {
+        const NAME: &'static str = "FilterListModel";
+        type Type = super::FilterListModel;
+        type ParentType = glib::Object;
+        type Interfaces = (ListModel,);
+    }
+
+    impl ObjectImpl for FilterListModel {
+        fn dispose(&self, _obj: &Self::Type) {
+            if let (Some(model), Some(handler)) = (
+                self.model.borrow_mut().take(),
+                self.handler.borrow_mut().take(),
+            ) {
+                model.disconnect(handler);
+            }
+        }
+    }
+
+    impl ListModelImpl for FilterListModel {
+        fn get_item_type(&self, _list_model: &Self::Type) -> glib::Type {
+            self.model
+                .borrow()
+                .as_ref()
+                .map(|model| model.get_item_type())
+                .unwrap_or_else(glib::Object::static_type)
+        }
+
+        fn get_n_items(&self, _list_model: &Self::Type) -> u32 {
+            self.matches.borrow().len() as u32
+        }
+
+        fn get_item(&self, _list_model: &Self::Type, position: u32) -> Option<glib::Object> {
+            let source_position = *self.matches.borrow().get(position as usize)?;
+            let model = self.model.borrow().clone()?;
+            model.get_object(source_position)
+        }
+    }
+
+    impl FilterListModel {
+        // Returns the positions of the matching items of the underlying model in the given range
+        pub(super) fn filter_range(&self, position: u32, n_items: u32) -> Vec<u32> {
+            let model = match self.model.borrow().clone() {
+                Some(model) => model,
+                None => return Vec::new(),
+            };
+
+            (position..position + n_items)
+                .filter(|&i| match model.get_object(i) {
+                    Some(item) => match *self.filter_func.borrow() {
+                        Some(ref filter_func) => filter_func(&item),
+                        None => true,
+                    },
+                    None => false,
+                })
+                .collect()
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`ListModel`] containing the items of another model for which a filter function returns
+    /// `true`.
+    ///
+    /// Changes of the underlying model are forwarded as the smallest possible `items-changed`
+    /// range. If the result of the filter function changes for some items, call
+    /// [`refilter`](Self::refilter).
+    pub struct FilterListModel(ObjectSubclass<imp::FilterListModel>) @implements ListModel;
+}
+
+impl FilterListModel {
+    pub fn new<P: IsA<ListModel>, F: Fn(&glib::Object) -> bool + 'static>(
+        model: &P,
+        filter_func: F,
+    ) -> FilterListModel {
+        let obj: FilterListModel =
+            glib::Object::new(&[]).expect("Failed to create filter list model");
+
+        let imp = imp::FilterListModel::from_instance(&obj);
+        let model = model.as_ref().clone();
+        *imp.filter_func.borrow_mut() = Some(Box::new(filter_func));
+        *imp.model.borrow_mut() = Some(model.clone());
+        *imp.matches.borrow_mut() = imp.filter_range(0, model.get_n_items());
+
+        let obj_weak = obj.downgrade();
+        let handler = model.connect_items_changed(move |_, position, removed, added| {
+            if let Some(obj) = obj_weak.upgrade() {
+                obj.items_changed_source(position, removed, added);
+            }
+        });
+        *imp.handler.borrow_mut() = Some(handler);
+
+        obj
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the underlying model.
+    pub fn model(&self) -> Option<ListModel> {
+        let imp = imp::FilterListModel::from_instance(self);
+        imp.model.borrow().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Replaces the filter function and filters all items again.
+    pub fn set_filter_func<F: Fn(&glib::Object) -> bool + 'static>(&self, filter_func: F) {
+        let imp = imp::FilterListModel::from_instance(self);
+        *imp.filter_func.borrow_mut() = Some(Box::new(filter_func));
+        self.refilter();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Filters all items again, e.g. after the properties the filter function depends on
+    /// changed.
+    pub fn refilter(&self) {
+        let imp = imp::FilterListModel::from_instance(self);
+        let n_items = imp
+            .model
+            .borrow()
+            .as_ref()
+            .map_or(0, |model| model.get_n_items());
+        let matches = imp.filter_range(0, n_items);
+
+        let changed = changed_range(&imp.matches.borrow(), &matches);
+        *imp.matches.borrow_mut() = matches;
+        if let Some((position, removed, added)) = changed {
+            self.items_changed(position, removed, added);
+        }
+    }
+
+    fn items_changed_source(&self, position: u32, removed: u32, added: u32) {
+        let imp = imp::FilterListModel::from_instance(self);
+        let added_matches = imp.filter_range(position, added);
+        let n_added = added_matches.len() as u32;
+
+        let (start, n_removed) = {
+            let mut matches = imp.matches.borrow_mut();
+            let start = match matches.binary_search(&position) {
+                Ok(i) | Err(i) => i,
+            };
+            let end = match matches.binary_search(&(position + removed)) {
+                Ok(i) | Err(i) => i,
+            };
+
+            for p in &mut matches[end..] {
+                *p = *p - removed + added;
+            }
+            matches.splice(start..end, added_matches);
+
+            (start as u32, (end - start) as u32)
+        };
+
+        if n_removed > 0 || n_added > 0 {
+            self.items_changed(start, n_removed, n_added);
+        }
+    }
+}
+
+// Returns the single `items-changed` range turning `old` into `new`, as
+// `(position, removed, added)`, or `None` if both are equal.
+pub(crate) fn changed_range<T: PartialEq>(old: &[T], new: &[T]) -> Option<(u32, u32, u32)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    if prefix == old.len() && prefix == new.len() {
+        return None;
+    }
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    Some((
+        prefix as u32,
+        (old.len() - prefix - suffix) as u32,
+        (new.len() - prefix - suffix) as u32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{
+        list_item, list_item_value, list_model_values, list_store_with, record_items_changed,
+    };
+
+    #[test]
+    fn test_changed_range() {
+        assert_eq!(changed_range(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(changed_range(&[1, 2, 3], &[1, 3]), Some((1, 1, 0)));
+        assert_eq!(changed_range(&[1, 3], &[1, 2, 2, 3]), Some((1, 0, 2)));
+        assert_eq!(changed_range(&[1, 2], &[3, 4]), Some((0, 2, 2)));
+        assert_eq!(changed_range::<i32>(&[], &[1]), Some((0, 0, 1)));
+    }
+
+    #[test]
+    fn test_filter() {
+        let store = list_store_with(&[1, 2, 3, 4, 5, 6]);
+        let model = FilterListModel::new(&store, |item| list_item_value(item) % 2 == 0);
+        let changes = record_items_changed(&model);
+        assert_eq!(list_model_values(&model), vec![2, 4, 6]);
+
+        // Removing a filtered out item is not visible
+        store.remove(0);
+        assert_eq!(list_model_values(&model), vec![2, 4, 6]);
+        assert!(changes.borrow().is_empty());
+
+        // Positions after a change are shifted
+        store.insert(1, &list_item(8));
+        assert_eq!(list_model_values(&model), vec![2, 8, 4, 6]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 0, 1)));
+
+        store.remove(3);
+        assert_eq!(list_model_values(&model), vec![2, 8, 6]);
+        assert_eq!(changes.borrow_mut().pop(), Some((2, 1, 0)));
+
+        model.set_filter_func(|item| list_item_value(item) > 5);
+        assert_eq!(list_model_values(&model), vec![8, 6]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 1, 0)));
+    }
+}
@@ -0,0 +1,231 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::subclass::prelude::*;
+use crate::ListModel;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+// A model contained in the flattened model
+struct Child {
+    model: ListModel,
+    handler: glib::SignalHandlerId,
+    n_items: Rc<Cell<u32>>,
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct FlattenListModel {
+        pub(super) model: RefCell<Option<ListModel>>,
+        pub(super) handler: RefCell<Option<glib::SignalHandlerId>>,
+        pub(super) children: RefCell<Vec<Child>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for FlattenListModel {
+        const NAME: &'static str = "FlattenListModel";
+        type Type = super::FlattenListModel;
+        type ParentType = glib::Object;
+        type Interfaces = (ListModel,);
+    }
+
+    impl ObjectImpl for FlattenListModel {
+        fn dispose(&self, _obj: &Self::Type) {
+            if let (Some(model), Some(handler)) = (
+                self.model.borrow_mut().take(),
+                self.handler.borrow_mut().take(),
+            ) {
+                model.disconnect(handler);
+            }
+            for child in self.children.borrow_mut().drain(..) {
+                child.model.disconnect(child.handler);
+            }
+        }
+    }
+
+    impl ListModelImpl for FlattenListModel {
+        fn get_item_type(&self, _list_model: &Self::Type) -> glib::Type {
+            glib::Object::static_type()
+        }
+
+        fn get_n_items(&self, _list_model: &Self::Type) -> u32 {
+            self.children
+                .borrow()
+                .iter()
+                .map(|child| child.n_items.get())
+                .sum()
+        }
+
+        fn get_item(&self, _list_model: &Self::Type, mut position: u32) -> Option<glib::Object> {
+            let model = self.children.borrow().iter().find_map(|child| {
+                if position < child.n_items.get() {
+                    Some(child.model.clone())
+                } else {
+                    position -= child.n_items.get();
+                    None
+                }
+            })?;
+            model.get_object(position)
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`ListModel`] containing the items of all models contained in another model, one after
+    /// another.
+    ///
+    /// All items of the underlying model must be [`ListModel`]s. Changes of the underlying model
+    /// and of the contained models are forwarded as the corresponding `items-changed` range.
+    pub struct FlattenListModel(ObjectSubclass<imp::FlattenListModel>) @implements ListModel;
+}
+
+impl FlattenListModel {
+    pub fn new<P: IsA<ListModel>>(model: &P) -> FlattenListModel {
+        let obj: FlattenListModel =
+            glib::Object::new(&[]).expect("Failed to create flatten list model");
+
+        let imp = imp::FlattenListModel::from_instance(&obj);
+        let model = model.as_ref().clone();
+        *imp.model.borrow_mut() = Some(model.clone());
+        *imp.children.borrow_mut() = (0..model.get_n_items())
+            .map(|i| obj.create_child(&model, i))
+            .collect();
+
+        let obj_weak = obj.downgrade();
+        let handler = model.connect_items_changed(move |model, position, removed, added| {
+            if let Some(obj) = obj_weak.upgrade() {
+                obj.items_changed_source(model, position, removed, added);
+            }
+        });
+        *imp.handler.borrow_mut() = Some(handler);
+
+        obj
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the underlying model.
+    pub fn model(&self) -> Option<ListModel> {
+        let imp = imp::FlattenListModel::from_instance(self);
+        imp.model.borrow().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the position of the contained model at `position` of the underlying model
+    /// inside this model.
+    pub fn model_offset(&self, position: u32) -> u32 {
+        let imp = imp::FlattenListModel::from_instance(self);
+        imp.children
+            .borrow()
+            .iter()
+            .take(position as usize)
+            .map(|child| child.n_items.get())
+            .sum()
+    }
+
+    fn create_child(&self, model: &ListModel, position: u32) -> Child {
+        let model = model
+            .get_object(position)
+            .expect("Item not found")
+            .downcast::<ListModel>()
+            .expect("Item is not a ListModel");
+        let n_items = Rc::new(Cell::new(model.get_n_items()));
+
+        let obj_weak = self.downgrade();
+        let n_items_clone = n_items.clone();
+        let handler = model.connect_items_changed(move |_, position, removed, added| {
+            if let Some(obj) = obj_weak.upgrade() {
+                // The child is identified by its item counter, as the same model might be
+                // contained several times
+                let imp = imp::FlattenListModel::from_instance(&obj);
+                let offset: u32 = imp
+                    .children
+                    .borrow()
+                    .iter()
+                    .take_while(|child| !Rc::ptr_eq(&child.n_items, &n_items_clone))
+                    .map(|child| child.n_items.get())
+                    .sum();
+                n_items_clone.set(n_items_clone.get() - removed + added);
+                obj.items_changed(offset + position, removed, added);
+            }
+        });
+
+        Child {
+            model,
+            handler,
+            n_items,
+        }
+    }
+
+    fn items_changed_source(&self, model: &ListModel, position: u32, removed: u32, added: u32) {
+        let imp = imp::FlattenListModel::from_instance(self);
+        let offset = self.model_offset(position);
+
+        let new_children = (position..position + added)
+            .map(|i| self.create_child(model, i))
+            .collect::<Vec<_>>();
+        let n_added: u32 = new_children.iter().map(|child| child.n_items.get()).sum();
+
+        let old_children = imp
+            .children
+            .borrow_mut()
+            .splice(
+                position as usize..(position + removed) as usize,
+                new_children,
+            )
+            .collect::<Vec<_>>();
+        let mut n_removed = 0;
+        for child in old_children {
+            n_removed += child.n_items.get();
+            child.model.disconnect(child.handler);
+        }
+
+        if n_removed > 0 || n_added > 0 {
+            self.items_changed(offset, n_removed, n_added);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{list_item, list_model_values, list_store_with, record_items_changed};
+    use crate::ListStore;
+
+    #[test]
+    fn test_flatten() {
+        let first = list_store_with(&[1, 2]);
+        let second = list_store_with(&[3]);
+        let models = ListStore::new(ListModel::static_type());
+        models.append(&first);
+        models.append(&second);
+
+        let model = FlattenListModel::new(&models);
+        let changes = record_items_changed(&model);
+        assert_eq!(list_model_values(&model), vec![1, 2, 3]);
+        assert_eq!(model.model_offset(1), 2);
+
+        second.append(&list_item(4));
+        assert_eq!(list_model_values(&model), vec![1, 2, 3, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((3, 0, 1)));
+
+        first.remove(0);
+        assert_eq!(list_model_values(&model), vec![2, 3, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 1, 0)));
+
+        models.insert(1, &list_store_with(&[5, 6]));
+        assert_eq!(list_model_values(&model), vec![2, 5, 6, 3, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 0, 2)));
+
+        models.remove(0);
+        assert_eq!(list_model_values(&model), vec![5, 6, 3, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 1, 0)));
+
+        // Removed models are not tracked anymore
+        first.append(&list_item(7));
+        assert!(changes.borrow().is_empty());
+    }
+}
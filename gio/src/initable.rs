@@ -0,0 +1,148 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::Cancellable;
+use glib::object::{IsA, IsClass};
+use glib::translate::*;
+use glib::{Object, ToValue};
+use std::error;
+use std::fmt;
+use std::ptr;
+
+glib::wrapper! {
+    pub struct Initable(Interface<ffi::GInitable, ffi::GInitableIface>);
+
+    match fn {
+        get_type => || ffi::g_initable_get_type(),
+    }
+}
+
+pub const NONE_INITABLE: Option<&Initable> = None;
+
+pub trait InitableExt: 'static {
+    /// Initializes the object.
+    ///
+    /// # Safety
+    ///
+    /// This must only be called once, right after constructing the object and before
+    /// using it in any other way.
+    #[doc(alias = "g_initable_init")]
+    unsafe fn init<P: IsA<Cancellable>>(&self, cancellable: Option<&P>) -> Result<(), glib::Error>;
+}
+
+impl<O: IsA<Initable>> InitableExt for O {
+    unsafe fn init<P: IsA<Cancellable>>(&self, cancellable: Option<&P>) -> Result<(), glib::Error> {
+        let mut error = ptr::null_mut();
+        let _ = ffi::g_initable_init(
+            self.as_ref().to_glib_none().0,
+            cancellable.map(|p| p.as_ref()).to_glib_none().0,
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(())
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+impl fmt::Display for Initable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Initable")
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Error returned when constructing an [`Initable`] or [`AsyncInitable`](crate::AsyncInitable)
+/// object fails.
+#[derive(Debug)]
+pub enum InitableError {
+    /// The object could not be created, e.g. because of invalid properties.
+    NewObjectFailed(glib::BoolError),
+    /// The object was created but its initialization failed.
+    Failed(glib::Error),
+}
+
+impl fmt::Display for InitableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InitableError::NewObjectFailed(err) => write!(f, "Object::new failed with {}", err),
+            InitableError::Failed(err) => write!(f, "Initialization failed with {}", err),
+        }
+    }
+}
+
+impl error::Error for InitableError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            InitableError::NewObjectFailed(err) => Some(err),
+            InitableError::Failed(err) => Some(err),
+        }
+    }
+}
+
+impl From<glib::BoolError> for InitableError {
+    fn from(err: glib::BoolError) -> Self {
+        InitableError::NewObjectFailed(err)
+    }
+}
+
+impl From<glib::Error> for InitableError {
+    fn from(err: glib::Error) -> Self {
+        InitableError::Failed(err)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Constructors for objects implementing [`Initable`] or [`AsyncInitable`](crate::AsyncInitable).
+pub trait InitableObjectExt {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new instance of `T` with the given properties and initializes it, like
+    /// `g_initable_new()`.
+    fn new_initable<T: IsA<Object> + IsClass + IsA<Initable>>(
+        properties: &[(&str, &dyn ToValue)],
+        cancellable: Option<&Cancellable>,
+    ) -> Result<T, InitableError>;
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new instance of `T` with the given properties and asynchronously initializes
+    /// it, like `g_async_initable_new_async()`.
+    fn new_async_initable<T: IsA<Object> + IsClass + IsA<crate::AsyncInitable>>(
+        properties: &[(&str, &dyn ToValue)],
+        io_priority: glib::Priority,
+        cancellable: Option<&Cancellable>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, InitableError>> + 'static>>;
+}
+
+impl InitableObjectExt for Object {
+    fn new_initable<T: IsA<Object> + IsClass + IsA<Initable>>(
+        properties: &[(&str, &dyn ToValue)],
+        cancellable: Option<&Cancellable>,
+    ) -> Result<T, InitableError> {
+        let obj = Object::new::<T>(properties)?;
+        unsafe { obj.init(cancellable)? };
+        Ok(obj)
+    }
+
+    fn new_async_initable<T: IsA<Object> + IsClass + IsA<crate::AsyncInitable>>(
+        properties: &[(&str, &dyn ToValue)],
+        io_priority: glib::Priority,
+        cancellable: Option<&Cancellable>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, InitableError>> + 'static>>
+    {
+        use crate::prelude::AsyncInitableExt;
+
+        let obj = Object::new::<T>(properties);
+        let cancellable = cancellable.cloned();
+        Box::pin(async move {
+            let obj = obj?;
+            let (sender, receiver) = futures_channel::oneshot::channel();
+            unsafe {
+                obj.init_async(io_priority, cancellable.as_ref(), move |res| {
+                    let _ = sender.send(res);
+                });
+            }
+            receiver.await.expect("\"init_async\" never completed")?;
+            Ok(obj)
+        })
+    }
+}
@@ -15,6 +15,13 @@ use std::io;
 use std::pin::Pin;
 
 pub trait IOStreamExtManual: Sized + IsA<IOStream> {
+    // rustdoc-stripper-ignore-next
+    /// Wraps the stream into an adapter implementing [`AsyncRead`] and [`AsyncWrite`], e.g. for
+    /// a [`SocketConnection`](crate::SocketConnection) or a
+    /// [`TlsConnection`](crate::TlsConnection).
+    ///
+    /// This fails and returns the stream if its input or output stream can't be polled. With
+    /// the `tokio` feature the adapter also implements the `tokio::io` traits.
     fn into_async_read_write(self) -> Result<IOStreamAsyncReadWrite<Self>, Self> {
         let write = self
             .get_output_stream()
@@ -95,3 +102,33 @@ impl<T: IsA<IOStream> + std::marker::Unpin> AsyncWrite for IOStreamAsyncReadWrit
         Pin::new(&mut Pin::get_mut(self).write).poll_flush(cx)
     }
 }
+
+#[cfg(feature = "tokio")]
+impl<T: IsA<IOStream> + std::marker::Unpin> tokio::io::AsyncRead for IOStreamAsyncReadWrite<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<Result<(), io::Error>> {
+        tokio::io::AsyncRead::poll_read(Pin::new(&mut Pin::get_mut(self).read), cx, buf)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: IsA<IOStream> + std::marker::Unpin> tokio::io::AsyncWrite for IOStreamAsyncReadWrite<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        tokio::io::AsyncWrite::poll_write(Pin::new(&mut Pin::get_mut(self).write), cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        tokio::io::AsyncWrite::poll_flush(Pin::new(&mut Pin::get_mut(self).write), cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        tokio::io::AsyncWrite::poll_shutdown(Pin::new(&mut Pin::get_mut(self).write), cx)
+    }
+}
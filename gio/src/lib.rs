@@ -11,19 +11,34 @@ pub use glib;
 
 mod app_info;
 mod application;
+mod async_initable;
+pub use self::async_initable::{AsyncInitable, NONE_ASYNC_INITABLE};
+mod async_read_input_stream;
+pub use crate::async_read_input_stream::AsyncReadInputStream;
+mod async_write_output_stream;
+pub use crate::async_write_output_stream::AsyncWriteOutputStream;
 #[cfg(test)]
 mod cancellable;
+mod codegen_variant;
+pub use self::codegen_variant::{codegen_from_variant, codegen_to_variant};
 mod converter;
 mod data_input_stream;
 mod dbus;
 pub use self::dbus::*;
+mod dbus_codegen;
+pub use self::dbus_codegen::{
+    compile_dbus_interfaces, dbus_codegen_invalid_reply, dbus_codegen_register_object,
+    dbus_codegen_set_property, dbus_codegen_unknown_property, generate_dbus_interfaces,
+};
 mod dbus_connection;
 pub use self::dbus_connection::{
-    ActionGroupExportId, FilterId, MenuModelExportId, RegistrationId, SignalSubscriptionId,
-    WatcherId,
+    ActionGroupExportId, DBusSignalStream, FilterId, MenuModelExportId, RegistrationId,
+    SignalSubscriptionId, WatcherId,
 };
+mod dbus_introspection;
 mod dbus_message;
 mod dbus_method_invocation;
+mod dbus_proxy;
 #[cfg(any(all(not(windows), not(target_os = "macos")), feature = "dox"))]
 mod desktop_app_info;
 mod error;
@@ -33,28 +48,66 @@ pub use crate::file_attribute_matcher::FileAttributematcherIter;
 mod file_enumerator;
 mod file_info;
 mod flags;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+mod flatten_list_model;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::flatten_list_model::FlattenListModel;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+mod filter_list_model;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::filter_list_model::FilterListModel;
 mod inet_address;
 mod inet_socket_address;
+mod initable;
+pub use self::initable::{Initable, InitableError, NONE_INITABLE};
 mod io_stream;
 pub use crate::io_stream::IOStreamAsyncReadWrite;
 mod input_stream;
 pub use crate::input_stream::{InputStreamAsyncBufRead, InputStreamRead};
 #[cfg(any(feature = "v2_44", feature = "dox"))]
+mod list_model;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::list_model::{ListModelIter, ListModelMutatedDuringIter};
+#[cfg(any(feature = "v2_44", feature = "dox"))]
 mod list_store;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::list_store::TypedListStore;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+mod map_list_model;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::map_list_model::MapListModel;
 mod memory_input_stream;
 mod memory_output_stream;
 mod output_stream;
 pub use crate::output_stream::OutputStreamWrite;
 mod pollable_input_stream;
-pub use crate::pollable_input_stream::InputStreamAsyncRead;
+pub use crate::pollable_input_stream::{pollable_source_new_full, InputStreamAsyncRead};
 mod pollable_output_stream;
 pub use crate::pollable_output_stream::OutputStreamAsyncWrite;
+mod pollable_waker;
 mod resource;
 pub use crate::resource::{compile_resources, resources_register_include_impl};
 mod settings;
+pub use crate::settings::BindingBuilder;
+mod settings_backend;
+pub use crate::settings_backend::SettingsBackendOriginTag;
+mod settings_codegen;
+pub use crate::settings_codegen::{compile_settings_schema, generate_settings_schema};
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+mod slice_list_model;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::slice_list_model::SliceListModel;
 mod socket;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+mod sort_list_model;
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub use crate::sort_list_model::SortListModel;
 mod subprocess;
+pub use crate::subprocess::{SubprocessExitStatus, SubprocessHandle, SubprocessLines};
 mod subprocess_launcher;
+pub use crate::subprocess_launcher::SubprocessBuilder;
+mod test_settings_backend;
+pub use crate::test_settings_backend::TestSettingsBackend;
 mod threaded_socket_service;
 #[cfg(any(unix, feature = "dox"))]
 mod unix_fd_list;
@@ -101,6 +154,7 @@ mod tls_connection;
 pub use crate::tls_connection::TlsConnectionManualExt;
 
 pub mod task;
+pub use crate::task::TypedTask;
 
 #[cfg(target_family = "windows")]
 mod win32_input_stream;
@@ -0,0 +1,226 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::ListModel;
+use std::cell::Cell;
+use std::error;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+pub trait ListModelExtManual: 'static {
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the items of the model.
+    ///
+    /// The iterator returns [`ListModelMutatedDuringIter`] once and stops if the model is
+    /// changed while iterating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item type of the model is not a `T`.
+    fn iter<T: IsA<glib::Object>>(&self) -> ListModelIter<T>;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all items of the model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item type of the model is not a `T`.
+    fn snapshot<T: IsA<glib::Object>>(&self) -> Vec<T>;
+}
+
+impl<O: IsA<ListModel>> ListModelExtManual for O {
+    fn iter<T: IsA<glib::Object>>(&self) -> ListModelIter<T> {
+        ListModelIter::new(self.as_ref())
+    }
+
+    fn snapshot<T: IsA<glib::Object>>(&self) -> Vec<T> {
+        assert_item_type::<T>(self.as_ref());
+
+        (0..self.get_n_items())
+            .filter_map(|i| self.get_object(i))
+            .map(|item| {
+                item.downcast::<T>()
+                    .expect("ListModel item of the wrong type")
+            })
+            .collect()
+    }
+}
+
+fn assert_item_type<T: IsA<glib::Object>>(model: &ListModel) {
+    let item_type = model.get_item_type();
+    assert!(
+        item_type.is_a(T::static_type()),
+        "ListModel item type {} is not a {}",
+        item_type,
+        T::static_type()
+    );
+}
+
+// rustdoc-stripper-ignore-next
+/// Error returned by [`ListModelIter`] if the model was changed while iterating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListModelMutatedDuringIter;
+
+impl fmt::Display for ListModelMutatedDuringIter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ListModel was mutated during iteration")
+    }
+}
+
+impl error::Error for ListModelMutatedDuringIter {}
+
+// rustdoc-stripper-ignore-next
+/// Iterator over the items of a [`ListModel`], created by
+/// [`ListModelExtManual::iter`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ListModelIter<T> {
+    model: ListModel,
+    front: u32,
+    back: u32,
+    changed: Rc<Cell<bool>>,
+    handler: Option<glib::SignalHandlerId>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: IsA<glib::Object>> ListModelIter<T> {
+    fn new(model: &ListModel) -> Self {
+        assert_item_type::<T>(model);
+
+        let changed = Rc::new(Cell::new(false));
+        let changed_clone = changed.clone();
+        let handler = model.connect_items_changed(move |_, _, _, _| changed_clone.set(true));
+
+        ListModelIter {
+            model: model.clone(),
+            front: 0,
+            back: model.get_n_items(),
+            changed,
+            handler: Some(handler),
+            phantom: PhantomData,
+        }
+    }
+
+    fn get(&mut self, position: u32) -> Result<T, ListModelMutatedDuringIter> {
+        match self.model.get_object(position) {
+            Some(item) if !self.changed.get() => Ok(item
+                .downcast::<T>()
+                .expect("ListModel item of the wrong type")),
+            _ => {
+                self.front = self.back;
+                Err(ListModelMutatedDuringIter)
+            }
+        }
+    }
+}
+
+impl<T: IsA<glib::Object>> Iterator for ListModelIter<T> {
+    type Item = Result<T, ListModelMutatedDuringIter>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let position = self.front;
+        self.front += 1;
+        Some(self.get(position))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.changed.get() {
+            std::cmp::min(1, self.back - self.front)
+        } else {
+            self.back - self.front
+        };
+        (len as usize, Some(len as usize))
+    }
+}
+
+impl<T: IsA<glib::Object>> DoubleEndedIterator for ListModelIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl<T: IsA<glib::Object>> ExactSizeIterator for ListModelIter<T> {}
+
+impl<T: IsA<glib::Object>> FusedIterator for ListModelIter<T> {}
+
+impl<T> Drop for ListModelIter<T> {
+    fn drop(&mut self) {
+        if let Some(handler) = self.handler.take() {
+            self.model.disconnect(handler);
+        }
+    }
+}
+
+impl<T> fmt::Debug for ListModelIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ListModelIter")
+            .field("model", &self.model)
+            .field("front", &self.front)
+            .field("back", &self.back)
+            .field("changed", &self.changed.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{list_item, list_item_value, list_store_with};
+
+    #[test]
+    fn test_iter() {
+        let store = list_store_with(&[1, 2, 3]);
+
+        let values = store
+            .iter::<glib::Object>()
+            .map(|item| list_item_value(&item.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let mut iter = store.iter::<glib::Object>();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(list_item_value(&iter.next_back().unwrap().unwrap()), 3);
+        assert_eq!(list_item_value(&iter.next().unwrap().unwrap()), 1);
+        assert_eq!(iter.len(), 1);
+    }
+
+    #[test]
+    fn test_iter_mutated() {
+        let store = list_store_with(&[1, 2, 3]);
+
+        let mut iter = store.iter::<glib::Object>();
+        assert!(iter.next().unwrap().is_ok());
+        store.append(&list_item(4));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(Err(ListModelMutatedDuringIter)));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let store = list_store_with(&[1, 2, 3]);
+        let items = store.snapshot::<glib::Object>();
+        store.remove_all();
+        assert_eq!(
+            items.iter().map(list_item_value).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_wrong_type() {
+        let store = list_store_with(&[1]);
+        let _ = store.iter::<crate::SimpleAction>();
+    }
+}
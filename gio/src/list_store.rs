@@ -1,9 +1,12 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::ListStore;
+use crate::prelude::*;
+use crate::{ListModel, ListModelIter, ListStore};
 use glib::translate::*;
 use glib::{IsA, Object};
 use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
 
 pub trait ListStoreExtManual {
     #[doc(alias = "g_list_store_insert_sorted")]
@@ -74,3 +77,224 @@ unsafe extern "C" fn compare_func_trampoline(
         Ordering::Greater => 1,
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// A [`ListStore`] whose items are all of type `T`.
+///
+/// The item type is checked at compile time. [`store`](Self::store) returns the underlying
+/// [`ListStore`], e.g. to bind it to a widget.
+pub struct TypedListStore<T> {
+    store: ListStore,
+    phantom: PhantomData<T>,
+}
+
+impl<T: IsA<glib::Object>> TypedListStore<T> {
+    pub fn new() -> Self {
+        TypedListStore {
+            store: ListStore::new(T::static_type()),
+            phantom: PhantomData,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Wraps `store`, or returns it back if its item type is not a `T`.
+    pub fn from_store(store: ListStore) -> Result<Self, ListStore> {
+        if store.get_item_type().is_a(T::static_type()) {
+            Ok(TypedListStore {
+                store,
+                phantom: PhantomData,
+            })
+        } else {
+            Err(store)
+        }
+    }
+
+    pub fn store(&self) -> &ListStore {
+        &self.store
+    }
+
+    pub fn len(&self) -> u32 {
+        self.store.get_n_items()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, position: u32) -> Option<T> {
+        self.store
+            .get_object(position)
+            .map(|item| unsafe { item.unsafe_cast() })
+    }
+
+    pub fn append(&self, item: &T) {
+        self.store.append(item);
+    }
+
+    pub fn insert(&self, position: u32, item: &T) {
+        self.store.insert(position, item);
+    }
+
+    pub fn insert_sorted<F: FnMut(&T, &T) -> Ordering>(
+        &self,
+        item: &T,
+        mut compare_func: F,
+    ) -> u32 {
+        self.store.insert_sorted(item, |a, b| unsafe {
+            compare_func(a.unsafe_cast_ref(), b.unsafe_cast_ref())
+        })
+    }
+
+    #[cfg(any(feature = "v2_46", feature = "dox"))]
+    #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_46")))]
+    pub fn sort<F: FnMut(&T, &T) -> Ordering>(&self, mut compare_func: F) {
+        self.store
+            .sort(|a, b| unsafe { compare_func(a.unsafe_cast_ref(), b.unsafe_cast_ref()) })
+    }
+
+    pub fn remove(&self, position: u32) {
+        self.store.remove(position);
+    }
+
+    pub fn remove_all(&self) {
+        self.store.remove_all();
+    }
+
+    pub fn splice(&self, position: u32, n_removals: u32, additions: &[T]) {
+        let additions = additions
+            .iter()
+            .map(|item| item.as_ref().clone())
+            .collect::<Vec<_>>();
+        self.store.splice(position, n_removals, &additions);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all items for which `f` returns `false`.
+    ///
+    /// Every run of consecutive removed items is removed with a single `items-changed`
+    /// emission.
+    pub fn retain<F: FnMut(&T) -> bool>(&self, mut f: F) {
+        let keep = self
+            .snapshot()
+            .iter()
+            .map(|item| f(item))
+            .collect::<Vec<_>>();
+
+        let mut position = 0;
+        let mut i = 0;
+        while i < keep.len() {
+            if keep[i] {
+                position += 1;
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            while i < keep.len() && !keep[i] {
+                i += 1;
+            }
+            self.store.splice(position, (i - start) as u32, &[]);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the items of the store.
+    ///
+    /// See [`ListModelExtManual::iter`](crate::prelude::ListModelExtManual::iter).
+    pub fn iter(&self) -> ListModelIter<T> {
+        self.store.iter()
+    }
+
+    pub fn snapshot(&self) -> Vec<T> {
+        self.store.snapshot()
+    }
+}
+
+impl<T: IsA<glib::Object>> Default for TypedListStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for TypedListStore<T> {
+    fn clone(&self) -> Self {
+        TypedListStore {
+            store: self.store.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for TypedListStore<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedListStore").field(&self.store).finish()
+    }
+}
+
+impl<T> AsRef<ListStore> for TypedListStore<T> {
+    fn as_ref(&self) -> &ListStore {
+        &self.store
+    }
+}
+
+impl<T> AsRef<ListModel> for TypedListStore<T> {
+    fn as_ref(&self) -> &ListModel {
+        self.store.upcast_ref()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Appends all items with a single `items-changed` emission.
+impl<T: IsA<glib::Object>> Extend<T> for TypedListStore<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let additions = iter
+            .into_iter()
+            .map(|item| item.upcast::<Object>())
+            .collect::<Vec<_>>();
+        self.store.splice(self.len(), 0, &additions);
+    }
+}
+
+impl<T: IsA<glib::Object>> std::iter::FromIterator<T> for TypedListStore<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut store = Self::new();
+        store.extend(iter);
+        store
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleAction;
+
+    fn names(store: &TypedListStore<SimpleAction>) -> Vec<String> {
+        store
+            .iter()
+            .map(|action| action.unwrap().get_name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_typed_list_store() {
+        let mut store = ["a", "b", "c"]
+            .iter()
+            .map(|name| SimpleAction::new(name, None))
+            .collect::<TypedListStore<SimpleAction>>();
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.get(1).unwrap().get_name(), "b");
+
+        store.extend(vec![SimpleAction::new("d", None)]);
+        store.splice(0, 1, &[SimpleAction::new("e", None)]);
+        assert_eq!(names(&store), vec!["e", "b", "c", "d"]);
+
+        let changes = crate::test_util::record_items_changed(store.store());
+        store.retain(|action| action.get_name() != "b" && action.get_name() != "c");
+        assert_eq!(names(&store), vec!["e", "d"]);
+        assert_eq!(*changes.borrow(), vec![(1, 2, 0)]);
+
+        let untyped = ListStore::new(glib::Object::static_type());
+        assert!(TypedListStore::<SimpleAction>::from_store(untyped).is_err());
+        assert!(TypedListStore::<SimpleAction>::from_store(store.store().clone()).is_ok());
+    }
+}
@@ -0,0 +1,178 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::subclass::prelude::*;
+use crate::ListModel;
+
+use std::cell::{Cell, RefCell};
+
+mod imp {
+    use super::*;
+
+    pub struct MapListModel {
+        pub(super) model: RefCell<Option<ListModel>>,
+        pub(super) handler: RefCell<Option<glib::SignalHandlerId>>,
+        pub(super) item_type: Cell<glib::Type>,
+        pub(super) map_func: RefCell<Option<Box<dyn Fn(&glib::Object) -> glib::Object>>>,
+        // Already mapped items, so that every position keeps returning the same object
+        pub(super) items: RefCell<Vec<Option<glib::Object>>>,
+    }
+
+    impl Default for MapListModel {
+        fn default() -> Self {
+            MapListModel {
+                model: RefCell::new(None),
+                handler: RefCell::new(None),
+                item_type: Cell::new(glib::Object::static_type()),
+                map_func: RefCell::new(None),
+                items: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for MapListModel {
+        const NAME: &'static str = "MapListModel";
+        type Type = super::MapListModel;
+        type ParentType = glib::Object;
+        type Interfaces = (ListModel,);
+    }
+
+    impl ObjectImpl for MapListModel {
+        fn dispose(&self, _obj: &Self::Type) {
+            if let (Some(model), Some(handler)) = (
+                self.model.borrow_mut().take(),
+                self.handler.borrow_mut().take(),
+            ) {
+                model.disconnect(handler);
+            }
+            self.items.borrow_mut().clear();
+        }
+    }
+
+    impl ListModelImpl for MapListModel {
+        fn get_item_type(&self, _list_model: &Self::Type) -> glib::Type {
+            self.item_type.get()
+        }
+
+        fn get_n_items(&self, _list_model: &Self::Type) -> u32 {
+            self.items.borrow().len() as u32
+        }
+
+        fn get_item(&self, _list_model: &Self::Type, position: u32) -> Option<glib::Object> {
+            if let Some(item) = self.items.borrow().get(position as usize)? {
+                return Some(item.clone());
+            }
+
+            let model = self.model.borrow().clone()?;
+            let item = model.get_object(position)?;
+            let item = match *self.map_func.borrow() {
+                Some(ref map_func) => map_func(&item),
+                None => item,
+            };
+
+            // The map function might have changed the model in the meantime
+            if let Some(cached) = self.items.borrow_mut().get_mut(position as usize) {
+                *cached = Some(item.clone());
+            }
+            Some(item)
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`ListModel`] containing the items of another model mapped by a function.
+    ///
+    /// Items are mapped lazily when they are first requested and the mapped objects are kept
+    /// until the corresponding item of the underlying model is removed, so that the same object
+    /// is returned every time. All mapped objects must be of the item type passed to
+    /// [`new`](Self::new).
+    pub struct MapListModel(ObjectSubclass<imp::MapListModel>) @implements ListModel;
+}
+
+impl MapListModel {
+    pub fn new<P: IsA<ListModel>, F: Fn(&glib::Object) -> glib::Object + 'static>(
+        model: &P,
+        item_type: glib::Type,
+        map_func: F,
+    ) -> MapListModel {
+        let obj: MapListModel = glib::Object::new(&[]).expect("Failed to create map list model");
+
+        let imp = imp::MapListModel::from_instance(&obj);
+        let model = model.as_ref().clone();
+        imp.item_type.set(item_type);
+        *imp.map_func.borrow_mut() = Some(Box::new(map_func));
+        *imp.items.borrow_mut() = vec![None; model.get_n_items() as usize];
+        *imp.model.borrow_mut() = Some(model.clone());
+
+        let obj_weak = obj.downgrade();
+        let handler = model.connect_items_changed(move |_, position, removed, added| {
+            if let Some(obj) = obj_weak.upgrade() {
+                let imp = imp::MapListModel::from_instance(&obj);
+                imp.items.borrow_mut().splice(
+                    position as usize..(position + removed) as usize,
+                    (0..added).map(|_| None),
+                );
+                obj.items_changed(position, removed, added);
+            }
+        });
+        *imp.handler.borrow_mut() = Some(handler);
+
+        obj
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the underlying model.
+    pub fn model(&self) -> Option<ListModel> {
+        let imp = imp::MapListModel::from_instance(self);
+        imp.model.borrow().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Replaces the map function, which invalidates all items.
+    pub fn set_map_func<F: Fn(&glib::Object) -> glib::Object + 'static>(&self, map_func: F) {
+        let imp = imp::MapListModel::from_instance(self);
+        *imp.map_func.borrow_mut() = Some(Box::new(map_func));
+
+        let n_items = {
+            let mut items = imp.items.borrow_mut();
+            for item in items.iter_mut() {
+                *item = None;
+            }
+            items.len() as u32
+        };
+        if n_items > 0 {
+            self.items_changed(0, n_items, n_items);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{
+        list_item, list_item_value, list_model_values, list_store_with, record_items_changed,
+    };
+
+    #[test]
+    fn test_map() {
+        let store = list_store_with(&[1, 2, 3]);
+        let model = MapListModel::new(&store, glib::Object::static_type(), |item| {
+            list_item(list_item_value(item) * 10)
+        });
+        let changes = record_items_changed(&model);
+        assert_eq!(list_model_values(&model), vec![10, 20, 30]);
+
+        // Mapped items are kept
+        assert_eq!(model.get_object(1), model.get_object(1));
+
+        store.splice(1, 1, &[list_item(4), list_item(5)]);
+        assert_eq!(list_model_values(&model), vec![10, 40, 50, 30]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 1, 2)));
+
+        model.set_map_func(|item| list_item(-list_item_value(item)));
+        assert_eq!(list_model_values(&model), vec![-1, -4, -5, -3]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 4, 4)));
+    }
+}
@@ -1,5 +1,6 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use crate::error::to_std_io_error;
 use crate::Cancellable;
 use crate::PollableInputStream;
 use crate::PollableInputStreamExt;
@@ -173,6 +174,26 @@ impl<O: IsA<PollableInputStream>> PollableInputStreamExtManual for O {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Creates a source for the pollable stream `stream` that is dispatched whenever
+/// `child_source` is, e.g. to implement `create_source` of
+/// [`PollableInputStreamImpl`](crate::subclass::prelude::PollableInputStreamImpl) or
+/// [`PollableOutputStreamImpl`](crate::subclass::prelude::PollableOutputStreamImpl).
+#[doc(alias = "g_pollable_source_new_full")]
+pub fn pollable_source_new_full<P: IsA<glib::Object>, C: IsA<Cancellable>>(
+    stream: &P,
+    child_source: Option<&glib::Source>,
+    cancellable: Option<&C>,
+) -> glib::Source {
+    unsafe {
+        from_glib_full(ffi::g_pollable_source_new_full(
+            stream.as_ref().to_glib_none().0,
+            child_source.to_glib_none().0,
+            cancellable.map(|c| c.as_ref()).to_glib_none().0,
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub struct InputStreamAsyncRead<T: IsA<PollableInputStream>>(T);
 
@@ -201,8 +222,7 @@ impl<T: IsA<PollableInputStream>> AsyncRead for InputStreamAsyncRead<T> {
         match gio_result {
             Ok(size) => Poll::Ready(Ok(size as usize)),
             Err(err) => {
-                let kind = err.kind::<crate::IOErrorEnum>().unwrap();
-                if kind == crate::IOErrorEnum::WouldBlock {
+                if err.kind::<crate::IOErrorEnum>() == Some(crate::IOErrorEnum::WouldBlock) {
                     let mut waker = Some(cx.waker().clone());
                     let source = stream.0.as_ref().create_source(
                         crate::NONE_CANCELLABLE,
@@ -220,9 +240,22 @@ impl<T: IsA<PollableInputStream>> AsyncRead for InputStreamAsyncRead<T> {
 
                     Poll::Pending
                 } else {
-                    Poll::Ready(Err(io::Error::new(io::ErrorKind::from(kind), err)))
+                    Poll::Ready(Err(to_std_io_error(err)))
                 }
             }
         }
     }
 }
+
+#[cfg(feature = "tokio")]
+impl<T: IsA<PollableInputStream>> tokio::io::AsyncRead for InputStreamAsyncRead<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let size = futures_core::ready!(AsyncRead::poll_read(self, cx, buf.initialize_unfilled()))?;
+        buf.advance(size);
+        Poll::Ready(Ok(()))
+    }
+}
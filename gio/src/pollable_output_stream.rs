@@ -1,6 +1,6 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::error::to_std_io_result;
+use crate::error::{to_std_io_error, to_std_io_result};
 use crate::Cancellable;
 use crate::OutputStreamExt;
 use crate::PollableOutputStream;
@@ -174,8 +174,7 @@ impl<T: IsA<PollableOutputStream>> AsyncWrite for OutputStreamAsyncWrite<T> {
         match gio_result {
             Ok(size) => Poll::Ready(Ok(size as usize)),
             Err(err) => {
-                let kind = err.kind::<crate::IOErrorEnum>().unwrap();
-                if kind == crate::IOErrorEnum::WouldBlock {
+                if err.kind::<crate::IOErrorEnum>() == Some(crate::IOErrorEnum::WouldBlock) {
                     let mut waker = Some(cx.waker().clone());
                     let source = stream.0.as_ref().create_source(
                         crate::NONE_CANCELLABLE,
@@ -193,7 +192,7 @@ impl<T: IsA<PollableOutputStream>> AsyncWrite for OutputStreamAsyncWrite<T> {
 
                     Poll::Pending
                 } else {
-                    Poll::Ready(Err(io::Error::new(io::ErrorKind::from(kind), err)))
+                    Poll::Ready(Err(to_std_io_error(err)))
                 }
             }
         }
@@ -263,3 +262,18 @@ impl<T: IsA<PollableOutputStream>> AsyncWrite for OutputStreamAsyncWrite<T> {
         }
     }
 }
+
+#[cfg(feature = "tokio")]
+impl<T: IsA<PollableOutputStream>> tokio::io::AsyncWrite for OutputStreamAsyncWrite<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(self, cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(self, cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_close(self, cx)
+    }
+}
@@ -0,0 +1,169 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::translate::*;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread::{self, Thread};
+
+// Bridges the wakeups of Rust futures to the sources of pollable GIO streams.
+//
+// The stream polls its Rust I/O object with a waker that marks the stream as ready again and
+// sets the ready time of all child sources created by `create_source` so that the pollable
+// sources of the stream are dispatched.
+#[derive(Debug, Clone)]
+pub(crate) struct PollableWaker(Arc<PollableWakerInner>);
+
+#[derive(Debug)]
+struct PollableWakerInner {
+    ready: AtomicBool,
+    sources: Mutex<Vec<glib::Source>>,
+}
+
+impl PollableWaker {
+    pub(crate) fn new() -> Self {
+        PollableWaker(Arc::new(PollableWakerInner {
+            ready: AtomicBool::new(true),
+            sources: Mutex::new(Vec::new()),
+        }))
+    }
+
+    pub(crate) fn is_ready(&self) -> bool {
+        self.0.ready.load(Ordering::SeqCst)
+    }
+
+    // Creates a source that is dispatched whenever the waker is woken up, to be used as
+    // child source of a pollable source.
+    pub(crate) fn create_source(&self) -> glib::Source {
+        unsafe extern "C" fn dispatch(
+            source: *mut glib::ffi::GSource,
+            callback: glib::ffi::GSourceFunc,
+            user_data: glib::ffi::gpointer,
+        ) -> glib::ffi::gboolean {
+            glib::ffi::g_source_set_ready_time(source, -1);
+            match callback {
+                Some(callback) => callback(user_data),
+                None => glib::ffi::G_SOURCE_CONTINUE,
+            }
+        }
+
+        static SOURCE_FUNCS: glib::ffi::GSourceFuncs = glib::ffi::GSourceFuncs {
+            prepare: None,
+            check: None,
+            dispatch: Some(dispatch),
+            finalize: None,
+            closure_callback: None,
+            closure_marshal: None,
+        };
+
+        let source: glib::Source = unsafe {
+            from_glib_full(glib::ffi::g_source_new(
+                &SOURCE_FUNCS as *const _ as *mut _,
+                mem::size_of::<glib::ffi::GSource>() as u32,
+            ))
+        };
+
+        let mut sources = self.0.sources.lock().unwrap();
+        sources.retain(|source| !source.is_destroyed());
+        sources.push(source.clone());
+        if self.is_ready() {
+            set_ready_time(&source, 0);
+        }
+
+        source
+    }
+
+    // Polls `func` with a waker waking up this `PollableWaker`. If `func` is pending, the
+    // stream is not ready anymore until the waker is woken up.
+    pub(crate) fn poll<T, F: FnOnce(&mut Context) -> Poll<T>>(&self, func: F) -> Poll<T> {
+        self.0.ready.store(false, Ordering::SeqCst);
+        for source in &*self.0.sources.lock().unwrap() {
+            set_ready_time(source, -1);
+        }
+
+        let waker = arc_waker(self.0.clone());
+        let res = func(&mut Context::from_waker(&waker));
+        if res.is_ready() {
+            self.0.ready.store(true, Ordering::SeqCst);
+        }
+
+        res
+    }
+
+    // Polls `func` until it is ready, blocking the current thread in between.
+    pub(crate) fn block_on<T, F: FnMut(&mut Context) -> Poll<T>>(mut func: F) -> T {
+        let waker = arc_waker(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(res) = func(&mut cx) {
+                return res;
+            }
+            thread::park();
+        }
+    }
+}
+
+fn set_ready_time(source: &glib::Source, ready_time: i64) {
+    unsafe {
+        glib::ffi::g_source_set_ready_time(source.to_glib_none().0, ready_time);
+    }
+}
+
+trait ArcWake: Send + Sync + 'static {
+    fn wake(&self);
+}
+
+impl ArcWake for PollableWakerInner {
+    fn wake(&self) {
+        self.ready.store(true, Ordering::SeqCst);
+        let mut sources = self.sources.lock().unwrap();
+        sources.retain(|source| !source.is_destroyed());
+        for source in &*sources {
+            set_ready_time(source, 0);
+        }
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl ArcWake for ThreadWaker {
+    fn wake(&self) {
+        self.0.unpark();
+    }
+}
+
+fn arc_waker<W: ArcWake>(wake: Arc<W>) -> Waker {
+    unsafe { Waker::from_raw(raw_waker::<W>(Arc::into_raw(wake) as *const ())) }
+}
+
+fn raw_waker<W: ArcWake>(data: *const ()) -> RawWaker {
+    RawWaker::new(
+        data,
+        &RawWakerVTable::new(
+            clone_waker::<W>,
+            wake_waker::<W>,
+            wake_by_ref_waker::<W>,
+            drop_waker::<W>,
+        ),
+    )
+}
+
+unsafe fn clone_waker<W: ArcWake>(data: *const ()) -> RawWaker {
+    let wake = mem::ManuallyDrop::new(Arc::from_raw(data as *const W));
+    raw_waker::<W>(Arc::into_raw(Arc::clone(&wake)) as *const ())
+}
+
+unsafe fn wake_waker<W: ArcWake>(data: *const ()) {
+    let wake = Arc::from_raw(data as *const W);
+    wake.wake();
+}
+
+unsafe fn wake_by_ref_waker<W: ArcWake>(data: *const ()) {
+    let wake = mem::ManuallyDrop::new(Arc::from_raw(data as *const W));
+    wake.wake();
+}
+
+unsafe fn drop_waker<W: ArcWake>(data: *const ()) {
+    drop(Arc::from_raw(data as *const W));
+}
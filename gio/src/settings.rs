@@ -209,6 +209,28 @@ mod test {
         assert!(!settings.get::<bool>("test-bool"));
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn rust_backend() {
+        set_env();
+        let backend = crate::TestSettingsBackend::new();
+        let settings = Settings::with_backend("com.github.gtk-rs.test", &backend);
+        assert_eq!(settings.get::<String>("test-string").as_str(), "Good");
+
+        settings.set("test-string", &"Written".to_string()).unwrap();
+        assert_eq!(
+            backend.value("/com/github/gtk-rs/test-string"),
+            Some("Written".to_variant())
+        );
+
+        backend.set_value("/com/github/gtk-rs/test-bool", Some(&false.to_variant()));
+        assert!(!settings.get::<bool>("test-bool"));
+
+        backend.set_writable("/com/github/gtk-rs/test-bool", false);
+        assert!(!settings.is_writable("test-bool"));
+        assert!(settings.set("test-bool", &true).is_err());
+    }
+
     #[test]
     #[should_panic]
     #[serial_test::serial]
@@ -0,0 +1,81 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::SettingsBackend;
+use glib::object::IsA;
+use glib::translate::*;
+
+// rustdoc-stripper-ignore-next
+/// Opaque tag identifying the origin of a change to a [`SettingsBackend`].
+///
+/// Backends receive it with each write and pass it on to the change notifications caused by
+/// that write, so that the writer can recognize its own changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SettingsBackendOriginTag(usize);
+
+impl SettingsBackendOriginTag {
+    pub(crate) fn from_raw(ptr: glib::ffi::gpointer) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(SettingsBackendOriginTag(ptr as usize))
+        }
+    }
+
+    pub(crate) fn as_ptr(tag: Option<Self>) -> glib::ffi::gpointer {
+        tag.map_or(std::ptr::null_mut(), |tag| tag.0 as glib::ffi::gpointer)
+    }
+}
+
+pub trait SettingsBackendExtManual: 'static {
+    #[doc(alias = "g_settings_backend_changed")]
+    fn changed(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>);
+
+    #[doc(alias = "g_settings_backend_keys_changed")]
+    fn keys_changed(
+        &self,
+        path: &str,
+        items: &[&str],
+        origin_tag: Option<SettingsBackendOriginTag>,
+    );
+
+    #[doc(alias = "g_settings_backend_path_changed")]
+    fn path_changed(&self, path: &str, origin_tag: Option<SettingsBackendOriginTag>);
+}
+
+impl<O: IsA<SettingsBackend>> SettingsBackendExtManual for O {
+    fn changed(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>) {
+        unsafe {
+            ffi::g_settings_backend_changed(
+                self.as_ref().to_glib_none().0,
+                key.to_glib_none().0,
+                SettingsBackendOriginTag::as_ptr(origin_tag),
+            );
+        }
+    }
+
+    fn keys_changed(
+        &self,
+        path: &str,
+        items: &[&str],
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) {
+        unsafe {
+            ffi::g_settings_backend_keys_changed(
+                self.as_ref().to_glib_none().0,
+                path.to_glib_none().0,
+                items.to_glib_none().0,
+                SettingsBackendOriginTag::as_ptr(origin_tag),
+            );
+        }
+    }
+
+    fn path_changed(&self, path: &str, origin_tag: Option<SettingsBackendOriginTag>) {
+        unsafe {
+            ffi::g_settings_backend_path_changed(
+                self.as_ref().to_glib_none().0,
+                path.to_glib_none().0,
+                SettingsBackendOriginTag::as_ptr(origin_tag),
+            );
+        }
+    }
+}
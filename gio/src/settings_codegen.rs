@@ -0,0 +1,878 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::dbus_codegen::{rust_type, to_snake_case, unique_suffix_names, unsupported, Names};
+use crate::IOErrorEnum;
+use glib::translate::*;
+use glib::VariantTy;
+use std::env;
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::ptr;
+
+// rustdoc-stripper-ignore-next
+/// Call from build script to compile the GSettings schema file `xml` and generate typed
+/// accessors for its schemas. `target` is relative to `OUT_DIR`.
+///
+/// The schemas of the directory containing `xml` are compiled with `glib-compile-schemas` into
+/// `OUT_DIR`, so that tests and uninstalled binaries can find them by setting
+/// `GSETTINGS_SCHEMA_DIR` to `OUT_DIR`.
+///
+/// ```no_run
+/// gio::compile_settings_schema("data/org.example.App.gschema.xml", "settings.rs");
+/// ```
+///
+/// The generated code is then pulled into the crate with `include!`:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/settings.rs"));
+/// ```
+///
+/// See [`generate_settings_schema`] for the items generated for each schema.
+pub fn compile_settings_schema<P: AsRef<Path>>(xml: P, target: &str) {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let xml = xml.as_ref();
+    let schema_dir = match xml.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let status = Command::new("glib-compile-schemas")
+        .arg("--strict")
+        .arg("--targetdir")
+        .arg(&out_dir)
+        .arg(schema_dir)
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        panic!("glib-compile-schemas failed with exit status {}", status);
+    }
+
+    let data = fs::read_to_string(xml)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", xml.display(), err));
+    let code = generate_settings_schema(&data).unwrap_or_else(|err| {
+        panic!(
+            "failed to generate settings bindings for {}: {}",
+            xml.display(),
+            err
+        )
+    });
+    fs::write(Path::new(&out_dir).join(target), code).unwrap();
+
+    println!("cargo:rerun-if-changed={}", xml.display());
+}
+
+// rustdoc-stripper-ignore-next
+/// Generates Rust source code for all schemas of the GSettings schema XML `xml`.
+///
+/// For a schema `org.example.App` this generates an `AppSettings` wrapper around a
+/// [`Settings`](crate::Settings) with a `new` constructor, or a `with_path` constructor if the
+/// schema is relocatable, and for each key `foo-bar`:
+///
+/// * a `get_foo_bar` method returning the current value,
+/// * a `set_foo_bar` method changing the value,
+/// * a `connect_foo_bar_changed` method connecting to the `changed` signal of the key,
+/// * a `bind_foo_bar` method returning a [`BindingBuilder`](crate::BindingBuilder) binding the
+///   key to an object property.
+///
+/// Key types are mapped to the Rust types implementing [`FromVariant`](glib::FromVariant) and
+/// [`ToVariant`](glib::ToVariant) for the corresponding `GVariant` type. Each `<enum>` of the
+/// schema file is mapped to a Rust enum and each `<flags>` to a `bitflags` type, which are used
+/// by the keys referring to them.
+///
+/// Schemas, enums and flags are named after the last segment of their id. Ids sharing the same
+/// last segment are told apart by prepending as many of the preceding segments as needed, e.g.
+/// `org.example.window.Mode` becomes `WindowMode` next to `org.example.export.Mode`. An error is
+/// returned if two types would still end up with the same name.
+pub fn generate_settings_schema(xml: &str) -> Result<String, glib::Error> {
+    let mut list = parse_schema_list(xml)?;
+    assign_names(&mut list)?;
+
+    let mut code = String::new();
+    for enum_ in &list.enums {
+        generate_enum(&mut code, enum_)?;
+    }
+    for schema in &list.schemas {
+        generate_schema(&mut code, schema, &list.enums)?;
+    }
+
+    Ok(code)
+}
+
+struct EnumInfo {
+    id: String,
+    name: String,
+    is_flags: bool,
+    values: Vec<(String, i64)>,
+}
+
+enum KeyKind {
+    Type(String),
+    Enum(String),
+    Flags(String),
+}
+
+struct KeyInfo {
+    name: String,
+    kind: KeyKind,
+    summary: Option<String>,
+}
+
+struct SchemaInfo {
+    id: String,
+    name: String,
+    path: Option<String>,
+    keys: Vec<KeyInfo>,
+}
+
+#[derive(Default)]
+struct SchemaList {
+    enums: Vec<EnumInfo>,
+    schemas: Vec<SchemaInfo>,
+}
+
+#[derive(Default)]
+struct ParseState {
+    list: SchemaList,
+    stack: Vec<String>,
+    text: String,
+    error: Option<glib::Error>,
+}
+
+impl ParseState {
+    fn start_element(&mut self, element: &str, attributes: &[(&str, &str)]) {
+        let attribute = |name| {
+            attributes
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.to_string())
+        };
+        let parent = self.stack.last().map(String::as_str);
+
+        match (parent, element) {
+            (Some("schemalist"), "enum") | (Some("schemalist"), "flags") => {
+                if let Some(id) = attribute("id") {
+                    self.list.enums.push(EnumInfo {
+                        id,
+                        name: String::new(),
+                        is_flags: element == "flags",
+                        values: Vec::new(),
+                    });
+                } else {
+                    self.fail(format!("<{}> without id", element));
+                }
+            }
+            (Some("enum"), "value") | (Some("flags"), "value") => {
+                let value = attribute("value").and_then(|v| parse_integer(&v));
+                match (attribute("nick"), value) {
+                    (Some(nick), Some(value)) => self
+                        .list
+                        .enums
+                        .last_mut()
+                        .unwrap()
+                        .values
+                        .push((nick, value)),
+                    _ => self.fail("<value> without valid nick and value".to_string()),
+                }
+            }
+            (Some("schemalist"), "schema") => {
+                if let Some(id) = attribute("id") {
+                    self.list.schemas.push(SchemaInfo {
+                        id,
+                        name: String::new(),
+                        path: attribute("path"),
+                        keys: Vec::new(),
+                    });
+                } else {
+                    self.fail("<schema> without id".to_string());
+                }
+            }
+            (Some("schema"), "key") => {
+                let kind = if let Some(type_) = attribute("type") {
+                    Some(KeyKind::Type(type_))
+                } else if let Some(enum_) = attribute("enum") {
+                    Some(KeyKind::Enum(enum_))
+                } else {
+                    attribute("flags").map(KeyKind::Flags)
+                };
+                match (attribute("name"), kind) {
+                    (Some(name), Some(kind)) => {
+                        self.list.schemas.last_mut().unwrap().keys.push(KeyInfo {
+                            name,
+                            kind,
+                            summary: None,
+                        })
+                    }
+                    _ => self.fail("<key> without name or type".to_string()),
+                }
+            }
+            _ => (),
+        }
+
+        self.stack.push(element.to_string());
+        self.text.clear();
+    }
+
+    fn end_element(&mut self) {
+        let element = self.stack.pop();
+        let parent = self.stack.last().map(String::as_str);
+
+        if element.as_deref() == Some("summary") && parent == Some("key") {
+            let summary = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if let Some(key) = self
+                .list
+                .schemas
+                .last_mut()
+                .and_then(|schema| schema.keys.last_mut())
+            {
+                key.summary = Some(summary);
+            }
+        }
+        self.text.clear();
+    }
+
+    fn fail(&mut self, message: String) {
+        if self.error.is_none() {
+            self.error = Some(glib::Error::new(IOErrorEnum::InvalidData, &message));
+        }
+    }
+}
+
+fn parse_integer(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        i64::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+unsafe extern "C" fn start_element_trampoline(
+    _context: *mut glib::ffi::GMarkupParseContext,
+    element_name: *const libc::c_char,
+    attribute_names: *mut *const libc::c_char,
+    attribute_values: *mut *const libc::c_char,
+    user_data: glib::ffi::gpointer,
+    _error: *mut *mut glib::ffi::GError,
+) {
+    let state = &mut *(user_data as *mut ParseState);
+    let element = CStr::from_ptr(element_name).to_str().unwrap_or_default();
+
+    let mut attributes = Vec::new();
+    let mut i = 0;
+    while !(*attribute_names.add(i)).is_null() {
+        attributes.push((
+            CStr::from_ptr(*attribute_names.add(i))
+                .to_str()
+                .unwrap_or_default(),
+            CStr::from_ptr(*attribute_values.add(i))
+                .to_str()
+                .unwrap_or_default(),
+        ));
+        i += 1;
+    }
+
+    state.start_element(element, &attributes);
+}
+
+unsafe extern "C" fn end_element_trampoline(
+    _context: *mut glib::ffi::GMarkupParseContext,
+    _element_name: *const libc::c_char,
+    user_data: glib::ffi::gpointer,
+    _error: *mut *mut glib::ffi::GError,
+) {
+    let state = &mut *(user_data as *mut ParseState);
+    state.end_element();
+}
+
+unsafe extern "C" fn text_trampoline(
+    _context: *mut glib::ffi::GMarkupParseContext,
+    text: *const libc::c_char,
+    text_len: libc::size_t,
+    user_data: glib::ffi::gpointer,
+    _error: *mut *mut glib::ffi::GError,
+) {
+    let state = &mut *(user_data as *mut ParseState);
+    let text = std::slice::from_raw_parts(text as *const u8, text_len);
+    state.text.push_str(&String::from_utf8_lossy(text));
+}
+
+fn parse_schema_list(xml: &str) -> Result<SchemaList, glib::Error> {
+    let parser = glib::ffi::GMarkupParser {
+        start_element: Some(start_element_trampoline),
+        end_element: Some(end_element_trampoline),
+        text: Some(text_trampoline),
+        passthrough: None,
+        error: None,
+    };
+    let mut state = ParseState::default();
+
+    unsafe {
+        let context = glib::ffi::g_markup_parse_context_new(
+            &parser,
+            0,
+            &mut state as *mut ParseState as glib::ffi::gpointer,
+            None,
+        );
+        let mut error = ptr::null_mut();
+        let ok = glib::ffi::g_markup_parse_context_parse(
+            context,
+            xml.as_ptr() as *const _,
+            xml.len() as _,
+            &mut error,
+        ) != glib::ffi::GFALSE
+            && glib::ffi::g_markup_parse_context_end_parse(context, &mut error)
+                != glib::ffi::GFALSE;
+        glib::ffi::g_markup_parse_context_free(context);
+        if !ok {
+            return Err(from_glib_full(error));
+        }
+    }
+
+    match state.error {
+        Some(error) => Err(error),
+        None => Ok(state.list),
+    }
+}
+
+fn to_camel_case(name: &str) -> String {
+    let mut ret = String::with_capacity(name.len());
+    for word in to_snake_case(name).split('_') {
+        let mut chars = word.chars();
+        if let Some(c) = chars.next() {
+            ret.extend(c.to_uppercase());
+            ret.extend(chars);
+        }
+    }
+
+    if ret.starts_with(char::is_numeric) {
+        format!("V{}", ret)
+    } else {
+        ret
+    }
+}
+
+// Names the generated types after their ids, see `unique_suffix_names`.
+fn assign_names(list: &mut SchemaList) -> Result<(), glib::Error> {
+    let camel_case = |segments: &[&str]| to_camel_case(&segments.join("."));
+
+    let enum_ids = list
+        .enums
+        .iter()
+        .map(|enum_| enum_.id.clone())
+        .collect::<Vec<_>>();
+    for (enum_, name) in list
+        .enums
+        .iter_mut()
+        .zip(unique_suffix_names(&enum_ids, camel_case))
+    {
+        enum_.name = name;
+    }
+
+    let schema_ids = list
+        .schemas
+        .iter()
+        .map(|schema| schema.id.clone())
+        .collect::<Vec<_>>();
+    for (schema, name) in list
+        .schemas
+        .iter_mut()
+        .zip(unique_suffix_names(&schema_ids, camel_case))
+    {
+        schema.name = format!("{}Settings", name);
+    }
+
+    let mut types = Names::new("the generated types");
+    for enum_ in &list.enums {
+        let kind = if enum_.is_flags { "flags" } else { "enum" };
+        types.insert(&enum_.name, format!("{} '{}'", kind, enum_.id))?;
+    }
+    for schema in &list.schemas {
+        types.insert(&schema.name, format!("schema '{}'", schema.id))?;
+    }
+
+    Ok(())
+}
+
+fn generate_enum(code: &mut String, enum_: &EnumInfo) -> Result<(), glib::Error> {
+    let name = &enum_.name;
+    let scope = format!(
+        "{} '{}'",
+        if enum_.is_flags { "flags" } else { "enum" },
+        enum_.id
+    );
+    let mut values = Names::new(&scope);
+
+    if enum_.is_flags {
+        writeln!(
+            code,
+            "gio::glib::bitflags::bitflags! {{
+    /// Flags of the `{id}` settings flags type.
+    pub struct {name}: u32 {{",
+            id = enum_.id,
+            name = name,
+        )
+        .unwrap();
+        for (nick, value) in &enum_.values {
+            let const_name = to_snake_case(nick).to_uppercase();
+            let const_name = if const_name.starts_with(char::is_numeric) {
+                format!("V{}", const_name)
+            } else {
+                const_name
+            };
+            values.insert(&const_name, format!("value '{}'", nick))?;
+            writeln!(code, "        const {} = {};", const_name, value).unwrap();
+        }
+        writeln!(code, "    }}\n}}\n").unwrap();
+        return Ok(());
+    }
+
+    writeln!(
+        code,
+        "/// Values of the `{id}` settings enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {name} {{",
+        id = enum_.id,
+        name = name,
+    )
+    .unwrap();
+    for (nick, value) in &enum_.values {
+        values.insert(&to_camel_case(nick), format!("value '{}'", nick))?;
+        writeln!(code, "    {} = {},", to_camel_case(nick), value).unwrap();
+    }
+    writeln!(
+        code,
+        "}}
+
+impl {name} {{
+    /// Returns the variant with the integer value `value`.
+    pub fn from_value(value: i32) -> Option<Self> {{
+        match value {{",
+        name = name,
+    )
+    .unwrap();
+    for (nick, value) in &enum_.values {
+        writeln!(
+            code,
+            "            {} => Some(Self::{}),",
+            value,
+            to_camel_case(nick)
+        )
+        .unwrap();
+    }
+    writeln!(
+        code,
+        "            _ => None,
+        }}
+    }}
+
+    /// Returns the nick of the variant in the schema.
+    pub fn nick(self) -> &'static str {{
+        match self {{"
+    )
+    .unwrap();
+    for (nick, _) in &enum_.values {
+        writeln!(
+            code,
+            "            Self::{} => {:?},",
+            to_camel_case(nick),
+            nick
+        )
+        .unwrap();
+    }
+    writeln!(code, "        }}\n    }}\n}}\n").unwrap();
+
+    Ok(())
+}
+
+fn find_enum<'a>(
+    enums: &'a [EnumInfo],
+    id: &str,
+    is_flags: bool,
+) -> Result<&'a EnumInfo, glib::Error> {
+    enums
+        .iter()
+        .find(|enum_| enum_.id == id && enum_.is_flags == is_flags)
+        .ok_or_else(|| {
+            unsupported(format!(
+                "Unknown {} type '{}'",
+                if is_flags { "flags" } else { "enum" },
+                id
+            ))
+        })
+}
+
+fn generate_schema(
+    code: &mut String,
+    schema: &SchemaInfo,
+    enums: &[EnumInfo],
+) -> Result<(), glib::Error> {
+    let name = &schema.name;
+
+    writeln!(
+        code,
+        "/// Typed accessors for the `{id}` settings schema.
+#[derive(Debug, Clone)]
+pub struct {name}(gio::Settings);
+
+impl {name} {{
+    pub const SCHEMA_ID: &'static str = {id:?};
+",
+        id = schema.id,
+        name = name,
+    )
+    .unwrap();
+
+    if schema.path.is_some() {
+        writeln!(
+            code,
+            "    pub fn new() -> Self {{
+        Self(gio::Settings::new(Self::SCHEMA_ID))
+    }}
+"
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            code,
+            "    pub fn with_path(path: &str) -> Self {{
+        Self(gio::Settings::with_path(Self::SCHEMA_ID, path))
+    }}
+"
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        code,
+        "    pub fn from_settings(settings: gio::Settings) -> Self {{
+        Self(settings)
+    }}
+
+    pub fn settings(&self) -> &gio::Settings {{
+        &self.0
+    }}"
+    )
+    .unwrap();
+
+    for key in &schema.keys {
+        generate_key(code, key, enums)?;
+    }
+
+    writeln!(code, "}}\n").unwrap();
+
+    if schema.path.is_some() {
+        writeln!(
+            code,
+            "impl Default for {name} {{
+    fn default() -> Self {{
+        Self::new()
+    }}
+}}
+",
+            name = name,
+        )
+        .unwrap();
+    }
+
+    Ok(())
+}
+
+fn generate_key(code: &mut String, key: &KeyInfo, enums: &[EnumInfo]) -> Result<(), glib::Error> {
+    let snake = to_snake_case(&key.name);
+    let doc = key
+        .summary
+        .as_ref()
+        .map(|summary| format!("    /// {}\n", summary))
+        .unwrap_or_default();
+
+    let (value_type, param_type, get, set) = match key.kind {
+        KeyKind::Type(ref type_) => {
+            let variant_type = VariantTy::new(type_).map_err(|_| {
+                unsupported(format!("Invalid type '{}' of key '{}'", type_, key.name))
+            })?;
+            let value_type = rust_type(variant_type)?;
+            let coerced = type_.contains(|c| c == 'o' || c == 'g');
+            let get = if coerced {
+                format!(
+                    "gio::codegen_from_variant(&gio::prelude::SettingsExt::get_value(&self.0, {key:?}), {type_:?})
+            .expect(\"Type mismatch of key '{key}'\")",
+                    key = key.name,
+                    type_ = type_,
+                )
+            } else {
+                format!(
+                    "gio::prelude::SettingsExtManual::get(&self.0, {:?})",
+                    key.name
+                )
+            };
+            let (param_type, set) = match type_.as_str() {
+                "s" => (
+                    "&str".to_string(),
+                    format!(
+                        "gio::prelude::SettingsExt::set_string(&self.0, {:?}, value)",
+                        key.name
+                    ),
+                ),
+                "as" => (
+                    "&[&str]".to_string(),
+                    format!(
+                        "gio::prelude::SettingsExt::set_strv(&self.0, {:?}, value)",
+                        key.name
+                    ),
+                ),
+                _ if coerced => (
+                    value_type.clone(),
+                    format!(
+                        "gio::prelude::SettingsExt::set_value(
+            &self.0,
+            {:?},
+            &gio::codegen_to_variant(&value, {:?})
+                .map_err(|err| gio::glib::bool_error!(err.to_string()))?,
+        )",
+                        key.name, type_
+                    ),
+                ),
+                _ => (
+                    value_type.clone(),
+                    format!(
+                        "gio::prelude::SettingsExtManual::set(&self.0, {:?}, &value)",
+                        key.name
+                    ),
+                ),
+            };
+            (value_type, param_type, get, set)
+        }
+        KeyKind::Enum(ref id) => {
+            let enum_type = find_enum(enums, id, false)?.name.clone();
+            let get = format!(
+                "{}::from_value(gio::prelude::SettingsExt::get_enum(&self.0, {key:?}))
+            .expect(\"Invalid value of key '{key}'\")",
+                enum_type,
+                key = key.name,
+            );
+            let set = format!(
+                "gio::prelude::SettingsExt::set_enum(&self.0, {:?}, value as i32)",
+                key.name
+            );
+            (enum_type.clone(), enum_type, get, set)
+        }
+        KeyKind::Flags(ref id) => {
+            let flags_type = find_enum(enums, id, true)?.name.clone();
+            let get = format!(
+                "{}::from_bits_truncate(gio::prelude::SettingsExt::get_flags(&self.0, {:?}))",
+                flags_type, key.name,
+            );
+            let set = format!(
+                "gio::prelude::SettingsExt::set_flags(&self.0, {:?}, value.bits())",
+                key.name
+            );
+            (flags_type.clone(), flags_type, get, set)
+        }
+    };
+
+    writeln!(
+        code,
+        "
+{doc}    pub fn get_{snake}(&self) -> {value_type} {{
+        {get}
+    }}
+
+    pub fn set_{snake}(&self, value: {param_type}) -> Result<(), gio::glib::BoolError> {{
+        {set}
+    }}
+
+    pub fn connect_{snake}_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {{
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some({key:?}), move |settings, _| {{
+            f(&Self(settings.clone()))
+        }})
+    }}
+
+    pub fn bind_{snake}<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {{
+        gio::prelude::SettingsExtManual::bind(&self.0, {key:?}, object, property)
+    }}",
+        doc = doc,
+        snake = snake,
+        key = key.name,
+        value_type = value_type,
+        param_type = param_type,
+        get = get,
+        set = set,
+    )
+    .unwrap();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = r#"
+<schemalist>
+  <enum id="org.example.Color">
+    <value nick="red" value="0"/>
+    <value nick="dark-green" value="1"/>
+  </enum>
+  <flags id="org.example.Style">
+    <value nick="bold" value="1"/>
+    <value nick="italic" value="0x2"/>
+  </flags>
+  <schema id="org.example.app" path="/org/example/app/">
+    <key name="window-title" type="s">
+      <default>"Example"</default>
+      <summary>Title of the
+        main window</summary>
+    </key>
+    <key name="zoom" type="d">
+      <default>1.0</default>
+    </key>
+    <key name="recent-files" type="as">
+      <default>[]</default>
+    </key>
+    <key name="color" enum="org.example.Color">
+      <default>"red"</default>
+    </key>
+    <key name="style" flags="org.example.Style">
+      <default>[]</default>
+    </key>
+  </schema>
+  <schema id="org.example.app.document">
+    <key name="modified" type="b">
+      <default>false</default>
+    </key>
+  </schema>
+</schemalist>
+"#;
+
+    #[test]
+    fn generate() {
+        let code = generate_settings_schema(XML).unwrap();
+
+        assert!(code.contains("pub enum Color {"));
+        assert!(code.contains("    DarkGreen = 1,"));
+        assert!(code.contains("            Self::DarkGreen => \"dark-green\","));
+        assert!(code.contains("pub struct Style: u32 {"));
+        assert!(code.contains("        const ITALIC = 2;"));
+
+        assert!(code.contains("pub struct AppSettings(gio::Settings);"));
+        assert!(code.contains("pub const SCHEMA_ID: &'static str = \"org.example.app\";"));
+        assert!(code.contains("impl Default for AppSettings {"));
+        assert!(code.contains(
+            "    /// Title of the main window\n    pub fn get_window_title(&self) -> String {"
+        ));
+        assert!(code.contains(
+            "    }\n\n    pub fn set_window_title(&self, value: &str) -> Result<(), gio::glib::BoolError> {"
+        ));
+        assert_eq!(code.matches("/// Title of the main window").count(), 1);
+        assert!(code.contains("pub fn get_zoom(&self) -> f64 {"));
+        assert!(code.contains("pub fn set_recent_files(&self, value: &[&str])"));
+        assert!(code.contains("pub fn get_color(&self) -> Color {"));
+        assert!(code.contains("pub fn set_style(&self, value: Style)"));
+        assert!(code.contains("pub fn connect_zoom_changed<F: Fn(&Self) + 'static>"));
+        assert!(code.contains("pub fn bind_color<'a, P: gio::glib::IsA<gio::glib::Object>>("));
+
+        assert!(code.contains("pub struct DocumentSettings(gio::Settings);"));
+        assert!(code.contains("pub fn with_path(path: &str) -> Self {"));
+        assert!(!code.contains("impl Default for DocumentSettings {"));
+    }
+
+    #[test]
+    fn test_schema() {
+        let xml = include_str!("../tests/com.github.gtk-rs.test.gschema.xml");
+        let code = generate_settings_schema(xml).unwrap();
+
+        assert!(code.contains("pub struct TestSettings(gio::Settings);"));
+        assert!(code.contains("pub fn get_test_string(&self) -> String {"));
+        assert!(code.contains("pub fn get_test_bool(&self) -> bool {"));
+    }
+
+    #[test]
+    fn qualified_names() {
+        let xml = r#"
+<schemalist>
+  <enum id="org.example.window.Mode">
+    <value nick="normal" value="0"/>
+  </enum>
+  <enum id="org.example.export.Mode">
+    <value nick="fast" value="0"/>
+  </enum>
+  <flags id="org.example.Style">
+    <value nick="bold" value="1"/>
+  </flags>
+  <schema id="org.example.window" path="/org/example/window/">
+    <key name="mode" enum="org.example.window.Mode">
+      <default>"normal"</default>
+    </key>
+    <key name="export-mode" enum="org.example.export.Mode">
+      <default>"fast"</default>
+    </key>
+  </schema>
+</schemalist>
+"#;
+        let code = generate_settings_schema(xml).unwrap();
+
+        assert!(code.contains("pub enum WindowMode {"));
+        assert!(code.contains("pub enum ExportMode {"));
+        assert!(code.contains("pub struct Style: u32 {"));
+        assert!(code.contains("pub struct WindowSettings(gio::Settings);"));
+        assert!(code.contains("pub fn get_mode(&self) -> WindowMode {"));
+        assert!(code.contains("pub fn get_export_mode(&self) -> ExportMode {"));
+    }
+
+    #[test]
+    fn name_collision() {
+        let xml = r#"
+<schemalist>
+  <enum id="org.example.Mode">
+    <value nick="normal" value="0"/>
+  </enum>
+  <flags id="org.Example.mode">
+    <value nick="bold" value="1"/>
+  </flags>
+</schemalist>
+"#;
+        let err = generate_settings_schema(xml).unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::InvalidArgument));
+        assert!(err.to_string().contains(
+            "enum 'org.example.Mode' and flags 'org.Example.mode' both map to `OrgExampleMode`"
+        ));
+
+        let xml = r#"
+<schemalist>
+  <enum id="org.example.Mode">
+    <value nick="dark-mode" value="0"/>
+    <value nick="dark_mode" value="1"/>
+  </enum>
+</schemalist>
+"#;
+        let err = generate_settings_schema(xml).unwrap_err();
+        assert!(err.to_string().contains(
+            "value 'dark-mode' and value 'dark_mode' both map to `DarkMode` in enum 'org.example.Mode'"
+        ));
+    }
+
+    #[test]
+    fn unknown_enum() {
+        let xml = r#"
+<schemalist>
+  <schema id="org.example.broken" path="/org/example/broken/">
+    <key name="color" enum="org.example.Missing">
+      <default>"red"</default>
+    </key>
+  </schema>
+</schemalist>
+"#;
+        assert!(generate_settings_schema(xml).is_err());
+    }
+}
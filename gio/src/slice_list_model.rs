@@ -0,0 +1,266 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::subclass::prelude::*;
+use crate::ListModel;
+
+use std::cell::{Cell, RefCell};
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct SliceListModel {
+        pub(super) model: RefCell<Option<ListModel>>,
+        pub(super) handler: RefCell<Option<glib::SignalHandlerId>>,
+        pub(super) offset: Cell<u32>,
+        pub(super) size: Cell<u32>,
+        // Number of items in the slice, as last announced with `items-changed`
+        pub(super) n_items: Cell<u32>,
+    }
+
+    impl SliceListModel {
+        pub(super) fn slice_len(&self) -> u32 {
+            let n_items = self
+                .model
+                .borrow()
+                .as_ref()
+                .map_or(0, |model| model.get_n_items());
+            n_items
+                .saturating_sub(self.offset.get())
+                .min(self.size.get())
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SliceListModel {
+        const NAME: &'static str = "SliceListModel";
+        type Type = super::SliceListModel;
+        type ParentType = glib::Object;
+        type Interfaces = (ListModel,);
+    }
+
+    impl ObjectImpl for SliceListModel {
+        fn dispose(&self, _obj: &Self::Type) {
+            if let (Some(model), Some(handler)) = (
+                self.model.borrow_mut().take(),
+                self.handler.borrow_mut().take(),
+            ) {
+                model.disconnect(handler);
+            }
+            self.n_items.set(0);
+        }
+    }
+
+    impl ListModelImpl for SliceListModel {
+        fn get_item_type(&self, _list_model: &Self::Type) -> glib::Type {
+            self.model
+                .borrow()
+                .as_ref()
+                .map_or(glib::Object::static_type(), |model| model.get_item_type())
+        }
+
+        fn get_n_items(&self, _list_model: &Self::Type) -> u32 {
+            self.n_items.get()
+        }
+
+        fn get_item(&self, _list_model: &Self::Type, position: u32) -> Option<glib::Object> {
+            if position >= self.n_items.get() {
+                return None;
+            }
+
+            let model = self.model.borrow().clone()?;
+            model.get_object(self.offset.get() + position)
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`ListModel`] presenting a contiguous range of the items of another model.
+    ///
+    /// The slice contains up to `size` items starting at `offset`, and is shorter if the
+    /// underlying model doesn't have enough items.
+    pub struct SliceListModel(ObjectSubclass<imp::SliceListModel>) @implements ListModel;
+}
+
+impl SliceListModel {
+    pub fn new<P: IsA<ListModel>>(model: &P, offset: u32, size: u32) -> SliceListModel {
+        let obj: SliceListModel =
+            glib::Object::new(&[]).expect("Failed to create slice list model");
+
+        let imp = imp::SliceListModel::from_instance(&obj);
+        let model = model.as_ref().clone();
+        imp.offset.set(offset);
+        imp.size.set(size);
+        *imp.model.borrow_mut() = Some(model.clone());
+        imp.n_items.set(imp.slice_len());
+
+        let obj_weak = obj.downgrade();
+        let handler = model.connect_items_changed(move |_, position, removed, added| {
+            if let Some(obj) = obj_weak.upgrade() {
+                obj.model_items_changed(position, removed, added);
+            }
+        });
+        *imp.handler.borrow_mut() = Some(handler);
+
+        obj
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the underlying model.
+    pub fn model(&self) -> Option<ListModel> {
+        let imp = imp::SliceListModel::from_instance(self);
+        imp.model.borrow().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the position of the first item of the slice in the underlying model.
+    pub fn offset(&self) -> u32 {
+        let imp = imp::SliceListModel::from_instance(self);
+        imp.offset.get()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Moves the slice to start at `offset`, which invalidates all items.
+    pub fn set_offset(&self, offset: u32) {
+        let imp = imp::SliceListModel::from_instance(self);
+        if imp.offset.replace(offset) == offset {
+            return;
+        }
+
+        let removed = imp.n_items.get();
+        let added = imp.slice_len();
+        imp.n_items.set(added);
+        if removed > 0 || added > 0 {
+            self.items_changed(0, removed, added);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the maximum number of items of the slice.
+    pub fn size(&self) -> u32 {
+        let imp = imp::SliceListModel::from_instance(self);
+        imp.size.get()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Changes the maximum number of items of the slice, which adds or removes items at its end.
+    pub fn set_size(&self, size: u32) {
+        let imp = imp::SliceListModel::from_instance(self);
+        imp.size.set(size);
+
+        let old_n_items = imp.n_items.get();
+        let n_items = imp.slice_len();
+        imp.n_items.set(n_items);
+        if n_items > old_n_items {
+            self.items_changed(old_n_items, 0, n_items - old_n_items);
+        } else if n_items < old_n_items {
+            self.items_changed(n_items, old_n_items - n_items, 0);
+        }
+    }
+
+    fn model_items_changed(&self, position: u32, removed: u32, added: u32) {
+        let imp = imp::SliceListModel::from_instance(self);
+        let offset = imp.offset.get();
+        let old_n_items = imp.n_items.get();
+        let n_items = imp.slice_len();
+        imp.n_items.set(n_items);
+
+        if position >= offset + old_n_items && position >= offset + n_items {
+            // After the slice, or it only grows into the space after the slice
+            if n_items > old_n_items {
+                self.items_changed(old_n_items, 0, n_items - old_n_items);
+            }
+        } else if removed == added {
+            // Only the replaced items within the slice change
+            let start = position.max(offset);
+            let end = (position + removed).min(offset + n_items);
+            if start < end {
+                self.items_changed(start - offset, end - start, end - start);
+            }
+        } else if position < offset {
+            // All items of the slice are shifted
+            if old_n_items > 0 || n_items > 0 {
+                self.items_changed(0, old_n_items, n_items);
+            }
+        } else {
+            // The items from `position` to the end of the slice are shifted
+            let position = position - offset;
+            self.items_changed(position, old_n_items - position, n_items - position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{list_item, list_model_values, list_store_with, record_items_changed};
+
+    #[test]
+    fn test_slice() {
+        let store = list_store_with(&[1, 2, 3, 4, 5]);
+        let model = SliceListModel::new(&store, 1, 3);
+        let changes = record_items_changed(&model);
+        assert_eq!(list_model_values(&model), vec![2, 3, 4]);
+        assert!(model.get_object(3).is_none());
+
+        // Replacing an item inside the slice only changes that item
+        store.splice(2, 1, &[list_item(6)]);
+        assert_eq!(list_model_values(&model), vec![2, 6, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 1, 1)));
+
+        // Changes after the slice are ignored
+        store.append(&list_item(7));
+        assert_eq!(list_model_values(&model), vec![2, 6, 4]);
+        assert!(changes.borrow().is_empty());
+
+        // Removing before the slice shifts all of it
+        store.remove(0);
+        assert_eq!(list_model_values(&model), vec![6, 4, 5]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 3, 3)));
+
+        // Inserting inside the slice shifts the items after it
+        store.insert(2, &list_item(8));
+        assert_eq!(list_model_values(&model), vec![6, 8, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 2, 2)));
+    }
+
+    #[test]
+    fn test_short_model() {
+        let store = list_store_with(&[1, 2]);
+        let model = SliceListModel::new(&store, 1, 3);
+        let changes = record_items_changed(&model);
+        assert_eq!(list_model_values(&model), vec![2]);
+
+        // The slice grows into the new items
+        store.append(&list_item(3));
+        assert_eq!(list_model_values(&model), vec![2, 3]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 0, 1)));
+
+        store.remove_all();
+        assert!(list_model_values(&model).is_empty());
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 2, 0)));
+    }
+
+    #[test]
+    fn test_set_offset_and_size() {
+        let store = list_store_with(&[1, 2, 3, 4, 5]);
+        let model = SliceListModel::new(&store, 0, 2);
+        let changes = record_items_changed(&model);
+
+        model.set_size(4);
+        assert_eq!(list_model_values(&model), vec![1, 2, 3, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((2, 0, 2)));
+
+        model.set_offset(3);
+        assert_eq!(model.offset(), 3);
+        assert_eq!(list_model_values(&model), vec![4, 5]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 4, 2)));
+
+        model.set_size(1);
+        assert_eq!(model.size(), 1);
+        assert_eq!(list_model_values(&model), vec![4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 1, 0)));
+    }
+}
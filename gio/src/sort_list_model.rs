@@ -0,0 +1,245 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::filter_list_model::changed_range;
+use crate::prelude::*;
+use crate::subclass::prelude::*;
+use crate::ListModel;
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::mem;
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct SortListModel {
+        pub(super) model: RefCell<Option<ListModel>>,
+        pub(super) handler: RefCell<Option<glib::SignalHandlerId>>,
+        pub(super) sort_func:
+            RefCell<Option<Box<dyn Fn(&glib::Object, &glib::Object) -> Ordering>>>,
+        // Items in sorted order together with their position in the underlying model
+        pub(super) entries: RefCell<Vec<(u32, glib::Object)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SortListModel {
+        const NAME: &'static str = "SortListModel";
+        type Type = super::SortListModel;
+        type ParentType = glib::Object;
+        type Interfaces = (ListModel,);
+    }
+
+    impl ObjectImpl for SortListModel {
+        fn dispose(&self, _obj: &Self::Type) {
+            if let (Some(model), Some(handler)) = (
+                self.model.borrow_mut().take(),
+                self.handler.borrow_mut().take(),
+            ) {
+                model.disconnect(handler);
+            }
+            self.entries.borrow_mut().clear();
+        }
+    }
+
+    impl ListModelImpl for SortListModel {
+        fn get_item_type(&self, _list_model: &Self::Type) -> glib::Type {
+            self.model
+                .borrow()
+                .as_ref()
+                .map(|model| model.get_item_type())
+                .unwrap_or_else(glib::Object::static_type)
+        }
+
+        fn get_n_items(&self, _list_model: &Self::Type) -> u32 {
+            self.entries.borrow().len() as u32
+        }
+
+        fn get_item(&self, _list_model: &Self::Type, position: u32) -> Option<glib::Object> {
+            self.entries
+                .borrow()
+                .get(position as usize)
+                .map(|(_, item)| item.clone())
+        }
+    }
+
+    impl SortListModel {
+        // Orders by the sort function, falling back to the position in the underlying model so
+        // that equal items keep their relative order
+        pub(super) fn compare(&self, a: &(u32, glib::Object), b: &(u32, glib::Object)) -> Ordering {
+            let ordering = match *self.sort_func.borrow() {
+                Some(ref sort_func) => sort_func(&a.1, &b.1),
+                None => Ordering::Equal,
+            };
+            ordering.then(a.0.cmp(&b.0))
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`ListModel`] containing the items of another model sorted by a compare function.
+    ///
+    /// Equal items keep the order of the underlying model. Changes of the underlying model are
+    /// forwarded as the smallest possible `items-changed` range. If the order of some items
+    /// changes, call [`resort`](Self::resort).
+    pub struct SortListModel(ObjectSubclass<imp::SortListModel>) @implements ListModel;
+}
+
+impl SortListModel {
+    pub fn new<P: IsA<ListModel>, F: Fn(&glib::Object, &glib::Object) -> Ordering + 'static>(
+        model: &P,
+        sort_func: F,
+    ) -> SortListModel {
+        let obj: SortListModel = glib::Object::new(&[]).expect("Failed to create sort list model");
+
+        let imp = imp::SortListModel::from_instance(&obj);
+        let model = model.as_ref().clone();
+        *imp.sort_func.borrow_mut() = Some(Box::new(sort_func));
+        *imp.model.borrow_mut() = Some(model.clone());
+
+        let mut entries = (0..model.get_n_items())
+            .filter_map(|i| model.get_object(i).map(|item| (i, item)))
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| imp.compare(a, b));
+        *imp.entries.borrow_mut() = entries;
+
+        let obj_weak = obj.downgrade();
+        let handler = model.connect_items_changed(move |_, position, removed, added| {
+            if let Some(obj) = obj_weak.upgrade() {
+                obj.items_changed_source(position, removed, added);
+            }
+        });
+        *imp.handler.borrow_mut() = Some(handler);
+
+        obj
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the underlying model.
+    pub fn model(&self) -> Option<ListModel> {
+        let imp = imp::SortListModel::from_instance(self);
+        imp.model.borrow().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Replaces the compare function and sorts all items again.
+    pub fn set_sort_func<F: Fn(&glib::Object, &glib::Object) -> Ordering + 'static>(
+        &self,
+        sort_func: F,
+    ) {
+        let imp = imp::SortListModel::from_instance(self);
+        *imp.sort_func.borrow_mut() = Some(Box::new(sort_func));
+        self.resort();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sorts all items again, e.g. after the properties the compare function depends on
+    /// changed.
+    pub fn resort(&self) {
+        let imp = imp::SortListModel::from_instance(self);
+
+        // The entries are taken out while sorting so that the compare function can access
+        // this model
+        let mut entries = mem::take(&mut *imp.entries.borrow_mut());
+        let old = entries.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        entries.sort_by(|a, b| imp.compare(a, b));
+        let new = entries.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        *imp.entries.borrow_mut() = entries;
+
+        if let Some((position, removed, added)) = changed_range(&old, &new) {
+            self.items_changed(position, removed, added);
+        }
+    }
+
+    fn items_changed_source(&self, position: u32, removed: u32, added: u32) {
+        let imp = imp::SortListModel::from_instance(self);
+        let model = match self.model() {
+            Some(model) => model,
+            None => return,
+        };
+
+        // Map the entries to their new positions in the underlying model, dropping the
+        // removed ones
+        let shift = |i: u32| {
+            if i < position {
+                Some(i)
+            } else if i < position + removed {
+                None
+            } else {
+                Some(i - removed + added)
+            }
+        };
+
+        let mut entries = mem::take(&mut *imp.entries.borrow_mut());
+        let old = entries.iter().map(|(i, _)| shift(*i)).collect::<Vec<_>>();
+        entries = entries
+            .into_iter()
+            .filter_map(|(i, item)| shift(i).map(|i| (i, item)))
+            .collect();
+
+        for i in position..position + added {
+            if let Some(item) = model.get_object(i) {
+                let entry = (i, item);
+                let index = match entries.binary_search_by(|probe| imp.compare(probe, &entry)) {
+                    Ok(index) | Err(index) => index,
+                };
+                entries.insert(index, entry);
+            }
+        }
+
+        let new = entries.iter().map(|(i, _)| Some(*i)).collect::<Vec<_>>();
+        *imp.entries.borrow_mut() = entries;
+
+        if let Some((position, removed, added)) = changed_range(&old, &new) {
+            self.items_changed(position, removed, added);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{
+        list_item, list_item_value, list_model_values, list_store_with, record_items_changed,
+    };
+
+    #[test]
+    fn test_sort() {
+        let store = list_store_with(&[5, 3, 1, 4]);
+        let model = SortListModel::new(&store, |a, b| list_item_value(a).cmp(&list_item_value(b)));
+        let changes = record_items_changed(&model);
+        assert_eq!(list_model_values(&model), vec![1, 3, 4, 5]);
+
+        store.append(&list_item(2));
+        assert_eq!(list_model_values(&model), vec![1, 2, 3, 4, 5]);
+        assert_eq!(changes.borrow_mut().pop(), Some((1, 0, 1)));
+
+        // Removes the 3
+        store.remove(1);
+        assert_eq!(list_model_values(&model), vec![1, 2, 4, 5]);
+        assert_eq!(changes.borrow_mut().pop(), Some((2, 1, 0)));
+
+        // Replaces the 5 by a 0
+        store.splice(0, 1, &[list_item(0)]);
+        assert_eq!(list_model_values(&model), vec![0, 1, 2, 4]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 4, 4)));
+
+        model.set_sort_func(|a, b| list_item_value(b).cmp(&list_item_value(a)));
+        assert_eq!(list_model_values(&model), vec![4, 2, 1, 0]);
+        assert_eq!(changes.borrow_mut().pop(), Some((0, 4, 4)));
+    }
+
+    #[test]
+    fn test_sort_stable() {
+        let store = list_store_with(&[1, 2, 3, 4]);
+        let model = SortListModel::new(&store, |a, b| {
+            (list_item_value(a) % 2).cmp(&(list_item_value(b) % 2))
+        });
+        assert_eq!(list_model_values(&model), vec![2, 4, 1, 3]);
+
+        let changes = record_items_changed(&model);
+        model.resort();
+        assert!(changes.borrow().is_empty());
+    }
+}
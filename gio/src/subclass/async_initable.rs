@@ -0,0 +1,356 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, Error, ObjectExt, StaticType};
+
+use std::boxed::Box as Box_;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+
+use crate::prelude::InitableExt;
+use crate::AsyncInitable;
+use crate::Cancellable;
+use crate::IOErrorEnum;
+use crate::Initable;
+use crate::Task;
+
+pub trait AsyncInitableImpl: ObjectImpl + AsyncInitableImplExt {
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that initializes the object.
+    ///
+    /// The future is spawned on the thread-default main context of the caller of
+    /// `g_async_initable_init_async()`. By default the parent implementation is used.
+    fn init_future(
+        &self,
+        initable: &Self::Type,
+        io_priority: glib::Priority,
+        cancellable: Option<&Cancellable>,
+    ) -> Pin<Box_<dyn Future<Output = Result<(), Error>> + 'static>> {
+        self.parent_init_future(initable, io_priority, cancellable)
+    }
+}
+
+pub trait AsyncInitableImplExt: ObjectSubclass {
+    // rustdoc-stripper-ignore-next
+    /// Chains up to the `init_async` of the parent type.
+    ///
+    /// If the parent type does not implement `AsyncInitable`, GIO's default implementation
+    /// would call `Initable::init` in a thread. As the implementation of the object is not
+    /// required to be `Send` and `Sync`, `Initable::init` is instead called from the future,
+    /// which blocks the main context while it runs. This fails if the object does not implement
+    /// [`Initable`](crate::Initable).
+    fn parent_init_future(
+        &self,
+        initable: &Self::Type,
+        io_priority: glib::Priority,
+        cancellable: Option<&Cancellable>,
+    ) -> Pin<Box_<dyn Future<Output = Result<(), Error>> + 'static>>;
+}
+
+impl<T: AsyncInitableImpl> AsyncInitableImplExt for T {
+    fn parent_init_future(
+        &self,
+        initable: &Self::Type,
+        io_priority: glib::Priority,
+        cancellable: Option<&Cancellable>,
+    ) -> Pin<Box_<dyn Future<Output = Result<(), Error>> + 'static>> {
+        if !Self::ParentType::static_type().is_a(AsyncInitable::static_type()) {
+            let initable = initable.clone();
+            let cancellable = cancellable.cloned();
+            return Box_::pin(async move {
+                match initable.dynamic_cast_ref::<Initable>() {
+                    // `init_async` is only called once, like `init`
+                    Some(initable) => unsafe { initable.init(cancellable.as_ref()) },
+                    None => Err(Error::new(
+                        IOErrorEnum::NotSupported,
+                        &format!(
+                            "Asynchronous initialization of {} requires it to implement Initable",
+                            initable.get_type()
+                        ),
+                    )),
+                }
+            });
+        }
+
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data.as_ref().get_parent_interface::<AsyncInitable>()
+                as *const ffi::GAsyncInitableIface;
+
+            let init_async = (*parent_iface)
+                .init_async
+                .expect("no parent \"init_async\" implementation");
+            let init_finish = (*parent_iface)
+                .init_finish
+                .expect("no parent \"init_finish\" implementation");
+
+            let (sender, receiver) = futures_channel::oneshot::channel();
+            let user_data = Box_::new((sender, init_finish));
+
+            unsafe extern "C" fn trampoline(
+                source_object: *mut glib::gobject_ffi::GObject,
+                res: *mut ffi::GAsyncResult,
+                user_data: glib::ffi::gpointer,
+            ) {
+                let (sender, init_finish): ParentInitData = *Box_::from_raw(user_data as *mut _);
+                let mut err = ptr::null_mut();
+                init_finish(source_object as *mut _, res, &mut err);
+                let result = if err.is_null() {
+                    Ok(())
+                } else {
+                    Err(from_glib_full(err))
+                };
+                let _ = sender.send(result);
+            }
+
+            init_async(
+                initable.unsafe_cast_ref::<AsyncInitable>().to_glib_none().0,
+                io_priority.to_glib(),
+                cancellable.to_glib_none().0,
+                Some(trampoline),
+                Box_::into_raw(user_data) as *mut _,
+            );
+
+            Box_::pin(async move {
+                receiver
+                    .await
+                    .expect("parent \"init_async\" never completed")
+            })
+        }
+    }
+}
+
+type ParentInitData = (
+    futures_channel::oneshot::Sender<Result<(), Error>>,
+    unsafe extern "C" fn(
+        *mut ffi::GAsyncInitable,
+        *mut ffi::GAsyncResult,
+        *mut *mut glib::ffi::GError,
+    ) -> glib::ffi::gboolean,
+);
+
+unsafe impl<T: AsyncInitableImpl> IsImplementable<T> for AsyncInitable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.init_async = Some(async_initable_init_async::<T>);
+        iface.init_finish = Some(async_initable_init_finish);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe extern "C" fn async_initable_init_async<T: AsyncInitableImpl>(
+    initable: *mut ffi::GAsyncInitable,
+    io_priority: libc::c_int,
+    cancellable: *mut ffi::GCancellable,
+    callback: ffi::GAsyncReadyCallback,
+    user_data: glib::ffi::gpointer,
+) {
+    let instance = &*(initable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let task: Task = from_glib_full(ffi::g_task_new(
+        initable as *mut _,
+        cancellable,
+        callback,
+        user_data,
+    ));
+    let cancellable = Option::<Cancellable>::from_glib_borrow(cancellable);
+
+    let fut = imp.init_future(
+        from_glib_borrow::<_, AsyncInitable>(initable).unsafe_cast_ref(),
+        from_glib(io_priority),
+        cancellable.as_ref().as_ref(),
+    );
+
+    glib::MainContext::ref_thread_default().spawn_local(async move {
+        match fut.await {
+            Ok(()) => ffi::g_task_return_boolean(task.to_glib_none().0, glib::ffi::GTRUE),
+            Err(e) => task.return_error(e),
+        }
+    });
+}
+
+unsafe extern "C" fn async_initable_init_finish(
+    initable: *mut ffi::GAsyncInitable,
+    res: *mut ffi::GAsyncResult,
+    err: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    if ffi::g_task_is_valid(res as *mut _, initable as *mut _) == glib::ffi::GFALSE {
+        return glib::ffi::GFALSE;
+    }
+
+    ffi::g_task_propagate_boolean(res as *mut ffi::GTask, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::subclass::prelude::*;
+    use crate::InitableError;
+    use glib::Object;
+
+    mod imp {
+        use super::*;
+        use std::cell::Cell;
+
+        #[derive(Default)]
+        pub struct SimpleAsyncInitable {
+            pub initialized: Cell<bool>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for SimpleAsyncInitable {
+            const NAME: &'static str = "SimpleAsyncInitable";
+            type Type = super::SimpleAsyncInitable;
+            type ParentType = glib::Object;
+            type Interfaces = (AsyncInitable,);
+        }
+
+        impl ObjectImpl for SimpleAsyncInitable {}
+
+        impl AsyncInitableImpl for SimpleAsyncInitable {
+            fn init_future(
+                &self,
+                initable: &Self::Type,
+                _io_priority: glib::Priority,
+                cancellable: Option<&Cancellable>,
+            ) -> Pin<Box_<dyn Future<Output = Result<(), Error>> + 'static>> {
+                let cancelled = cancellable.map_or(false, |c| c.is_cancelled());
+                let initable = initable.clone();
+                Box_::pin(async move {
+                    if cancelled {
+                        return Err(Error::new(IOErrorEnum::Cancelled, "init cancelled"));
+                    }
+                    SimpleAsyncInitable::from_instance(&initable)
+                        .initialized
+                        .set(true);
+                    Ok(())
+                })
+            }
+        }
+
+        // Uses the default implementation of `AsyncInitable`, which requires `Initable`
+        #[derive(Default)]
+        pub struct DefaultInitable {
+            pub init_thread: Cell<Option<std::thread::ThreadId>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for DefaultInitable {
+            const NAME: &'static str = "DefaultInitable";
+            type Type = super::DefaultInitable;
+            type ParentType = glib::Object;
+            type Interfaces = (Initable, AsyncInitable);
+        }
+
+        impl ObjectImpl for DefaultInitable {}
+
+        impl InitableImpl for DefaultInitable {
+            fn init(
+                &self,
+                _initable: &Self::Type,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<(), Error> {
+                self.init_thread.set(Some(std::thread::current().id()));
+                Ok(())
+            }
+        }
+
+        impl AsyncInitableImpl for DefaultInitable {}
+
+        #[derive(Default)]
+        pub struct NotInitable;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for NotInitable {
+            const NAME: &'static str = "NotInitable";
+            type Type = super::NotInitable;
+            type ParentType = glib::Object;
+            type Interfaces = (AsyncInitable,);
+        }
+
+        impl ObjectImpl for NotInitable {}
+
+        impl AsyncInitableImpl for NotInitable {}
+    }
+
+    glib::wrapper! {
+        pub struct SimpleAsyncInitable(ObjectSubclass<imp::SimpleAsyncInitable>)
+            @implements AsyncInitable;
+    }
+
+    glib::wrapper! {
+        pub struct DefaultInitable(ObjectSubclass<imp::DefaultInitable>)
+            @implements Initable, AsyncInitable;
+    }
+
+    glib::wrapper! {
+        pub struct NotInitable(ObjectSubclass<imp::NotInitable>)
+            @implements AsyncInitable;
+    }
+
+    #[test]
+    fn test_new_async_initable() {
+        let obj = glib::MainContext::new()
+            .block_on(Object::new_async_initable::<SimpleAsyncInitable>(
+                &[],
+                glib::PRIORITY_DEFAULT,
+                None,
+            ))
+            .unwrap();
+        assert!(imp::SimpleAsyncInitable::from_instance(&obj)
+            .initialized
+            .get());
+    }
+
+    #[test]
+    fn test_new_async_initable_cancelled() {
+        let cancellable = Cancellable::new();
+        cancellable.cancel();
+        let err = glib::MainContext::new()
+            .block_on(Object::new_async_initable::<SimpleAsyncInitable>(
+                &[],
+                glib::PRIORITY_DEFAULT,
+                Some(&cancellable),
+            ))
+            .unwrap_err();
+        match err {
+            InitableError::Failed(err) => assert_eq!(err.kind(), Some(IOErrorEnum::Cancelled)),
+            err => panic!("Unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_default_init_future() {
+        let obj = glib::MainContext::new()
+            .block_on(Object::new_async_initable::<DefaultInitable>(
+                &[],
+                glib::PRIORITY_DEFAULT,
+                None,
+            ))
+            .unwrap();
+        assert_eq!(
+            imp::DefaultInitable::from_instance(&obj).init_thread.get(),
+            Some(std::thread::current().id())
+        );
+    }
+
+    #[test]
+    fn test_default_init_future_without_initable() {
+        let err = glib::MainContext::new()
+            .block_on(Object::new_async_initable::<NotInitable>(
+                &[],
+                glib::PRIORITY_DEFAULT,
+                None,
+            ))
+            .unwrap_err();
+        match err {
+            InitableError::Failed(err) => assert_eq!(err.kind(), Some(IOErrorEnum::NotSupported)),
+            err => panic!("Unexpected error {:?}", err),
+        }
+    }
+}
@@ -0,0 +1,744 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, Error};
+
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use crate::Cancellable;
+use crate::File;
+use crate::FileEnumerator;
+use crate::FileExt;
+use crate::FileInfo;
+use crate::FileInputStream;
+use crate::FileQueryInfoFlags;
+use crate::IOErrorEnum;
+
+fn not_supported<T>() -> Result<T, Error> {
+    Err(Error::new(
+        IOErrorEnum::NotSupported,
+        "Operation not supported",
+    ))
+}
+
+// rustdoc-stripper-ignore-next
+/// Implementation of a [`File`] in Rust.
+///
+/// Only the methods identifying and navigating files are required. Operations that are not
+/// implemented fail with [`IOErrorEnum::NotSupported`], as for any `GFile` lacking them.
+/// GIO implements the asynchronous variants of the operations by calling these methods from
+/// worker threads.
+///
+/// `File::get_child()` is implemented by GIO through
+/// [`resolve_relative_path`](FileImpl::resolve_relative_path). To make `File::for_uri()`
+/// create instances of the implementation for a custom URI scheme, register the scheme with
+/// [`VfsExt::register_uri_scheme`](crate::VfsExt::register_uri_scheme).
+pub trait FileImpl: ObjectImpl + Send + Sync {
+    // rustdoc-stripper-ignore-next
+    /// Returns a new instance referring to the same file.
+    fn dup(&self, file: &Self::Type) -> File;
+
+    fn hash(&self, file: &Self::Type) -> u32 {
+        let uri = self.get_uri(file);
+        let uri: Stash<*const libc::c_char, _> = uri.to_glib_none();
+        unsafe { glib::ffi::g_str_hash(uri.0 as glib::ffi::gconstpointer) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Compares with another file of the same type.
+    fn equal(&self, file: &Self::Type, other: &File) -> bool {
+        self.get_uri(file) == other.get_uri().as_str()
+    }
+
+    fn is_native(&self, _file: &Self::Type) -> bool {
+        false
+    }
+
+    fn has_uri_scheme(&self, file: &Self::Type, uri_scheme: &str) -> bool {
+        self.get_uri_scheme(file)
+            .map_or(false, |scheme| scheme.eq_ignore_ascii_case(uri_scheme))
+    }
+
+    fn get_uri_scheme(&self, file: &Self::Type) -> Option<String>;
+
+    fn get_basename(&self, file: &Self::Type) -> Option<PathBuf>;
+
+    fn get_path(&self, _file: &Self::Type) -> Option<PathBuf> {
+        None
+    }
+
+    fn get_uri(&self, file: &Self::Type) -> String;
+
+    fn get_parse_name(&self, file: &Self::Type) -> String {
+        self.get_uri(file)
+    }
+
+    fn get_parent(&self, file: &Self::Type) -> Option<File>;
+
+    // rustdoc-stripper-ignore-next
+    /// Checks if `file`, a file of the same type, is a descendant of `prefix`.
+    fn prefix_matches(&self, prefix: &Self::Type, file: &File) -> bool {
+        let prefix = self.get_uri(prefix);
+        let uri = file.get_uri();
+        let prefix = prefix.trim_end_matches('/');
+        uri.len() > prefix.len() && uri.starts_with(prefix) && uri.as_bytes()[prefix.len()] == b'/'
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the path of `descendant`, a file of the same type, relative to `parent`.
+    fn get_relative_path(&self, parent: &Self::Type, descendant: &File) -> Option<PathBuf> {
+        if !self.prefix_matches(parent, descendant) {
+            return None;
+        }
+        let parent = self.get_uri(parent);
+        let uri = descendant.get_uri();
+        let relative = &uri[parent.trim_end_matches('/').len() + 1..];
+        unsafe {
+            from_glib_full(glib::ffi::g_uri_unescape_string(
+                relative.to_glib_none().0,
+                ptr::null(),
+            ))
+        }
+    }
+
+    fn resolve_relative_path(&self, file: &Self::Type, relative_path: &Path) -> File;
+
+    fn get_child_for_display_name(
+        &self,
+        file: &Self::Type,
+        display_name: &str,
+    ) -> Result<File, Error> {
+        Ok(self.resolve_relative_path(file, Path::new(display_name)))
+    }
+
+    fn enumerate_children(
+        &self,
+        _file: &Self::Type,
+        _attributes: &str,
+        _flags: FileQueryInfoFlags,
+        _cancellable: Option<&Cancellable>,
+    ) -> Result<FileEnumerator, Error> {
+        not_supported()
+    }
+
+    fn query_info(
+        &self,
+        _file: &Self::Type,
+        _attributes: &str,
+        _flags: FileQueryInfoFlags,
+        _cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        not_supported()
+    }
+
+    fn read(
+        &self,
+        _file: &Self::Type,
+        _cancellable: Option<&Cancellable>,
+    ) -> Result<FileInputStream, Error> {
+        not_supported()
+    }
+}
+
+unsafe impl<T: FileImpl> IsImplementable<T> for File {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.dup = Some(file_dup::<T>);
+        iface.hash = Some(file_hash::<T>);
+        iface.equal = Some(file_equal::<T>);
+        iface.is_native = Some(file_is_native::<T>);
+        iface.has_uri_scheme = Some(file_has_uri_scheme::<T>);
+        iface.get_uri_scheme = Some(file_get_uri_scheme::<T>);
+        iface.get_basename = Some(file_get_basename::<T>);
+        iface.get_path = Some(file_get_path::<T>);
+        iface.get_uri = Some(file_get_uri::<T>);
+        iface.get_parse_name = Some(file_get_parse_name::<T>);
+        iface.get_parent = Some(file_get_parent::<T>);
+        iface.prefix_matches = Some(file_prefix_matches::<T>);
+        iface.get_relative_path = Some(file_get_relative_path::<T>);
+        iface.resolve_relative_path = Some(file_resolve_relative_path::<T>);
+        iface.get_child_for_display_name = Some(file_get_child_for_display_name::<T>);
+        iface.enumerate_children = Some(file_enumerate_children::<T>);
+        iface.query_info = Some(file_query_info::<T>);
+        iface.read_fn = Some(file_read::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe fn set_error<R>(err: *mut *mut glib::ffi::GError, e: Error, ret: R) -> R {
+    if !err.is_null() {
+        let mut e = mem::ManuallyDrop::new(e);
+        *err = e.to_glib_none_mut().0;
+    }
+    ret
+}
+
+unsafe extern "C" fn file_dup<T: FileImpl>(file: *mut ffi::GFile) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.dup(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+        .to_glib_full()
+}
+
+unsafe extern "C" fn file_hash<T: FileImpl>(file: *mut ffi::GFile) -> libc::c_uint {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.hash(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+}
+
+unsafe extern "C" fn file_equal<T: FileImpl>(
+    file1: *mut ffi::GFile,
+    file2: *mut ffi::GFile,
+) -> glib::ffi::gboolean {
+    let instance = &*(file1 as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.equal(
+        from_glib_borrow::<_, File>(file1).unsafe_cast_ref(),
+        &from_glib_borrow::<_, File>(file2),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn file_is_native<T: FileImpl>(file: *mut ffi::GFile) -> glib::ffi::gboolean {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.is_native(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+        .to_glib()
+}
+
+unsafe extern "C" fn file_has_uri_scheme<T: FileImpl>(
+    file: *mut ffi::GFile,
+    uri_scheme: *const libc::c_char,
+) -> glib::ffi::gboolean {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+    let uri_scheme: Borrowed<glib::GString> = from_glib_borrow(uri_scheme);
+
+    imp.has_uri_scheme(
+        from_glib_borrow::<_, File>(file).unsafe_cast_ref(),
+        uri_scheme.as_str(),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn file_get_uri_scheme<T: FileImpl>(file: *mut ffi::GFile) -> *mut libc::c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.get_uri_scheme(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+        .to_glib_full()
+}
+
+unsafe fn path_to_glib_full(path: Option<PathBuf>) -> *mut libc::c_char {
+    match path {
+        Some(path) => glib::ffi::g_strdup(path.to_glib_none().0),
+        None => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn file_get_basename<T: FileImpl>(file: *mut ffi::GFile) -> *mut libc::c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    path_to_glib_full(imp.get_basename(from_glib_borrow::<_, File>(file).unsafe_cast_ref()))
+}
+
+unsafe extern "C" fn file_get_path<T: FileImpl>(file: *mut ffi::GFile) -> *mut libc::c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    path_to_glib_full(imp.get_path(from_glib_borrow::<_, File>(file).unsafe_cast_ref()))
+}
+
+unsafe extern "C" fn file_get_uri<T: FileImpl>(file: *mut ffi::GFile) -> *mut libc::c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.get_uri(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+        .to_glib_full()
+}
+
+unsafe extern "C" fn file_get_parse_name<T: FileImpl>(file: *mut ffi::GFile) -> *mut libc::c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.get_parse_name(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+        .to_glib_full()
+}
+
+unsafe extern "C" fn file_get_parent<T: FileImpl>(file: *mut ffi::GFile) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.get_parent(from_glib_borrow::<_, File>(file).unsafe_cast_ref())
+        .to_glib_full()
+}
+
+unsafe extern "C" fn file_prefix_matches<T: FileImpl>(
+    prefix: *mut ffi::GFile,
+    file: *mut ffi::GFile,
+) -> glib::ffi::gboolean {
+    let instance = &*(prefix as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.prefix_matches(
+        from_glib_borrow::<_, File>(prefix).unsafe_cast_ref(),
+        &from_glib_borrow::<_, File>(file),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn file_get_relative_path<T: FileImpl>(
+    parent: *mut ffi::GFile,
+    descendant: *mut ffi::GFile,
+) -> *mut libc::c_char {
+    let instance = &*(parent as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    path_to_glib_full(imp.get_relative_path(
+        from_glib_borrow::<_, File>(parent).unsafe_cast_ref(),
+        &from_glib_borrow::<_, File>(descendant),
+    ))
+}
+
+unsafe extern "C" fn file_resolve_relative_path<T: FileImpl>(
+    file: *mut ffi::GFile,
+    relative_path: *const libc::c_char,
+) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+    let relative_path: PathBuf = from_glib_none(relative_path);
+
+    imp.resolve_relative_path(
+        from_glib_borrow::<_, File>(file).unsafe_cast_ref(),
+        &relative_path,
+    )
+    .to_glib_full()
+}
+
+unsafe extern "C" fn file_get_child_for_display_name<T: FileImpl>(
+    file: *mut ffi::GFile,
+    display_name: *const libc::c_char,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+    let display_name: Borrowed<glib::GString> = from_glib_borrow(display_name);
+
+    match imp.get_child_for_display_name(
+        from_glib_borrow::<_, File>(file).unsafe_cast_ref(),
+        display_name.as_str(),
+    ) {
+        Ok(child) => child.to_glib_full(),
+        Err(e) => set_error(err, e, ptr::null_mut()),
+    }
+}
+
+unsafe extern "C" fn file_enumerate_children<T: FileImpl>(
+    file: *mut ffi::GFile,
+    attributes: *const libc::c_char,
+    flags: ffi::GFileQueryInfoFlags,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileEnumerator {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+    let attributes: Borrowed<glib::GString> = from_glib_borrow(attributes);
+
+    match imp.enumerate_children(
+        from_glib_borrow::<_, File>(file).unsafe_cast_ref(),
+        attributes.as_str(),
+        from_glib(flags),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(enumerator) => enumerator.to_glib_full(),
+        Err(e) => set_error(err, e, ptr::null_mut()),
+    }
+}
+
+unsafe extern "C" fn file_query_info<T: FileImpl>(
+    file: *mut ffi::GFile,
+    attributes: *const libc::c_char,
+    flags: ffi::GFileQueryInfoFlags,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+    let attributes: Borrowed<glib::GString> = from_glib_borrow(attributes);
+
+    match imp.query_info(
+        from_glib_borrow::<_, File>(file).unsafe_cast_ref(),
+        attributes.as_str(),
+        from_glib(flags),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.to_glib_full(),
+        Err(e) => set_error(err, e, ptr::null_mut()),
+    }
+}
+
+unsafe extern "C" fn file_read<T: FileImpl>(
+    file: *mut ffi::GFile,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInputStream {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    match imp.read(
+        from_glib_borrow::<_, File>(file).unsafe_cast_ref(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(stream) => stream.to_glib_full(),
+        Err(e) => set_error(err, e, ptr::null_mut()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::subclass::prelude::*;
+    use once_cell::sync::OnceCell;
+    use std::cell::{Cell, RefCell};
+
+    // The files of the `MemFile` tree, all in `/dir`
+    const FILES: &[&str] = &["a.txt", "b.txt"];
+
+    fn contents(path: &str) -> String {
+        format!("contents of {}", path)
+    }
+
+    mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct MemFile {
+            pub path: OnceCell<String>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MemFile {
+            const NAME: &'static str = "MemFile";
+            type Type = super::MemFile;
+            type ParentType = glib::Object;
+            type Interfaces = (File,);
+        }
+
+        impl ObjectImpl for MemFile {}
+
+        impl FileImpl for MemFile {
+            fn dup(&self, _file: &Self::Type) -> File {
+                super::MemFile::new(self.path.get().unwrap()).upcast()
+            }
+
+            fn get_uri_scheme(&self, _file: &Self::Type) -> Option<String> {
+                Some("rsmem".to_string())
+            }
+
+            fn get_basename(&self, _file: &Self::Type) -> Option<PathBuf> {
+                let path = self.path.get().unwrap();
+                Some(PathBuf::from(path.rsplit('/').next().unwrap()))
+            }
+
+            fn get_uri(&self, _file: &Self::Type) -> String {
+                format!("rsmem://{}", self.path.get().unwrap())
+            }
+
+            fn get_parent(&self, _file: &Self::Type) -> Option<File> {
+                let path = self.path.get().unwrap();
+                match path.rfind('/') {
+                    Some(0) if path.len() > 1 => Some(super::MemFile::new("/").upcast()),
+                    Some(pos) if pos > 0 => Some(super::MemFile::new(&path[..pos]).upcast()),
+                    _ => None,
+                }
+            }
+
+            fn resolve_relative_path(&self, _file: &Self::Type, relative_path: &Path) -> File {
+                let path = self.path.get().unwrap();
+                let relative_path = relative_path.to_str().unwrap();
+                let path = if relative_path.starts_with('/') {
+                    relative_path.to_string()
+                } else {
+                    format!("{}/{}", path.trim_end_matches('/'), relative_path)
+                };
+                super::MemFile::new(&path).upcast()
+            }
+
+            fn enumerate_children(
+                &self,
+                _file: &Self::Type,
+                _attributes: &str,
+                _flags: FileQueryInfoFlags,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<FileEnumerator, Error> {
+                if self.path.get().unwrap() != "/dir" {
+                    return Err(Error::new(IOErrorEnum::NotDirectory, "Not a directory"));
+                }
+                let enumerator = glib::Object::new::<super::MemEnumerator>(&[]).unwrap();
+                MemEnumerator::from_instance(&enumerator)
+                    .children
+                    .replace(FILES.iter().rev().map(|name| name.to_string()).collect());
+                Ok(enumerator.upcast())
+            }
+
+            fn read(
+                &self,
+                _file: &Self::Type,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<FileInputStream, Error> {
+                let path = self.path.get().unwrap();
+                match path.strip_prefix("/dir/") {
+                    Some(name) if FILES.contains(&name) => {
+                        let stream = glib::Object::new::<super::MemInputStream>(&[]).unwrap();
+                        MemInputStream::from_instance(&stream)
+                            .data
+                            .replace(contents(path).into_bytes());
+                        Ok(stream.upcast())
+                    }
+                    _ => Err(Error::new(IOErrorEnum::NotFound, "No such file")),
+                }
+            }
+        }
+
+        #[derive(Default)]
+        pub struct MemEnumerator {
+            pub children: RefCell<Vec<String>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MemEnumerator {
+            const NAME: &'static str = "MemEnumerator";
+            type Type = super::MemEnumerator;
+            type ParentType = FileEnumerator;
+        }
+
+        impl ObjectImpl for MemEnumerator {}
+
+        impl FileEnumeratorImpl for MemEnumerator {
+            fn next_file(
+                &self,
+                _enumerator: &Self::Type,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<Option<FileInfo>, Error> {
+                Ok(self.children.borrow_mut().pop().map(|name| {
+                    let info = FileInfo::new();
+                    info.set_name(&name);
+                    info
+                }))
+            }
+        }
+
+        #[derive(Default)]
+        pub struct MemInputStream {
+            pub data: RefCell<Vec<u8>>,
+            pub pos: Cell<usize>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MemInputStream {
+            const NAME: &'static str = "MemInputStream";
+            type Type = super::MemInputStream;
+            type ParentType = FileInputStream;
+        }
+
+        impl ObjectImpl for MemInputStream {}
+
+        impl InputStreamImpl for MemInputStream {
+            fn read(
+                &self,
+                _stream: &Self::Type,
+                buffer: &mut [u8],
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<usize, Error> {
+                let data = self.data.borrow();
+                let pos = self.pos.get();
+                let len = buffer.len().min(data.len() - pos);
+                buffer[..len].copy_from_slice(&data[pos..pos + len]);
+                self.pos.set(pos + len);
+                Ok(len)
+            }
+        }
+
+        impl FileInputStreamImpl for MemInputStream {
+            fn tell(&self, _stream: &Self::Type) -> i64 {
+                self.pos.get() as i64
+            }
+
+            fn can_seek(&self, _stream: &Self::Type) -> bool {
+                true
+            }
+
+            fn seek(
+                &self,
+                _stream: &Self::Type,
+                offset: i64,
+                type_: glib::SeekType,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<(), Error> {
+                let base = match type_ {
+                    glib::SeekType::Set => 0,
+                    glib::SeekType::Cur => self.pos.get() as i64,
+                    glib::SeekType::End => self.data.borrow().len() as i64,
+                    _ => unreachable!(),
+                };
+                let pos = base + offset;
+                if pos < 0 || pos > self.data.borrow().len() as i64 {
+                    return Err(Error::new(IOErrorEnum::InvalidArgument, "Invalid offset"));
+                }
+                self.pos.set(pos as usize);
+                Ok(())
+            }
+
+            fn query_info(
+                &self,
+                _stream: &Self::Type,
+                _attributes: &str,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<FileInfo, Error> {
+                let info = FileInfo::new();
+                info.set_size(self.data.borrow().len() as i64);
+                Ok(info)
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct MemFile(ObjectSubclass<imp::MemFile>) @implements File;
+    }
+
+    glib::wrapper! {
+        pub struct MemEnumerator(ObjectSubclass<imp::MemEnumerator>) @extends FileEnumerator;
+    }
+
+    glib::wrapper! {
+        pub struct MemInputStream(ObjectSubclass<imp::MemInputStream>)
+            @extends FileInputStream, crate::InputStream, @implements crate::Seekable;
+    }
+
+    impl MemFile {
+        fn new(path: &str) -> Self {
+            let file = glib::Object::new::<Self>(&[]).unwrap();
+            imp::MemFile::from_instance(&file)
+                .path
+                .set(path.to_string())
+                .unwrap();
+            file
+        }
+    }
+
+    #[test]
+    fn test_navigation() {
+        let dir = MemFile::new("/dir");
+        let child = dir.get_child("file.txt");
+
+        assert_eq!(child.get_uri(), "rsmem:///dir/file.txt");
+        assert_eq!(child.get_basename(), Some(PathBuf::from("file.txt")));
+        assert!(child.has_uri_scheme("RSMEM"));
+        assert!(child.has_prefix(&dir));
+        assert!(child.get_parent().unwrap().equal(&dir));
+        assert_eq!(
+            dir.get_relative_path(&child),
+            Some(PathBuf::from("file.txt"))
+        );
+    }
+
+    #[test]
+    fn test_not_supported() {
+        let file = MemFile::new("/dir/a.txt");
+        let err = file
+            .query_info("*", FileQueryInfoFlags::NONE, crate::NONE_CANCELLABLE)
+            .unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::NotSupported));
+    }
+
+    #[test]
+    fn test_enumerate_children() {
+        let dir = MemFile::new("/dir");
+        let enumerator = dir
+            .enumerate_children(
+                "standard::name",
+                FileQueryInfoFlags::NONE,
+                crate::NONE_CANCELLABLE,
+            )
+            .unwrap();
+
+        let mut names = Vec::new();
+        while let Some(info) = enumerator.next_file(crate::NONE_CANCELLABLE).unwrap() {
+            names.push(info.get_name());
+        }
+        assert_eq!(names, [PathBuf::from("a.txt"), PathBuf::from("b.txt")]);
+        enumerator.close(crate::NONE_CANCELLABLE).unwrap();
+
+        let err = dir
+            .get_child("a.txt")
+            .enumerate_children("*", FileQueryInfoFlags::NONE, crate::NONE_CANCELLABLE)
+            .unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::NotDirectory));
+    }
+
+    #[test]
+    fn test_read() {
+        let file = MemFile::new("/dir").get_child("b.txt");
+        let stream = file.read(crate::NONE_CANCELLABLE).unwrap();
+        let expected = contents("/dir/b.txt");
+
+        let mut buf = vec![0; 64];
+        let (len, err) = stream.read_all(&mut buf, crate::NONE_CANCELLABLE).unwrap();
+        assert!(err.is_none());
+        assert_eq!(&buf[..len], expected.as_bytes());
+        assert_eq!(stream.tell(), expected.len() as i64);
+
+        assert!(stream.can_seek());
+        stream
+            .seek(-3, glib::SeekType::End, crate::NONE_CANCELLABLE)
+            .unwrap();
+        let len = stream.read(&mut buf, crate::NONE_CANCELLABLE).unwrap();
+        assert_eq!(&buf[..len], b"txt");
+
+        let info = stream
+            .query_info("standard::size", crate::NONE_CANCELLABLE)
+            .unwrap();
+        assert_eq!(info.get_size(), expected.len() as i64);
+
+        let err = MemFile::new("/dir/c.txt")
+            .read(crate::NONE_CANCELLABLE)
+            .unwrap_err();
+        assert_eq!(err.kind(), Some(IOErrorEnum::NotFound));
+    }
+
+    #[cfg(feature = "v2_50")]
+    #[test]
+    fn test_uri_scheme() {
+        let vfs = crate::Vfs::get_default();
+        assert!(vfs.register_uri_scheme(
+            "rsmem",
+            Some(Box::new(|_: &crate::Vfs, uri: &str| {
+                MemFile::new(uri.trim_start_matches("rsmem://")).upcast()
+            })),
+            None,
+        ));
+
+        let file = File::for_uri("rsmem:///dir/file.txt");
+        assert!(file.downcast_ref::<MemFile>().is_some());
+        assert_eq!(file.get_basename(), Some(PathBuf::from("file.txt")));
+
+        assert!(vfs.unregister_uri_scheme("rsmem"));
+    }
+}
@@ -0,0 +1,129 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use glib::{Cast, Error};
+
+use crate::Cancellable;
+use crate::FileEnumerator;
+use crate::FileInfo;
+
+use std::mem;
+use std::ptr;
+
+pub trait FileEnumeratorImpl: ObjectImpl + FileEnumeratorImplExt + Send {
+    fn next_file(
+        &self,
+        enumerator: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<Option<FileInfo>, Error>;
+
+    fn close(
+        &self,
+        enumerator: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        self.parent_close(enumerator, cancellable)
+    }
+}
+
+pub trait FileEnumeratorImplExt: ObjectSubclass {
+    fn parent_close(
+        &self,
+        enumerator: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error>;
+}
+
+impl<T: FileEnumeratorImpl> FileEnumeratorImplExt for T {
+    fn parent_close(
+        &self,
+        enumerator: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GFileEnumeratorClass;
+            let f = match (*parent_class).close_fn {
+                Some(f) => f,
+                None => return Ok(()),
+            };
+            let mut err = ptr::null_mut();
+            if from_glib(f(
+                enumerator
+                    .unsafe_cast_ref::<FileEnumerator>()
+                    .to_glib_none()
+                    .0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            )) {
+                Ok(())
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+}
+
+unsafe impl<T: FileEnumeratorImpl> IsSubclassable<T> for FileEnumerator {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <glib::Object as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.next_file = Some(enumerator_next_file::<T>);
+        klass.close_fn = Some(enumerator_close::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <glib::Object as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn enumerator_next_file<T: FileEnumeratorImpl>(
+    ptr: *mut ffi::GFileEnumerator,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<FileEnumerator> = from_glib_borrow(ptr);
+
+    match imp.next_file(
+        wrap.unsafe_cast_ref(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.to_glib_full(),
+        Err(e) => {
+            let mut e = mem::ManuallyDrop::new(e);
+            *err = e.to_glib_none_mut().0;
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn enumerator_close<T: FileEnumeratorImpl>(
+    ptr: *mut ffi::GFileEnumerator,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<FileEnumerator> = from_glib_borrow(ptr);
+
+    match imp.close(
+        wrap.unsafe_cast_ref(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(_) => glib::ffi::GTRUE,
+        Err(e) => {
+            let mut e = mem::ManuallyDrop::new(e);
+            *err = e.to_glib_none_mut().0;
+            glib::ffi::GFALSE
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, Error, SeekType};
+
+use std::mem;
+use std::ptr;
+
+use crate::subclass::prelude::InputStreamImpl;
+use crate::Cancellable;
+use crate::FileInfo;
+use crate::FileInputStream;
+use crate::IOErrorEnum;
+use crate::InputStream;
+
+pub trait FileInputStreamImpl: InputStreamImpl + FileInputStreamImplExt {
+    fn tell(&self, stream: &Self::Type) -> i64 {
+        self.parent_tell(stream)
+    }
+
+    fn can_seek(&self, stream: &Self::Type) -> bool {
+        self.parent_can_seek(stream)
+    }
+
+    fn seek(
+        &self,
+        stream: &Self::Type,
+        offset: i64,
+        type_: SeekType,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        self.parent_seek(stream, offset, type_, cancellable)
+    }
+
+    fn query_info(
+        &self,
+        stream: &Self::Type,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        self.parent_query_info(stream, attributes, cancellable)
+    }
+}
+
+// The vfuncs of `GFileInputStream` itself are unset, in which case the parent implementations
+// behave like the `g_file_input_stream_*()` functions.
+pub trait FileInputStreamImplExt: ObjectSubclass {
+    fn parent_tell(&self, stream: &Self::Type) -> i64;
+
+    fn parent_can_seek(&self, stream: &Self::Type) -> bool;
+
+    fn parent_seek(
+        &self,
+        stream: &Self::Type,
+        offset: i64,
+        type_: SeekType,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error>;
+
+    fn parent_query_info(
+        &self,
+        stream: &Self::Type,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error>;
+}
+
+impl<T: FileInputStreamImpl> FileInputStreamImplExt for T {
+    fn parent_tell(&self, stream: &Self::Type) -> i64 {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GFileInputStreamClass;
+            match (*parent_class).tell {
+                Some(f) => f(stream.unsafe_cast_ref::<FileInputStream>().to_glib_none().0),
+                None => 0,
+            }
+        }
+    }
+
+    fn parent_can_seek(&self, stream: &Self::Type) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GFileInputStreamClass;
+            match (*parent_class).can_seek {
+                Some(f) => from_glib(f(stream
+                    .unsafe_cast_ref::<FileInputStream>()
+                    .to_glib_none()
+                    .0)),
+                None => (*parent_class).seek.is_some(),
+            }
+        }
+    }
+
+    fn parent_seek(
+        &self,
+        stream: &Self::Type,
+        offset: i64,
+        type_: SeekType,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GFileInputStreamClass;
+            let f = match (*parent_class).seek {
+                Some(f) => f,
+                None => {
+                    return Err(Error::new(
+                        IOErrorEnum::NotSupported,
+                        "Seek not supported on stream",
+                    ))
+                }
+            };
+            let mut err = ptr::null_mut();
+            if from_glib(f(
+                stream.unsafe_cast_ref::<FileInputStream>().to_glib_none().0,
+                offset,
+                type_.to_glib(),
+                cancellable.to_glib_none().0,
+                &mut err,
+            )) {
+                Ok(())
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_query_info(
+        &self,
+        stream: &Self::Type,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GFileInputStreamClass;
+            let f = match (*parent_class).query_info {
+                Some(f) => f,
+                None => {
+                    return Err(Error::new(
+                        IOErrorEnum::NotSupported,
+                        "Stream doesn't support query_info",
+                    ))
+                }
+            };
+            let mut err = ptr::null_mut();
+            let info = f(
+                stream.unsafe_cast_ref::<FileInputStream>().to_glib_none().0,
+                attributes.to_glib_none().0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(info))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+}
+
+unsafe impl<T: FileInputStreamImpl> IsSubclassable<T> for FileInputStream {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <InputStream as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.tell = Some(stream_tell::<T>);
+        klass.can_seek = Some(stream_can_seek::<T>);
+        klass.seek = Some(stream_seek::<T>);
+        klass.query_info = Some(stream_query_info::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <InputStream as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn stream_tell<T: FileInputStreamImpl>(ptr: *mut ffi::GFileInputStream) -> i64 {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<FileInputStream> = from_glib_borrow(ptr);
+
+    imp.tell(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn stream_can_seek<T: FileInputStreamImpl>(
+    ptr: *mut ffi::GFileInputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<FileInputStream> = from_glib_borrow(ptr);
+
+    imp.can_seek(wrap.unsafe_cast_ref()).to_glib()
+}
+
+unsafe extern "C" fn stream_seek<T: FileInputStreamImpl>(
+    ptr: *mut ffi::GFileInputStream,
+    offset: i64,
+    type_: glib::ffi::GSeekType,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<FileInputStream> = from_glib_borrow(ptr);
+
+    match imp.seek(
+        wrap.unsafe_cast_ref(),
+        offset,
+        from_glib(type_),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(()) => glib::ffi::GTRUE,
+        Err(e) => {
+            let mut e = mem::ManuallyDrop::new(e);
+            *err = e.to_glib_none_mut().0;
+            glib::ffi::GFALSE
+        }
+    }
+}
+
+unsafe extern "C" fn stream_query_info<T: FileInputStreamImpl>(
+    ptr: *mut ffi::GFileInputStream,
+    attributes: *const libc::c_char,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<FileInputStream> = from_glib_borrow(ptr);
+
+    match imp.query_info(
+        wrap.unsafe_cast_ref(),
+        &glib::GString::from_glib_borrow(attributes),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.to_glib_full(),
+        Err(e) => {
+            let mut e = mem::ManuallyDrop::new(e);
+            *err = e.to_glib_none_mut().0;
+            ptr::null_mut()
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, Error};
+
+use std::mem;
+use std::ptr;
+
+use crate::Cancellable;
+use crate::Initable;
+
+pub trait InitableImpl: ObjectImpl {
+    fn init(&self, initable: &Self::Type, cancellable: Option<&Cancellable>) -> Result<(), Error>;
+}
+
+pub trait InitableImplExt: ObjectSubclass {
+    fn parent_init(
+        &self,
+        initable: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error>;
+}
+
+impl<T: InitableImpl> InitableImplExt for T {
+    fn parent_init(
+        &self,
+        initable: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().get_parent_interface::<Initable>() as *const ffi::GInitableIface;
+
+            let func = (*parent_iface)
+                .init
+                .expect("no parent \"init\" implementation");
+
+            let mut err = ptr::null_mut();
+            func(
+                initable.unsafe_cast_ref::<Initable>().to_glib_none().0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+
+            if err.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+}
+
+unsafe impl<T: InitableImpl> IsImplementable<T> for Initable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.init = Some(initable_init::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe extern "C" fn initable_init<T: InitableImpl>(
+    initable: *mut ffi::GInitable,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    let instance = &*(initable as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    match imp.init(
+        from_glib_borrow::<_, Initable>(initable).unsafe_cast_ref(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(()) => glib::ffi::GTRUE,
+        Err(e) => {
+            if !err.is_null() {
+                let mut e = mem::ManuallyDrop::new(e);
+                *err = e.to_glib_none_mut().0;
+            }
+            glib::ffi::GFALSE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use glib::Object;
+
+    mod imp {
+        use super::*;
+        use std::cell::Cell;
+
+        #[derive(Default)]
+        pub struct SimpleInitable {
+            pub fail: Cell<bool>,
+            pub initialized: Cell<bool>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for SimpleInitable {
+            const NAME: &'static str = "SimpleInitable";
+            type Type = super::SimpleInitable;
+            type ParentType = glib::Object;
+            type Interfaces = (Initable,);
+        }
+
+        impl ObjectImpl for SimpleInitable {}
+
+        impl InitableImpl for SimpleInitable {
+            fn init(
+                &self,
+                _initable: &Self::Type,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<(), Error> {
+                if self.fail.get() {
+                    return Err(Error::new(crate::IOErrorEnum::Failed, "init failed"));
+                }
+                self.initialized.set(true);
+                Ok(())
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct SimpleInitable(ObjectSubclass<imp::SimpleInitable>)
+            @implements Initable;
+    }
+
+    #[test]
+    fn test_new_initable() {
+        let obj = Object::new_initable::<SimpleInitable>(&[], None).unwrap();
+        assert!(imp::SimpleInitable::from_instance(&obj).initialized.get());
+    }
+
+    #[test]
+    fn test_init_error() {
+        let obj = Object::new::<SimpleInitable>(&[]).unwrap();
+        imp::SimpleInitable::from_instance(&obj).fail.set(true);
+        let err = unsafe { obj.init(crate::NONE_CANCELLABLE) }.unwrap_err();
+        assert_eq!(err.kind(), Some(crate::IOErrorEnum::Failed));
+    }
+}
@@ -3,12 +3,20 @@
 mod action_group;
 mod action_map;
 mod application;
+mod async_initable;
+mod file;
+mod file_enumerator;
+mod file_input_stream;
+mod initable;
 mod input_stream;
 mod io_stream;
 #[cfg(any(feature = "v2_44", feature = "dox"))]
 mod list_model;
 mod output_stream;
+mod pollable_input_stream;
+mod pollable_output_stream;
 mod seekable;
+mod settings_backend;
 
 pub use self::application::ArgumentList;
 
@@ -19,10 +27,20 @@ pub mod prelude {
     pub use super::action_group::{ActionGroupImpl, ActionGroupImplExt};
     pub use super::action_map::{ActionMapImpl, ActionMapImplExt};
     pub use super::application::{ApplicationImpl, ApplicationImplExt};
+    pub use super::async_initable::{AsyncInitableImpl, AsyncInitableImplExt};
+    pub use super::file::FileImpl;
+    pub use super::file_enumerator::{FileEnumeratorImpl, FileEnumeratorImplExt};
+    pub use super::file_input_stream::{FileInputStreamImpl, FileInputStreamImplExt};
+    pub use super::initable::{InitableImpl, InitableImplExt};
     pub use super::input_stream::{InputStreamImpl, InputStreamImplExt};
     pub use super::io_stream::{IOStreamImpl, IOStreamImplExt};
     #[cfg(any(feature = "v2_44", feature = "dox"))]
     pub use super::list_model::{ListModelImpl, ListModelImplExt};
     pub use super::output_stream::{OutputStreamImpl, OutputStreamImplExt};
+    pub use super::pollable_input_stream::{PollableInputStreamImpl, PollableInputStreamImplExt};
+    pub use super::pollable_output_stream::{
+        PollableOutputStreamImpl, PollableOutputStreamImplExt,
+    };
     pub use super::seekable::{SeekableImpl, SeekableImplExt};
+    pub use super::settings_backend::{SettingsBackendImpl, SettingsBackendImplExt};
 }
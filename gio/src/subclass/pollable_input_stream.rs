@@ -0,0 +1,235 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::translate::*;
+use glib::Cast;
+use glib::Error;
+
+use glib::subclass::prelude::*;
+
+use std::mem;
+use std::ptr;
+
+use crate::Cancellable;
+use crate::PollableInputStream;
+
+pub trait PollableInputStreamImpl: ObjectImpl + PollableInputStreamImplExt + Send {
+    fn can_poll(&self, stream: &Self::Type) -> bool {
+        self.parent_can_poll(stream)
+    }
+
+    fn is_readable(&self, stream: &Self::Type) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a source that is dispatched once the stream is readable.
+    ///
+    /// [`pollable_source_new_full`](crate::pollable_source_new_full) can be used to create a
+    /// source with the right type from a child source signalling readiness.
+    fn create_source(&self, stream: &Self::Type, cancellable: Option<&Cancellable>)
+        -> glib::Source;
+
+    // rustdoc-stripper-ignore-next
+    /// Reads from the stream without blocking, failing with
+    /// [`IOErrorEnum::WouldBlock`](crate::IOErrorEnum::WouldBlock) if no data is available.
+    fn read_nonblocking(&self, stream: &Self::Type, buffer: &mut [u8]) -> Result<usize, Error>;
+}
+
+pub trait PollableInputStreamImplExt: ObjectSubclass {
+    fn parent_can_poll(&self, stream: &Self::Type) -> bool;
+    fn parent_is_readable(&self, stream: &Self::Type) -> bool;
+    fn parent_create_source(
+        &self,
+        stream: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> glib::Source;
+    fn parent_read_nonblocking(
+        &self,
+        stream: &Self::Type,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error>;
+}
+
+impl<T: PollableInputStreamImpl> PollableInputStreamImplExt for T {
+    fn parent_can_poll(&self, stream: &Self::Type) -> bool {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableInputStream>()
+                as *const ffi::GPollableInputStreamInterface;
+
+            match (*parent_iface).can_poll {
+                Some(func) => from_glib(func(
+                    stream
+                        .unsafe_cast_ref::<PollableInputStream>()
+                        .to_glib_none()
+                        .0,
+                )),
+                None => true,
+            }
+        }
+    }
+
+    fn parent_is_readable(&self, stream: &Self::Type) -> bool {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableInputStream>()
+                as *const ffi::GPollableInputStreamInterface;
+
+            let func = (*parent_iface)
+                .is_readable
+                .expect("no parent \"is_readable\" implementation");
+            from_glib(func(
+                stream
+                    .unsafe_cast_ref::<PollableInputStream>()
+                    .to_glib_none()
+                    .0,
+            ))
+        }
+    }
+
+    fn parent_create_source(
+        &self,
+        stream: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> glib::Source {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableInputStream>()
+                as *const ffi::GPollableInputStreamInterface;
+
+            let func = (*parent_iface)
+                .create_source
+                .expect("no parent \"create_source\" implementation");
+            from_glib_full(func(
+                stream
+                    .unsafe_cast_ref::<PollableInputStream>()
+                    .to_glib_none()
+                    .0,
+                cancellable.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_read_nonblocking(
+        &self,
+        stream: &Self::Type,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableInputStream>()
+                as *const ffi::GPollableInputStreamInterface;
+
+            let func = (*parent_iface)
+                .read_nonblocking
+                .expect("no parent \"read_nonblocking\" implementation");
+            let mut err = ptr::null_mut();
+            let res = func(
+                stream
+                    .unsafe_cast_ref::<PollableInputStream>()
+                    .to_glib_none()
+                    .0,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut err,
+            );
+            if res == -1 {
+                Err(from_glib_full(err))
+            } else {
+                assert!(res >= 0);
+                let res = res as usize;
+                assert!(res <= buffer.len());
+                Ok(res)
+            }
+        }
+    }
+}
+
+unsafe impl<T: PollableInputStreamImpl> IsImplementable<T> for PollableInputStream {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.can_poll = Some(pollable_input_stream_can_poll::<T>);
+        iface.is_readable = Some(pollable_input_stream_is_readable::<T>);
+        iface.create_source = Some(pollable_input_stream_create_source::<T>);
+        iface.read_nonblocking = Some(pollable_input_stream_read_nonblocking::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe extern "C" fn pollable_input_stream_can_poll<T: PollableInputStreamImpl>(
+    stream: *mut ffi::GPollableInputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.can_poll(from_glib_borrow::<_, PollableInputStream>(stream).unsafe_cast_ref())
+        .to_glib()
+}
+
+unsafe extern "C" fn pollable_input_stream_is_readable<T: PollableInputStreamImpl>(
+    stream: *mut ffi::GPollableInputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.is_readable(from_glib_borrow::<_, PollableInputStream>(stream).unsafe_cast_ref())
+        .to_glib()
+}
+
+unsafe extern "C" fn pollable_input_stream_create_source<T: PollableInputStreamImpl>(
+    stream: *mut ffi::GPollableInputStream,
+    cancellable: *mut ffi::GCancellable,
+) -> *mut glib::ffi::GSource {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.create_source(
+        from_glib_borrow::<_, PollableInputStream>(stream).unsafe_cast_ref(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    )
+    .to_glib_full()
+}
+
+unsafe extern "C" fn pollable_input_stream_read_nonblocking<T: PollableInputStreamImpl>(
+    stream: *mut ffi::GPollableInputStream,
+    buffer: *mut u8,
+    count: usize,
+    err: *mut *mut glib::ffi::GError,
+) -> isize {
+    assert!(count <= isize::MAX as usize);
+
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    match imp.read_nonblocking(
+        from_glib_borrow::<_, PollableInputStream>(stream).unsafe_cast_ref(),
+        if count == 0 {
+            &mut []
+        } else {
+            std::slice::from_raw_parts_mut(buffer, count)
+        },
+    ) {
+        Ok(res) => {
+            assert!(res <= isize::MAX as usize);
+            assert!(res <= count);
+            res as isize
+        }
+        Err(e) => {
+            if !err.is_null() {
+                let mut e = mem::ManuallyDrop::new(e);
+                *err = e.to_glib_none_mut().0;
+            }
+            -1
+        }
+    }
+}
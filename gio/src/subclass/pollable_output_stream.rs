@@ -0,0 +1,227 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::translate::*;
+use glib::Cast;
+use glib::Error;
+
+use glib::subclass::prelude::*;
+
+use std::mem;
+use std::ptr;
+
+use crate::Cancellable;
+use crate::PollableOutputStream;
+
+pub trait PollableOutputStreamImpl: ObjectImpl + PollableOutputStreamImplExt + Send {
+    fn can_poll(&self, stream: &Self::Type) -> bool {
+        self.parent_can_poll(stream)
+    }
+
+    fn is_writable(&self, stream: &Self::Type) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a source that is dispatched once the stream is writable.
+    ///
+    /// [`pollable_source_new_full`](crate::pollable_source_new_full) can be used to create a
+    /// source with the right type from a child source signalling readiness.
+    fn create_source(&self, stream: &Self::Type, cancellable: Option<&Cancellable>)
+        -> glib::Source;
+
+    // rustdoc-stripper-ignore-next
+    /// Writes to the stream without blocking, failing with
+    /// [`IOErrorEnum::WouldBlock`](crate::IOErrorEnum::WouldBlock) if no data can be written.
+    fn write_nonblocking(&self, stream: &Self::Type, buffer: &[u8]) -> Result<usize, Error>;
+}
+
+pub trait PollableOutputStreamImplExt: ObjectSubclass {
+    fn parent_can_poll(&self, stream: &Self::Type) -> bool;
+    fn parent_is_writable(&self, stream: &Self::Type) -> bool;
+    fn parent_create_source(
+        &self,
+        stream: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> glib::Source;
+    fn parent_write_nonblocking(&self, stream: &Self::Type, buffer: &[u8]) -> Result<usize, Error>;
+}
+
+impl<T: PollableOutputStreamImpl> PollableOutputStreamImplExt for T {
+    fn parent_can_poll(&self, stream: &Self::Type) -> bool {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableOutputStream>()
+                as *const ffi::GPollableOutputStreamInterface;
+
+            match (*parent_iface).can_poll {
+                Some(func) => from_glib(func(
+                    stream
+                        .unsafe_cast_ref::<PollableOutputStream>()
+                        .to_glib_none()
+                        .0,
+                )),
+                None => true,
+            }
+        }
+    }
+
+    fn parent_is_writable(&self, stream: &Self::Type) -> bool {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableOutputStream>()
+                as *const ffi::GPollableOutputStreamInterface;
+
+            let func = (*parent_iface)
+                .is_writable
+                .expect("no parent \"is_writable\" implementation");
+            from_glib(func(
+                stream
+                    .unsafe_cast_ref::<PollableOutputStream>()
+                    .to_glib_none()
+                    .0,
+            ))
+        }
+    }
+
+    fn parent_create_source(
+        &self,
+        stream: &Self::Type,
+        cancellable: Option<&Cancellable>,
+    ) -> glib::Source {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableOutputStream>()
+                as *const ffi::GPollableOutputStreamInterface;
+
+            let func = (*parent_iface)
+                .create_source
+                .expect("no parent \"create_source\" implementation");
+            from_glib_full(func(
+                stream
+                    .unsafe_cast_ref::<PollableOutputStream>()
+                    .to_glib_none()
+                    .0,
+                cancellable.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_write_nonblocking(&self, stream: &Self::Type, buffer: &[u8]) -> Result<usize, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .get_parent_interface::<PollableOutputStream>()
+                as *const ffi::GPollableOutputStreamInterface;
+
+            let func = (*parent_iface)
+                .write_nonblocking
+                .expect("no parent \"write_nonblocking\" implementation");
+            let mut err = ptr::null_mut();
+            let res = func(
+                stream
+                    .unsafe_cast_ref::<PollableOutputStream>()
+                    .to_glib_none()
+                    .0,
+                buffer.as_ptr() as *mut u8,
+                buffer.len(),
+                &mut err,
+            );
+            if res == -1 {
+                Err(from_glib_full(err))
+            } else {
+                assert!(res >= 0);
+                let res = res as usize;
+                assert!(res <= buffer.len());
+                Ok(res)
+            }
+        }
+    }
+}
+
+unsafe impl<T: PollableOutputStreamImpl> IsImplementable<T> for PollableOutputStream {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.can_poll = Some(pollable_output_stream_can_poll::<T>);
+        iface.is_writable = Some(pollable_output_stream_is_writable::<T>);
+        iface.create_source = Some(pollable_output_stream_create_source::<T>);
+        iface.write_nonblocking = Some(pollable_output_stream_write_nonblocking::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe extern "C" fn pollable_output_stream_can_poll<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.can_poll(from_glib_borrow::<_, PollableOutputStream>(stream).unsafe_cast_ref())
+        .to_glib()
+}
+
+unsafe extern "C" fn pollable_output_stream_is_writable<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.is_writable(from_glib_borrow::<_, PollableOutputStream>(stream).unsafe_cast_ref())
+        .to_glib()
+}
+
+unsafe extern "C" fn pollable_output_stream_create_source<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+    cancellable: *mut ffi::GCancellable,
+) -> *mut glib::ffi::GSource {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    imp.create_source(
+        from_glib_borrow::<_, PollableOutputStream>(stream).unsafe_cast_ref(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    )
+    .to_glib_full()
+}
+
+unsafe extern "C" fn pollable_output_stream_write_nonblocking<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+    buffer: *mut u8,
+    count: usize,
+    err: *mut *mut glib::ffi::GError,
+) -> isize {
+    assert!(count <= isize::MAX as usize);
+
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.get_impl();
+
+    match imp.write_nonblocking(
+        from_glib_borrow::<_, PollableOutputStream>(stream).unsafe_cast_ref(),
+        if count == 0 {
+            &[]
+        } else {
+            std::slice::from_raw_parts(buffer as *const u8, count)
+        },
+    ) {
+        Ok(res) => {
+            assert!(res <= isize::MAX as usize);
+            assert!(res <= count);
+            res as isize
+        }
+        Err(e) => {
+            if !err.is_null() {
+                let mut e = mem::ManuallyDrop::new(e);
+                *err = e.to_glib_none_mut().0;
+            }
+            -1
+        }
+    }
+}
@@ -0,0 +1,388 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use glib::{Cast, Variant, VariantTy};
+
+use std::collections::BTreeMap;
+
+use crate::Permission;
+use crate::SettingsBackend;
+use crate::SettingsBackendOriginTag;
+
+// rustdoc-stripper-ignore-next
+/// Implementation of a [`SettingsBackend`] in Rust.
+///
+/// Keys are absolute paths like `/org/example/app/key`. After changing a value, the backend
+/// has to notify about it with
+/// [`SettingsBackendExtManual`](crate::prelude::SettingsBackendExtManual) and the
+/// `origin_tag` of the write causing it, if any.
+pub trait SettingsBackendImpl: ObjectImpl + SettingsBackendImplExt + Send + Sync {
+    // rustdoc-stripper-ignore-next
+    /// Reads the value of `key`, or its default value if `default_value` is `true`.
+    ///
+    /// `None` is returned if the backend has no value, and values not of `expected_type` are
+    /// ignored.
+    fn read(
+        &self,
+        backend: &Self::Type,
+        key: &str,
+        expected_type: &VariantTy,
+        default_value: bool,
+    ) -> Option<Variant>;
+
+    fn read_user_value(
+        &self,
+        backend: &Self::Type,
+        key: &str,
+        expected_type: &VariantTy,
+    ) -> Option<Variant> {
+        self.parent_read_user_value(backend, key, expected_type)
+    }
+
+    fn get_writable(&self, backend: &Self::Type, key: &str) -> bool;
+
+    fn write(
+        &self,
+        backend: &Self::Type,
+        key: &str,
+        value: &Variant,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Writes several values at once. `None` values reset their keys.
+    ///
+    /// By default each value is passed to [`write`](SettingsBackendImpl::write) or
+    /// [`reset`](SettingsBackendImpl::reset) in turn.
+    fn write_tree(
+        &self,
+        backend: &Self::Type,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool {
+        for (key, value) in tree {
+            match value {
+                Some(value) => {
+                    if !self.write(backend, key, value, origin_tag) {
+                        return false;
+                    }
+                }
+                None => self.reset(backend, key, origin_tag),
+            }
+        }
+        true
+    }
+
+    fn reset(&self, backend: &Self::Type, key: &str, origin_tag: Option<SettingsBackendOriginTag>);
+
+    fn subscribe(&self, backend: &Self::Type, name: &str) {
+        self.parent_subscribe(backend, name)
+    }
+
+    fn unsubscribe(&self, backend: &Self::Type, name: &str) {
+        self.parent_unsubscribe(backend, name)
+    }
+
+    fn sync(&self, backend: &Self::Type) {
+        self.parent_sync(backend)
+    }
+
+    fn get_permission(&self, backend: &Self::Type, path: &str) -> Permission {
+        self.parent_get_permission(backend, path)
+    }
+}
+
+pub trait SettingsBackendImplExt: ObjectSubclass {
+    fn parent_read_user_value(
+        &self,
+        backend: &Self::Type,
+        key: &str,
+        expected_type: &VariantTy,
+    ) -> Option<Variant>;
+    fn parent_subscribe(&self, backend: &Self::Type, name: &str);
+    fn parent_unsubscribe(&self, backend: &Self::Type, name: &str);
+    fn parent_sync(&self, backend: &Self::Type);
+    fn parent_get_permission(&self, backend: &Self::Type, path: &str) -> Permission;
+}
+
+impl<T: SettingsBackendImpl> SettingsBackendImplExt for T {
+    fn parent_read_user_value(
+        &self,
+        backend: &Self::Type,
+        key: &str,
+        expected_type: &VariantTy,
+    ) -> Option<Variant> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .read_user_value
+                .expect("No parent class implementation for \"read_user_value\"");
+            from_glib_full(f(
+                backend
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                expected_type.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_subscribe(&self, backend: &Self::Type, name: &str) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).subscribe {
+                f(
+                    backend
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    name.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_unsubscribe(&self, backend: &Self::Type, name: &str) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).unsubscribe {
+                f(
+                    backend
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    name.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_sync(&self, backend: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).sync {
+                f(backend
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0)
+            }
+        }
+    }
+
+    fn parent_get_permission(&self, backend: &Self::Type, path: &str) -> Permission {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .get_permission
+                .expect("No parent class implementation for \"get_permission\"");
+            from_glib_full(f(
+                backend
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                path.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+unsafe impl<T: SettingsBackendImpl> IsSubclassable<T> for SettingsBackend {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <glib::Object as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.read = Some(backend_read::<T>);
+        klass.read_user_value = Some(backend_read_user_value::<T>);
+        klass.get_writable = Some(backend_get_writable::<T>);
+        klass.write = Some(backend_write::<T>);
+        klass.write_tree = Some(backend_write_tree::<T>);
+        klass.reset = Some(backend_reset::<T>);
+        klass.subscribe = Some(backend_subscribe::<T>);
+        klass.unsubscribe = Some(backend_unsubscribe::<T>);
+        klass.sync = Some(backend_sync::<T>);
+        klass.get_permission = Some(backend_get_permission::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <glib::Object as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn backend_read<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    expected_type: *const glib::ffi::GVariantType,
+    default_value: glib::ffi::gboolean,
+) -> *mut glib::ffi::GVariant {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let key: Borrowed<glib::GString> = from_glib_borrow(key);
+    let expected_type = VariantTy::from_ptr(expected_type);
+
+    imp.read(
+        wrap.unsafe_cast_ref(),
+        key.as_str(),
+        expected_type,
+        from_glib(default_value),
+    )
+    .filter(|value| value.type_() == expected_type)
+    .to_glib_full()
+}
+
+unsafe extern "C" fn backend_read_user_value<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    expected_type: *const glib::ffi::GVariantType,
+) -> *mut glib::ffi::GVariant {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let key: Borrowed<glib::GString> = from_glib_borrow(key);
+    let expected_type = VariantTy::from_ptr(expected_type);
+
+    imp.read_user_value(wrap.unsafe_cast_ref(), key.as_str(), expected_type)
+        .filter(|value| value.type_() == expected_type)
+        .to_glib_full()
+}
+
+unsafe extern "C" fn backend_get_writable<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let key: Borrowed<glib::GString> = from_glib_borrow(key);
+
+    imp.get_writable(wrap.unsafe_cast_ref(), key.as_str())
+        .to_glib()
+}
+
+unsafe extern "C" fn backend_write<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    value: *mut glib::ffi::GVariant,
+    origin_tag: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let key: Borrowed<glib::GString> = from_glib_borrow(key);
+    let value: Variant = from_glib_none(value);
+
+    imp.write(
+        wrap.unsafe_cast_ref(),
+        key.as_str(),
+        &value,
+        SettingsBackendOriginTag::from_raw(origin_tag),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn collect_tree(
+    key: glib::ffi::gpointer,
+    value: glib::ffi::gpointer,
+    data: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let tree = &mut *(data as *mut BTreeMap<String, Option<Variant>>);
+    tree.insert(
+        from_glib_none(key as *const libc::c_char),
+        from_glib_none(value as *mut glib::ffi::GVariant),
+    );
+    glib::ffi::GFALSE
+}
+
+unsafe extern "C" fn backend_write_tree<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    tree: *mut glib::ffi::GTree,
+    origin_tag: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+
+    let mut values = BTreeMap::new();
+    glib::ffi::g_tree_foreach(
+        tree,
+        Some(collect_tree),
+        &mut values as *mut _ as glib::ffi::gpointer,
+    );
+
+    imp.write_tree(
+        wrap.unsafe_cast_ref(),
+        &values,
+        SettingsBackendOriginTag::from_raw(origin_tag),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn backend_reset<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    origin_tag: glib::ffi::gpointer,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let key: Borrowed<glib::GString> = from_glib_borrow(key);
+
+    imp.reset(
+        wrap.unsafe_cast_ref(),
+        key.as_str(),
+        SettingsBackendOriginTag::from_raw(origin_tag),
+    )
+}
+
+unsafe extern "C" fn backend_subscribe<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    name: *const libc::c_char,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let name: Borrowed<glib::GString> = from_glib_borrow(name);
+
+    imp.subscribe(wrap.unsafe_cast_ref(), name.as_str())
+}
+
+unsafe extern "C" fn backend_unsubscribe<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    name: *const libc::c_char,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let name: Borrowed<glib::GString> = from_glib_borrow(name);
+
+    imp.unsubscribe(wrap.unsafe_cast_ref(), name.as_str())
+}
+
+unsafe extern "C" fn backend_sync<T: SettingsBackendImpl>(ptr: *mut ffi::GSettingsBackend) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+
+    imp.sync(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn backend_get_permission<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    path: *const libc::c_char,
+) -> *mut ffi::GPermission {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<SettingsBackend> = from_glib_borrow(ptr);
+    let path: Borrowed<glib::GString> = from_glib_borrow(path);
+
+    imp.get_permission(wrap.unsafe_cast_ref(), path.as_str())
+        .to_glib_full()
+}
@@ -1,11 +1,19 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use crate::prelude::*;
 use crate::Cancellable;
+use crate::DataInputStream;
+use crate::InputStream;
+use crate::OutputStream;
 use crate::Subprocess;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
 use glib::object::IsA;
 use glib::translate::*;
 use glib::GString;
 use libc::c_char;
+use std::fmt;
+use std::future::Future;
 use std::pin::Pin;
 use std::ptr;
 
@@ -81,3 +89,174 @@ impl Subprocess {
         }))
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// How a subprocess terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubprocessExitStatus {
+    /// The subprocess exited normally with the given exit code.
+    Exited(i32),
+    /// The subprocess was terminated by the given signal.
+    Signaled(i32),
+}
+
+impl SubprocessExitStatus {
+    fn from_subprocess(subprocess: &Subprocess) -> Self {
+        if subprocess.get_if_exited() {
+            SubprocessExitStatus::Exited(subprocess.get_exit_status())
+        } else if subprocess.get_if_signaled() {
+            SubprocessExitStatus::Signaled(subprocess.get_term_sig())
+        } else {
+            SubprocessExitStatus::Exited(subprocess.get_status())
+        }
+    }
+
+    pub fn success(&self) -> bool {
+        *self == SubprocessExitStatus::Exited(0)
+    }
+
+    pub fn code(&self) -> Option<i32> {
+        match *self {
+            SubprocessExitStatus::Exited(code) => Some(code),
+            SubprocessExitStatus::Signaled(_) => None,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A subprocess spawned by a [`SubprocessBuilder`](crate::SubprocessBuilder).
+///
+/// The pipes requested from the builder can be taken once each. If the builder enabled
+/// [`kill_on_drop`](crate::SubprocessBuilder::kill_on_drop), the subprocess is forcefully
+/// terminated when the handle is dropped.
+#[derive(Debug)]
+pub struct SubprocessHandle {
+    subprocess: Subprocess,
+    stdin: Option<OutputStream>,
+    stdout: Option<InputStream>,
+    stderr: Option<InputStream>,
+    kill_on_drop: bool,
+}
+
+impl SubprocessHandle {
+    pub(crate) fn new(subprocess: Subprocess, kill_on_drop: bool) -> Self {
+        SubprocessHandle {
+            stdin: subprocess.get_stdin_pipe(),
+            stdout: subprocess.get_stdout_pipe(),
+            stderr: subprocess.get_stderr_pipe(),
+            subprocess,
+            kill_on_drop,
+        }
+    }
+
+    pub fn subprocess(&self) -> &Subprocess {
+        &self.subprocess
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Takes the stdin pipe of the subprocess.
+    ///
+    /// Returns `None` if stdin is not a pipe or was already taken.
+    pub fn take_stdin(&mut self) -> Option<OutputStream> {
+        self.stdin.take()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Takes the stdout pipe of the subprocess as a stream of lines.
+    ///
+    /// Returns `None` if stdout is not a pipe or was already taken.
+    pub fn stdout_lines(&mut self) -> Option<SubprocessLines> {
+        self.stdout
+            .take()
+            .map(|stream| SubprocessLines::new(&stream))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Takes the stderr pipe of the subprocess as a stream of lines.
+    ///
+    /// Returns `None` if stderr is not a pipe or was already taken.
+    pub fn stderr_lines(&mut self) -> Option<SubprocessLines> {
+        self.stderr
+            .take()
+            .map(|stream| SubprocessLines::new(&stream))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Waits for the subprocess to terminate.
+    pub fn wait_future(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<SubprocessExitStatus, glib::Error>> + 'static>> {
+        let subprocess = self.subprocess.clone();
+        Box::pin(async move {
+            subprocess.wait_async_future().await?;
+            Ok(SubprocessExitStatus::from_subprocess(&subprocess))
+        })
+    }
+}
+
+impl Drop for SubprocessHandle {
+    fn drop(&mut self) {
+        // Does nothing if the subprocess already terminated
+        if self.kill_on_drop {
+            self.subprocess.force_exit();
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the UTF-8 lines read from a pipe of a subprocess, without the line endings.
+///
+/// The lines are read asynchronously on the thread-default main context.
+pub struct SubprocessLines {
+    stream: DataInputStream,
+    read: Option<Pin<Box<dyn Future<Output = Result<Option<GString>, glib::Error>> + 'static>>>,
+    done: bool,
+}
+
+impl SubprocessLines {
+    fn new(stream: &InputStream) -> Self {
+        SubprocessLines {
+            stream: DataInputStream::new(stream),
+            read: None,
+            done: false,
+        }
+    }
+}
+
+impl fmt::Debug for SubprocessLines {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SubprocessLines")
+            .field("stream", &self.stream)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl Stream for SubprocessLines {
+    type Item = Result<String, glib::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        let stream = self.stream.clone();
+        let read = self
+            .read
+            .get_or_insert_with(|| stream.read_line_utf8_async_future(glib::PRIORITY_DEFAULT));
+        let res = futures_core::ready!(read.as_mut().poll(ctx));
+        self.read = None;
+
+        match res {
+            Ok(Some(line)) => Poll::Ready(Some(Ok(line.into()))),
+            Ok(None) => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+            Err(err) => {
+                self.done = true;
+                Poll::Ready(Some(Err(err)))
+            }
+        }
+    }
+}
@@ -1,11 +1,17 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use crate::SubprocessFlags;
+use crate::SubprocessHandle;
 use crate::SubprocessLauncher;
 #[cfg(any(unix, feature = "dox"))]
+use crate::UnixFDList;
+#[cfg(any(unix, feature = "dox"))]
 #[cfg(any(unix, feature = "dox"))]
 use glib::translate::*;
+use std::ffi::{OsStr, OsString};
 #[cfg(any(unix, all(feature = "dox", unix)))]
 use std::os::unix::io::IntoRawFd;
+use std::path::Path;
 
 #[cfg(all(feature = "dox", not(unix)))]
 pub trait IntoRawFd: Sized {
@@ -15,6 +21,12 @@ pub trait IntoRawFd: Sized {
 }
 
 impl SubprocessLauncher {
+    // rustdoc-stripper-ignore-next
+    /// Creates a [`SubprocessBuilder`] for running `program`.
+    pub fn builder<S: AsRef<OsStr>>(program: S) -> SubprocessBuilder {
+        SubprocessBuilder::new(program)
+    }
+
     #[cfg(any(unix, feature = "dox"))]
     #[cfg_attr(feature = "dox", doc(cfg(unix)))]
     #[doc(alias = "g_subprocess_launcher_take_fd")]
@@ -55,3 +67,254 @@ impl SubprocessLauncher {
         }
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// A builder configuring and spawning a subprocess in a single call chain.
+///
+/// ```no_run
+/// # use futures_util::StreamExt;
+/// # async fn run() -> Result<(), gio::glib::Error> {
+/// let mut child = gio::SubprocessLauncher::builder("ls")
+///     .arg("-l")
+///     .cwd("/tmp")
+///     .env("LC_ALL", "C")
+///     .stdout_pipe()
+///     .kill_on_drop(true)
+///     .spawn()?;
+///
+/// let mut lines = child.stdout_lines().unwrap();
+/// while let Some(line) = lines.next().await {
+///     println!("{}", line?);
+/// }
+/// assert!(child.wait_future().await?.success());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "The builder must be spawned"]
+pub struct SubprocessBuilder {
+    launcher: SubprocessLauncher,
+    argv: Vec<OsString>,
+    flags: SubprocessFlags,
+    kill_on_drop: bool,
+    error: Option<glib::Error>,
+}
+
+impl SubprocessBuilder {
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
+        SubprocessBuilder {
+            launcher: SubprocessLauncher::new(SubprocessFlags::NONE),
+            argv: vec![program.as_ref().to_owned()],
+            flags: SubprocessFlags::NONE,
+            kill_on_drop: false,
+            error: None,
+        }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(mut self, arg: S) -> Self {
+        self.argv.push(arg.as_ref().to_owned());
+        self
+    }
+
+    pub fn args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(mut self, args: I) -> Self {
+        self.argv
+            .extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+        self
+    }
+
+    pub fn cwd<P: AsRef<Path>>(self, cwd: P) -> Self {
+        self.launcher.set_cwd(cwd);
+        self
+    }
+
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(self, variable: K, value: V) -> Self {
+        self.launcher.setenv(variable, value, true);
+        self
+    }
+
+    pub fn env_remove<K: AsRef<OsStr>>(self, variable: K) -> Self {
+        self.launcher.unsetenv(variable);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Clears the environment inherited from the current process.
+    pub fn env_clear(self) -> Self {
+        self.launcher.set_environ(&[]);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds `flags` to the flags of the subprocess.
+    pub fn flags(mut self, flags: SubprocessFlags) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    pub fn stdin_pipe(self) -> Self {
+        self.flags(SubprocessFlags::STDIN_PIPE)
+    }
+
+    pub fn stdout_pipe(self) -> Self {
+        self.flags(SubprocessFlags::STDOUT_PIPE)
+    }
+
+    pub fn stdout_silence(self) -> Self {
+        self.flags(SubprocessFlags::STDOUT_SILENCE)
+    }
+
+    pub fn stderr_pipe(self) -> Self {
+        self.flags(SubprocessFlags::STDERR_PIPE)
+    }
+
+    pub fn stderr_silence(self) -> Self {
+        self.flags(SubprocessFlags::STDERR_SILENCE)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Redirects stderr of the subprocess to its stdout.
+    pub fn stderr_merge(self) -> Self {
+        self.flags(SubprocessFlags::STDERR_MERGE)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the subprocess is forcefully terminated when its [`SubprocessHandle`] is
+    /// dropped.
+    pub fn kill_on_drop(mut self, kill_on_drop: bool) -> Self {
+        self.kill_on_drop = kill_on_drop;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Passes `source_fd` to the subprocess as `target_fd`.
+    #[cfg(any(unix, feature = "dox"))]
+    #[cfg_attr(feature = "dox", doc(cfg(unix)))]
+    pub fn take_fd<F: IntoRawFd>(self, source_fd: F, target_fd: i32) -> Self {
+        self.launcher.take_fd(source_fd, target_fd);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Passes all file descriptors of `fd_list` to the subprocess, the file descriptor at index
+    /// `i` of the list as `first_target_fd + i`.
+    ///
+    /// The file descriptors are duplicated, so `fd_list` keeps its own copies.
+    #[cfg(any(unix, feature = "dox"))]
+    #[cfg_attr(feature = "dox", doc(cfg(unix)))]
+    pub fn pass_fd_list(mut self, fd_list: &UnixFDList, first_target_fd: i32) -> Self {
+        use crate::prelude::*;
+        use crate::unix_fd_list::UnixFDListExtManual;
+
+        for index in 0..fd_list.get_length() {
+            match fd_list.get(index) {
+                Ok(fd) => self.launcher.take_fd(fd, first_target_fd + index),
+                Err(err) => {
+                    self.error.get_or_insert(err);
+                }
+            }
+        }
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Spawns the subprocess.
+    pub fn spawn(self) -> Result<SubprocessHandle, glib::Error> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        self.launcher.set_flags(self.flags);
+        let argv = self
+            .argv
+            .iter()
+            .map(|arg| arg.as_os_str())
+            .collect::<Vec<_>>();
+        let subprocess = self.launcher.spawnv(&argv)?;
+        Ok(SubprocessHandle::new(subprocess, self.kill_on_drop))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::io::Read;
+    use std::os::unix::io::FromRawFd;
+
+    fn collect_lines(lines: crate::SubprocessLines) -> Vec<String> {
+        glib::MainContext::new().block_on(lines.map(|line| line.unwrap()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_lines_and_status() {
+        let mut child = SubprocessLauncher::builder("sh")
+            .args(&["-c", "echo a; echo b >&2; echo c; exit 3"])
+            .stdout_pipe()
+            .stderr_pipe()
+            .spawn()
+            .unwrap();
+
+        assert_eq!(collect_lines(child.stdout_lines().unwrap()), vec!["a", "c"]);
+        assert_eq!(collect_lines(child.stderr_lines().unwrap()), vec!["b"]);
+        assert!(child.stdout_lines().is_none());
+
+        let status = glib::MainContext::new()
+            .block_on(child.wait_future())
+            .unwrap();
+        assert_eq!(status, crate::SubprocessExitStatus::Exited(3));
+        assert!(!status.success());
+    }
+
+    #[test]
+    fn test_env_and_cwd() {
+        let mut child = SubprocessLauncher::builder("sh")
+            .args(&["-c", "echo $FOO; echo $BAR; pwd"])
+            .env_clear()
+            .env("FOO", "foo")
+            .env("BAR", "bar")
+            .env_remove("BAR")
+            .cwd("/")
+            .stdout_pipe()
+            .spawn()
+            .unwrap();
+
+        assert_eq!(
+            collect_lines(child.stdout_lines().unwrap()),
+            vec!["foo", "", "/"]
+        );
+    }
+
+    #[test]
+    fn test_kill_on_drop() {
+        let child = SubprocessLauncher::builder("sleep")
+            .arg("60")
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+
+        let subprocess = child.subprocess().clone();
+        drop(child);
+        subprocess.wait(crate::NONE_CANCELLABLE).unwrap();
+        assert!(subprocess.get_if_signaled());
+    }
+
+    #[test]
+    fn test_pass_fd_list() {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let mut read = unsafe { std::fs::File::from_raw_fd(fds[0]) };
+
+        let fd_list = UnixFDList::from_array(vec![fds[1]]);
+        let child = SubprocessLauncher::builder("sh")
+            .args(&["-c", "echo hello >&3"])
+            .pass_fd_list(&fd_list, 3)
+            .spawn()
+            .unwrap();
+        drop(fd_list);
+
+        let mut output = String::new();
+        read.read_to_string(&mut output).unwrap();
+        assert_eq!(output, "hello\n");
+        child.subprocess().wait(crate::NONE_CANCELLABLE).unwrap();
+    }
+}
@@ -1,13 +1,22 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use crate::prelude::*;
 use crate::AsyncResult;
 use crate::Cancellable;
+use crate::IOErrorEnum;
 use crate::Task;
 use glib::object::IsA;
 use glib::translate::*;
 use libc::c_void;
+use std::any::Any;
 use std::boxed::Box as Box_;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
 use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
 impl Task {
     pub fn new<P: IsA<Cancellable>, Q: FnOnce(&AsyncResult, Option<&glib::Object>) + 'static>(
@@ -87,6 +96,302 @@ impl Task {
     }
 }
 
+// Source tag of the tasks created by `TypedTask`, identifying tasks whose result pointer is a
+// `TypedTaskResult`.
+static TYPED_TASK_SOURCE_TAG: u8 = 0;
+
+type TypedTaskResult = Box_<dyn Any + Send>;
+
+// rustdoc-stripper-ignore-next
+/// A [`Task`] returning a value of type `V`.
+///
+/// This is used to implement GIO-style `_async`/`_finish` pairs in Rust: the `_async` function
+/// creates the task, either with a Rust callback using [`new`](Self::new) or with the C
+/// callback passed by the caller using [`from_raw_callback`](Self::from_raw_callback), and
+/// completes it with [`return_result`](Self::return_result),
+/// [`run_in_thread`](Self::run_in_thread) or [`spawn_future`](Self::spawn_future). The
+/// `_finish` function then retrieves the result with [`propagate`](Self::propagate).
+pub struct TypedTask<V> {
+    task: Task,
+    phantom: PhantomData<fn() -> V>,
+}
+
+impl<V: Send + 'static> TypedTask<V> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new task calling `callback` with the result once it completes.
+    pub fn new<
+        P: IsA<Cancellable>,
+        Q: FnOnce(Result<V, glib::Error>, Option<&glib::Object>) + 'static,
+    >(
+        source_object: Option<&glib::Object>,
+        cancellable: Option<&P>,
+        callback: Q,
+    ) -> Self {
+        let callback_data = Box_::new(callback);
+        unsafe extern "C" fn trampoline<
+            V: Send + 'static,
+            Q: FnOnce(Result<V, glib::Error>, Option<&glib::Object>) + 'static,
+        >(
+            source_object: *mut glib::gobject_ffi::GObject,
+            res: *mut ffi::GAsyncResult,
+            user_data: glib::ffi::gpointer,
+        ) {
+            let source_object = Option::<glib::Object>::from_glib_borrow(source_object);
+            let res = AsyncResult::from_glib_borrow(res);
+            let callback: Box_<Q> = Box_::from_raw(user_data as *mut _);
+            let result = TypedTask::<V>::propagate(&*res, source_object.as_ref().as_ref());
+            callback(result, source_object.as_ref().as_ref());
+        }
+        let callback = trampoline::<V, Q>;
+        unsafe {
+            Self::from_raw_callback(
+                source_object.to_glib_none().0,
+                cancellable.map(|p| p.as_ref()).to_glib_none().0,
+                Some(callback),
+                Box_::into_raw(callback_data) as *mut _,
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new task calling the C `callback` once it completes.
+    ///
+    /// This is meant for `_async` functions or virtual methods called from C, which then
+    /// retrieve the result with [`propagate`](Self::propagate) in the corresponding `_finish`
+    /// function.
+    ///
+    /// # Safety
+    ///
+    /// `source_object` and `cancellable` must be valid or `NULL`, and `callback` must be
+    /// safe to call with `user_data`.
+    pub unsafe fn from_raw_callback(
+        source_object: *mut glib::gobject_ffi::GObject,
+        cancellable: *mut ffi::GCancellable,
+        callback: ffi::GAsyncReadyCallback,
+        user_data: glib::ffi::gpointer,
+    ) -> Self {
+        let task: Task = from_glib_full(ffi::g_task_new(
+            source_object,
+            cancellable,
+            callback,
+            user_data,
+        ));
+        ffi::g_task_set_source_tag(
+            task.to_glib_none().0,
+            &TYPED_TASK_SOURCE_TAG as *const u8 as glib::ffi::gpointer,
+        );
+        TypedTask {
+            task,
+            phantom: PhantomData,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the result of the task `result` created for `source_object`.
+    ///
+    /// This fails with [`IOErrorEnum::InvalidArgument`] if `result` is not a `TypedTask` of
+    /// `source_object`, with [`IOErrorEnum::Cancelled`] if the task was cancelled, and with
+    /// [`IOErrorEnum::InvalidData`] if the task has no result of type `V`.
+    pub fn propagate<P: IsA<AsyncResult>>(
+        result: &P,
+        source_object: Option<&glib::Object>,
+    ) -> Result<V, glib::Error> {
+        let result: &AsyncResult = result.as_ref();
+        let task = match result.downcast_ref::<Task>() {
+            Some(task) if Task::is_valid(task, source_object) => task,
+            _ => {
+                return Err(glib::Error::new(
+                    IOErrorEnum::InvalidArgument,
+                    "Result is not a task of the source object",
+                ))
+            }
+        };
+
+        unsafe {
+            let tag = ffi::g_task_get_source_tag(task.to_glib_none().0);
+            if tag as *const u8 != &TYPED_TASK_SOURCE_TAG as *const u8 {
+                return Err(glib::Error::new(
+                    IOErrorEnum::InvalidArgument,
+                    "Result is not a typed task",
+                ));
+            }
+
+            let mut error = ptr::null_mut();
+            let value = ffi::g_task_propagate_pointer(task.to_glib_none().0, &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+            if value.is_null() {
+                return Err(glib::Error::new(
+                    IOErrorEnum::InvalidData,
+                    "Task result was already propagated",
+                ));
+            }
+            let value = *Box_::from_raw(value as *mut TypedTaskResult);
+            value.downcast::<V>().map(|value| *value).map_err(|_| {
+                glib::Error::new(IOErrorEnum::InvalidData, "Task result has a different type")
+            })
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Completes the task with `result`.
+    pub fn return_result(self, result: Result<V, glib::Error>) {
+        unsafe { return_result_raw(self.task.to_glib_none().0, result) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Runs `func` in a thread of the GIO thread pool and completes the task with its result.
+    ///
+    /// `func` is passed the cancellable of the task. This uses the task data of the task,
+    /// which must not be set otherwise.
+    #[doc(alias = "g_task_run_in_thread")]
+    pub fn run_in_thread<
+        F: FnOnce(Option<&Cancellable>) -> Result<V, glib::Error> + Send + 'static,
+    >(
+        self,
+        func: F,
+    ) {
+        unsafe extern "C" fn thread_func<
+            V: Send + 'static,
+            F: FnOnce(Option<&Cancellable>) -> Result<V, glib::Error> + Send + 'static,
+        >(
+            task: *mut ffi::GTask,
+            _source_object: *mut glib::gobject_ffi::GObject,
+            task_data: glib::ffi::gpointer,
+            cancellable: *mut ffi::GCancellable,
+        ) {
+            let func = (*(task_data as *mut Option<F>))
+                .take()
+                .expect("Task function was already called");
+            let cancellable = Option::<Cancellable>::from_glib_borrow(cancellable);
+            return_result_raw(task, func(cancellable.as_ref().as_ref()));
+        }
+        unsafe extern "C" fn destroy_func<F>(data: glib::ffi::gpointer) {
+            let _ = Box_::from_raw(data as *mut Option<F>);
+        }
+
+        unsafe {
+            ffi::g_task_set_task_data(
+                self.task.to_glib_none().0,
+                Box_::into_raw(Box_::new(Some(func))) as *mut _,
+                Some(destroy_func::<F>),
+            );
+            ffi::g_task_run_in_thread(self.task.to_glib_none().0, Some(thread_func::<V, F>));
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Spawns `future` on the thread-default main context and completes the task with its
+    /// result.
+    ///
+    /// If the cancellable of the task is cancelled before `future` resolves, `future` is
+    /// dropped and the task fails with [`IOErrorEnum::Cancelled`].
+    pub fn spawn_future<F: Future<Output = Result<V, glib::Error>> + 'static>(self, future: F) {
+        let cancellable = self.cancellable();
+        let (sender, receiver) = futures_channel::oneshot::channel();
+        let sender = Arc::new(Mutex::new(Some(sender)));
+
+        let handler_id = cancellable.as_ref().map(|cancellable| {
+            let sender = sender.clone();
+            cancellable.connect_cancelled(move |_| {
+                if let Some(sender) = sender.lock().unwrap().take() {
+                    let _ = sender.send(());
+                }
+            })
+        });
+        if cancellable.as_ref().map_or(false, |c| c.is_cancelled()) {
+            if let Some(sender) = sender.lock().unwrap().take() {
+                let _ = sender.send(());
+            }
+        }
+
+        let future = CancellableFuture {
+            future: Box_::pin(future),
+            cancelled: Some(receiver),
+        };
+
+        glib::MainContext::ref_thread_default().spawn_local(async move {
+            let result = future.await;
+            if let (Some(cancellable), Some(handler_id)) = (cancellable, handler_id) {
+                glib::ObjectExt::disconnect(&cancellable, handler_id);
+            }
+            self.return_result(result);
+        });
+    }
+
+    pub fn cancellable(&self) -> Option<Cancellable> {
+        unsafe { from_glib_none(ffi::g_task_get_cancellable(self.task.to_glib_none().0)) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the underlying untyped [`Task`].
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+}
+
+unsafe fn return_result_raw<V: Send + 'static>(
+    task: *mut ffi::GTask,
+    result: Result<V, glib::Error>,
+) {
+    unsafe extern "C" fn result_free(value: *mut c_void) {
+        let _ = Box_::from_raw(value as *mut TypedTaskResult);
+    }
+
+    match result {
+        Ok(value) => {
+            let value: TypedTaskResult = Box_::new(value);
+            ffi::g_task_return_pointer(
+                task,
+                Box_::into_raw(Box_::new(value)) as *mut c_void,
+                Some(result_free),
+            );
+        }
+        Err(error) => ffi::g_task_return_error(task, error.to_glib_full() as *mut _),
+    }
+}
+
+impl<V> fmt::Debug for TypedTask<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedTask").field(&self.task).finish()
+    }
+}
+
+impl<V> AsRef<Task> for TypedTask<V> {
+    fn as_ref(&self) -> &Task {
+        &self.task
+    }
+}
+
+struct CancellableFuture<F> {
+    future: Pin<Box_<F>>,
+    cancelled: Option<futures_channel::oneshot::Receiver<()>>,
+}
+
+impl<V, F: Future<Output = Result<V, glib::Error>>> Future for CancellableFuture<F> {
+    type Output = Result<V, glib::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if let Some(ref mut cancelled) = this.cancelled {
+            match Pin::new(cancelled).poll(cx) {
+                Poll::Ready(Ok(())) => {
+                    return Poll::Ready(Err(glib::Error::new(
+                        IOErrorEnum::Cancelled,
+                        "Operation was cancelled",
+                    )))
+                }
+                Poll::Ready(Err(_)) => this.cancelled = None,
+                Poll::Pending => (),
+            }
+        }
+
+        this.future.as_mut().poll(cx)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -243,4 +548,141 @@ mod test {
             Ok(_) => panic!(),
         }
     }
+
+    #[test]
+    fn test_typed_result() {
+        match run_async_local(|tx, l| {
+            let t = TypedTask::<String>::new(
+                None,
+                crate::NONE_CANCELLABLE,
+                move |res, _source_object| {
+                    tx.send(res).unwrap();
+                    l.quit();
+                },
+            );
+            t.return_result(Ok(String::from("done")));
+        }) {
+            Err(_) => panic!(),
+            Ok(s) => assert_eq!(s, "done"),
+        }
+    }
+
+    #[test]
+    fn test_typed_run_in_thread() {
+        match run_async_local(|tx, l| {
+            let t = TypedTask::<Vec<u8>>::new(
+                None,
+                crate::NONE_CANCELLABLE,
+                move |res, _source_object| {
+                    tx.send(res).unwrap();
+                    l.quit();
+                },
+            );
+            t.run_in_thread(|cancellable| {
+                assert!(cancellable.is_none());
+                Ok(vec![1, 2, 3])
+            });
+        }) {
+            Err(_) => panic!(),
+            Ok(v) => assert_eq!(v, [1, 2, 3]),
+        }
+    }
+
+    #[test]
+    fn test_typed_spawn_future() {
+        match run_async_local(|tx, l| {
+            let t =
+                TypedTask::<i32>::new(None, crate::NONE_CANCELLABLE, move |res, _source_object| {
+                    tx.send(res).unwrap();
+                    l.quit();
+                });
+            t.spawn_future(async { Ok(42) });
+        }) {
+            Err(_) => panic!(),
+            Ok(i) => assert_eq!(i, 42),
+        }
+    }
+
+    #[test]
+    fn test_typed_spawn_future_cancelled() {
+        match run_async_local(|tx, l| {
+            let (sender, receiver) = futures_channel::oneshot::channel::<()>();
+            let c = crate::Cancellable::new();
+            let t = TypedTask::<i32>::new(None, Some(&c), move |res, _source_object| {
+                drop(sender);
+                tx.send(res).unwrap();
+                l.quit();
+            });
+            t.spawn_future(async move {
+                let _ = receiver.await;
+                Ok(42)
+            });
+            c.cancel();
+        }) {
+            Err(e) => match e.kind().unwrap() {
+                crate::IOErrorEnum::Cancelled => {}
+                _ => panic!(),
+            },
+            Ok(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_typed_wrong_type() {
+        type Data = (
+            std::sync::mpsc::Sender<Result<i32, glib::Error>>,
+            glib::MainLoop,
+        );
+
+        unsafe extern "C" fn callback(
+            source_object: *mut glib::gobject_ffi::GObject,
+            res: *mut ffi::GAsyncResult,
+            user_data: glib::ffi::gpointer,
+        ) {
+            let (tx, l) = *Box_::from_raw(user_data as *mut Data);
+            let source_object = Option::<glib::Object>::from_glib_borrow(source_object);
+            let res = AsyncResult::from_glib_borrow(res);
+            tx.send(TypedTask::<i32>::propagate(
+                &*res,
+                source_object.as_ref().as_ref(),
+            ))
+            .unwrap();
+            l.quit();
+        }
+
+        match run_async_local(|tx, l| unsafe {
+            let data: Box_<Data> = Box_::new((tx, l));
+            let t = TypedTask::<String>::from_raw_callback(
+                ptr::null_mut(),
+                ptr::null_mut(),
+                Some(callback),
+                Box_::into_raw(data) as glib::ffi::gpointer,
+            );
+            t.return_result(Ok("wrong".to_string()));
+        }) {
+            Err(e) => assert_eq!(e.kind(), Some(crate::IOErrorEnum::InvalidData)),
+            Ok(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_typed_wrong_task() {
+        match run_async_local(|tx, l| {
+            let t = crate::Task::new(
+                None,
+                crate::NONE_CANCELLABLE,
+                move |a: &AsyncResult, b: Option<&glib::Object>| {
+                    tx.send(TypedTask::<i32>::propagate(a, b)).unwrap();
+                    l.quit();
+                },
+            );
+            t.return_value(&100_i32.to_value());
+        }) {
+            Err(e) => match e.kind().unwrap() {
+                crate::IOErrorEnum::InvalidArgument => {}
+                _ => panic!(),
+            },
+            Ok(_) => panic!(),
+        }
+    }
 }
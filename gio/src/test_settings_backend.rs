@@ -0,0 +1,144 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::prelude::*;
+use crate::SettingsBackend;
+use glib::subclass::prelude::*;
+use glib::Variant;
+use std::collections::{BTreeMap, HashSet};
+
+mod imp {
+    use super::*;
+    use crate::subclass::prelude::*;
+    use crate::SettingsBackendOriginTag;
+    use glib::VariantTy;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub struct TestSettingsBackend {
+        pub values: Mutex<BTreeMap<String, Variant>>,
+        pub read_only: Mutex<HashSet<String>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestSettingsBackend {
+        const NAME: &'static str = "GioRsTestSettingsBackend";
+        type Type = super::TestSettingsBackend;
+        type ParentType = SettingsBackend;
+    }
+
+    impl ObjectImpl for TestSettingsBackend {}
+
+    impl SettingsBackendImpl for TestSettingsBackend {
+        fn read(
+            &self,
+            _backend: &Self::Type,
+            key: &str,
+            _expected_type: &VariantTy,
+            default_value: bool,
+        ) -> Option<Variant> {
+            if default_value {
+                return None;
+            }
+            self.values.lock().unwrap().get(key).cloned()
+        }
+
+        fn get_writable(&self, _backend: &Self::Type, key: &str) -> bool {
+            !self.read_only.lock().unwrap().contains(key)
+        }
+
+        fn write(
+            &self,
+            backend: &Self::Type,
+            key: &str,
+            value: &Variant,
+            origin_tag: Option<SettingsBackendOriginTag>,
+        ) -> bool {
+            if !self.get_writable(backend, key) {
+                return false;
+            }
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.clone());
+            backend.changed(key, origin_tag);
+            true
+        }
+
+        fn reset(
+            &self,
+            backend: &Self::Type,
+            key: &str,
+            origin_tag: Option<SettingsBackendOriginTag>,
+        ) {
+            if self.values.lock().unwrap().remove(key).is_some() {
+                backend.changed(key, origin_tag);
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`SettingsBackend`] keeping the values in memory, for use in tests.
+    ///
+    /// Unlike the memory backend of GIO, the stored values can be inspected and modified
+    /// directly, and keys can be made read-only.
+    pub struct TestSettingsBackend(ObjectSubclass<imp::TestSettingsBackend>)
+        @extends SettingsBackend;
+}
+
+impl TestSettingsBackend {
+    pub fn new() -> Self {
+        glib::Object::new(&[]).expect("Failed to create TestSettingsBackend")
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value stored for `key`, if any.
+    pub fn value(&self, key: &str) -> Option<Variant> {
+        let imp = imp::TestSettingsBackend::from_instance(self);
+        imp.values.lock().unwrap().get(key).cloned()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all stored values.
+    pub fn values(&self) -> BTreeMap<String, Variant> {
+        let imp = imp::TestSettingsBackend::from_instance(self);
+        imp.values.lock().unwrap().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Stores a value as if it was changed by another process, or removes it if `value` is
+    /// `None`.
+    pub fn set_value(&self, key: &str, value: Option<&Variant>) {
+        let imp = imp::TestSettingsBackend::from_instance(self);
+        {
+            let mut values = imp.values.lock().unwrap();
+            match value {
+                Some(value) => values.insert(key.to_string(), value.clone()),
+                None => values.remove(key),
+            };
+        }
+        self.changed(key, None);
+    }
+
+    pub fn set_writable(&self, key: &str, writable: bool) {
+        let imp = imp::TestSettingsBackend::from_instance(self);
+        let changed = {
+            let mut read_only = imp.read_only.lock().unwrap();
+            if writable {
+                read_only.remove(key)
+            } else {
+                read_only.insert(key.to_string())
+            }
+        };
+        if changed {
+            self.writable_changed(key);
+        }
+    }
+}
+
+impl Default for TestSettingsBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
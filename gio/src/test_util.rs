@@ -42,3 +42,74 @@ pub fn run_async_local<T: 'static, Q: FnOnce(Sender<T>, MainLoop) + Send + 'stat
 
     rx.recv().unwrap()
 }
+
+// Creates a list item carrying `value`, to be read back with `list_item_value`
+#[allow(dead_code)]
+pub fn list_item(value: i32) -> Object {
+    let item = Object::new::<Object>(&[]).unwrap();
+    unsafe { item.set_data("test-value", value) };
+    item
+}
+
+#[allow(dead_code)]
+pub fn list_item_value(item: &Object) -> i32 {
+    unsafe { *item.get_data::<i32>("test-value").unwrap().as_ref() }
+}
+
+#[allow(dead_code)]
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub fn list_store_with(values: &[i32]) -> crate::ListStore {
+    let store = crate::ListStore::new(Object::static_type());
+    for &value in values {
+        store.append(&list_item(value));
+    }
+    store
+}
+
+#[allow(dead_code)]
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub fn list_model_values<P: IsA<crate::ListModel>>(model: &P) -> Vec<i32> {
+    use crate::prelude::*;
+
+    (0..model.get_n_items())
+        .map(|i| list_item_value(&model.get_object(i).unwrap()))
+        .collect()
+}
+
+// Records all `items-changed` emissions of `model`
+#[allow(dead_code)]
+#[cfg(any(feature = "v2_44", feature = "dox"))]
+pub fn record_items_changed<P: IsA<crate::ListModel>>(
+    model: &P,
+) -> std::rc::Rc<std::cell::RefCell<Vec<(u32, u32, u32)>>> {
+    use crate::prelude::*;
+
+    let changes = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let changes_clone = changes.clone();
+    model.connect_items_changed(move |_, position, removed, added| {
+        changes_clone.borrow_mut().push((position, removed, added));
+    });
+    changes
+}
+
+// Starts a private message bus, which is also used for `BusType::Session` until it is taken
+// down again. Tests using it have to be serialized.
+#[allow(dead_code)]
+pub fn test_bus() -> crate::TestDBus {
+    let bus = crate::TestDBus::new(crate::TestDBusFlags::NONE);
+    bus.up();
+    bus
+}
+
+// Opens a new connection to `bus`, separate from the `BusType::Session` singleton
+#[allow(dead_code)]
+pub fn test_bus_connection(bus: &crate::TestDBus) -> crate::DBusConnection {
+    crate::DBusConnection::new_for_address_sync(
+        &bus.get_bus_address().unwrap(),
+        crate::DBusConnectionFlags::AUTHENTICATION_CLIENT
+            | crate::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+        None,
+        crate::NONE_CANCELLABLE,
+    )
+    .unwrap()
+}
@@ -0,0 +1,161 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg(feature = "v2_46")]
+
+use futures_util::StreamExt;
+use gio::prelude::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Generated from `dbus_codegen/calculator.xml` with `gio::generate_dbus_interfaces`
+#[allow(clippy::type_complexity)]
+mod calculator {
+    include!("dbus_codegen/calculator.rs");
+}
+
+use calculator::{register_calculator, Calculator, CalculatorEmitter, CalculatorProxy};
+
+#[derive(Default)]
+struct Server {
+    history: Mutex<Vec<(String, String)>>,
+    precision: Mutex<u32>,
+}
+
+impl Calculator for Server {
+    fn add(&self, a: i32, b: i32) -> Result<i32, glib::Error> {
+        let result = a.checked_add(b).ok_or_else(|| {
+            glib::Error::new(gio::IOErrorEnum::InvalidArgument, "Integer overflow")
+        })?;
+        self.history.lock().unwrap().push((
+            format!("{} + {} = {}", a, b, result),
+            "/org/example/Calculator".to_string(),
+        ));
+        Ok(result)
+    }
+
+    fn reset(&self) -> Result<(), glib::Error> {
+        self.history.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn get_history(
+        &self,
+    ) -> Result<(Vec<(String, String)>, HashMap<String, glib::Variant>), glib::Error> {
+        let history = self.history.lock().unwrap().clone();
+        let mut meta = HashMap::new();
+        meta.insert("count".to_string(), (history.len() as u32).to_variant());
+        Ok((history, meta))
+    }
+
+    fn precision(&self) -> u32 {
+        *self.precision.lock().unwrap()
+    }
+
+    fn set_precision(&self, value: u32) -> bool {
+        if value > 10 {
+            return false;
+        }
+        *self.precision.lock().unwrap() = value;
+        true
+    }
+
+    fn name(&self) -> String {
+        "calculator".to_string()
+    }
+}
+
+fn connect(bus: &gio::TestDBus) -> gio::DBusConnection {
+    gio::DBusConnection::new_for_address_sync(
+        &bus.get_bus_address().unwrap(),
+        gio::DBusConnectionFlags::AUTHENTICATION_CLIENT
+            | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+        None,
+        gio::NONE_CANCELLABLE,
+    )
+    .unwrap()
+}
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let code = gio::generate_dbus_interfaces(include_str!("dbus_codegen/calculator.xml")).unwrap();
+    assert_eq!(code, include_str!("dbus_codegen/calculator.rs"));
+}
+
+#[test]
+fn proxy_calls_server() {
+    let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+    bus.up();
+
+    let c = glib::MainContext::new();
+    c.block_on(async {
+        let server_connection = connect(&bus);
+        let client_connection = connect(&bus);
+        let id = register_calculator(
+            &server_connection,
+            "/org/example/Calculator",
+            Server::default(),
+        )
+        .unwrap();
+
+        let proxy = CalculatorProxy::new(
+            &client_connection,
+            server_connection.get_unique_name().as_deref(),
+            "/org/example/Calculator",
+        )
+        .await
+        .unwrap();
+
+        // Methods
+        assert_eq!(proxy.add(2, 3).await.unwrap(), 5);
+        let err = proxy.add(i32::MAX, 1).await.unwrap_err();
+        assert_eq!(err.kind(), Some(gio::IOErrorEnum::InvalidArgument));
+        let (history, meta) = proxy.get_history().await.unwrap();
+        assert_eq!(
+            history,
+            vec![(
+                "2 + 3 = 5".to_string(),
+                "/org/example/Calculator".to_string()
+            )]
+        );
+        assert_eq!(meta["count"].get::<u32>(), Some(1));
+        proxy.reset().await.unwrap();
+        assert!(proxy.get_history().await.unwrap().0.is_empty());
+
+        // Properties
+        assert_eq!(proxy.name().as_deref(), Some("calculator"));
+        let proxy = CalculatorProxy::from_proxy(proxy.proxy().clone());
+        assert_eq!(proxy.precision(), Some(0));
+        proxy.set_precision(4).await.unwrap();
+        assert!(proxy.set_precision(11).await.is_err());
+        let reply = client_connection
+            .call_future(
+                server_connection.get_unique_name().as_deref(),
+                "/org/example/Calculator",
+                "org.freedesktop.DBus.Properties",
+                "Get",
+                Some(&("org.example.Calculator", "Precision").to_variant()),
+                None,
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            reply.get::<(glib::Variant,)>().unwrap().0.get::<u32>(),
+            Some(4)
+        );
+
+        // Signals
+        let mut results = proxy.receive_result_ready();
+        // Make sure the match rule of the stream reached the bus before emitting
+        proxy.reset().await.unwrap();
+        CalculatorEmitter::new(&server_connection, "/org/example/Calculator")
+            .emit_result_ready(42)
+            .unwrap();
+        assert_eq!(results.next().await, Some((42,)));
+
+        server_connection.unregister_object(id).unwrap();
+    });
+
+    bus.down();
+}
@@ -0,0 +1,231 @@
+// D-Bus interface `org.example.Calculator`.
+
+fn calculator_interface_info() -> gio::DBusInterfaceInfo {
+    gio::DBusNodeInfo::new_for_xml("<node>\n  <interface name=\"org.example.Calculator\">\n    <method name=\"Add\">\n      <arg type=\"i\" name=\"a\" direction=\"in\">\n      </arg>\n      <arg type=\"i\" name=\"b\" direction=\"in\">\n      </arg>\n      <arg type=\"i\" name=\"result\" direction=\"out\">\n      </arg>\n    </method>\n    <method name=\"Reset\">\n    </method>\n    <method name=\"GetHistory\">\n      <arg type=\"a(so)\" name=\"entries\" direction=\"out\">\n      </arg>\n      <arg type=\"a{sv}\" name=\"meta\" direction=\"out\">\n      </arg>\n    </method>\n    <signal name=\"ResultReady\">\n      <arg type=\"i\" name=\"value\">\n      </arg>\n    </signal>\n    <property type=\"u\" name=\"Precision\" access=\"readwrite\">\n    </property>\n    <property type=\"s\" name=\"Name\" access=\"read\">\n    </property>\n  </interface>\n</node>\n")
+        .expect("invalid D-Bus introspection data")
+        .lookup_interface("org.example.Calculator")
+        .expect("missing D-Bus interface")
+}
+
+/// Server side of the `org.example.Calculator` D-Bus interface.
+pub trait Calculator: Send + Sync + 'static {
+    /// Handles calls of the `Add` method.
+    fn add(&self, a: i32, b: i32) -> Result<i32, gio::glib::Error>;
+    /// Handles calls of the `Reset` method.
+    fn reset(&self) -> Result<(), gio::glib::Error>;
+    /// Handles calls of the `GetHistory` method.
+    fn get_history(&self) -> Result<(Vec<(String, String,)>, std::collections::HashMap<String, gio::glib::Variant>,), gio::glib::Error>;
+    /// Returns the value of the `Precision` property.
+    fn precision(&self) -> u32;
+    /// Sets the value of the `Precision` property.
+    ///
+    /// Returns `false` if the value was rejected.
+    fn set_precision(&self, value: u32) -> bool;
+    /// Returns the value of the `Name` property.
+    fn name(&self) -> String;
+}
+
+/// Exports `imp` as the `org.example.Calculator` interface of the object at `object_path`.
+pub fn register_calculator<T: Calculator>(
+    connection: &gio::DBusConnection,
+    object_path: &str,
+    imp: T,
+) -> Result<gio::RegistrationId, gio::glib::Error> {
+    let imp = std::sync::Arc::new(imp);
+    let method_imp = imp.clone();
+    let get_imp = imp.clone();
+    let set_imp = imp.clone();
+    drop(imp);
+    gio::dbus_codegen_register_object(
+        connection,
+        object_path,
+        &calculator_interface_info(),
+        move |method_name, parameters, invocation| match method_name {
+            "Add" => {
+                let (arg0, arg1,) = match gio::codegen_from_variant::<(i32, i32,)>(&parameters, "(ii)") {
+                    Some(args) => args,
+                    None => {
+                        return invocation.return_dbus_error("org.freedesktop.DBus.Error.InvalidArgs", "Invalid arguments");
+                    }
+                };
+                match method_imp.add(arg0, arg1).and_then(|ret| gio::codegen_to_variant(&(ret,), "(i)")) {
+                    Ok(reply) => invocation.return_value(Some(&reply)),
+                    Err(err) => invocation.return_gerror(err),
+                }
+            }
+            "Reset" => {
+                match method_imp.reset() {
+                    Ok(()) => invocation.return_value(None),
+                    Err(err) => invocation.return_gerror(err),
+                }
+            }
+            "GetHistory" => {
+                match method_imp.get_history().and_then(|ret| gio::codegen_to_variant(&ret, "(a(so)a{sv})")) {
+                    Ok(reply) => invocation.return_value(Some(&reply)),
+                    Err(err) => invocation.return_gerror(err),
+                }
+            }
+            _ => invocation.return_dbus_error("org.freedesktop.DBus.Error.UnknownMethod", "Unknown method"),
+        },
+        move |property_name| match property_name {
+            "Precision" => gio::codegen_to_variant(&get_imp.precision(), "u"),
+            "Name" => gio::codegen_to_variant(&get_imp.name(), "s"),
+            _ => Err(gio::dbus_codegen_unknown_property(property_name)),
+        },
+        move |property_name, value| match property_name {
+            "Precision" => gio::dbus_codegen_set_property(property_name, &value, "u", |value| {
+                set_imp.set_precision(value)
+            }),
+            _ => Err(gio::dbus_codegen_unknown_property(property_name)),
+        },
+    )
+}
+
+/// Emits the signals of the `org.example.Calculator` interface for an object exported with
+/// [`register_calculator`].
+#[derive(Clone, Debug)]
+pub struct CalculatorEmitter {
+    connection: gio::DBusConnection,
+    object_path: String,
+}
+
+impl CalculatorEmitter {
+    pub fn new(connection: &gio::DBusConnection, object_path: &str) -> Self {
+        Self {
+            connection: connection.clone(),
+            object_path: object_path.to_string(),
+        }
+    }
+
+    /// Emits the `ResultReady` signal.
+    pub fn emit_result_ready(&self, value: i32) -> Result<(), gio::glib::Error> {
+        self.connection.emit_signal(
+            None,
+            &self.object_path,
+            "org.example.Calculator",
+            "ResultReady",
+            Some(&gio::codegen_to_variant(&(value,), "(i)")?),
+        )
+    }
+}
+
+/// Client side proxy for the `org.example.Calculator` D-Bus interface.
+#[derive(Clone, Debug)]
+pub struct CalculatorProxy(gio::DBusProxy);
+
+impl CalculatorProxy {
+    pub const INTERFACE_NAME: &'static str = "org.example.Calculator";
+
+    /// Creates a proxy for the object at `object_path` owned by `name` on `connection`.
+    pub async fn new(
+        connection: &gio::DBusConnection,
+        name: Option<&str>,
+        object_path: &str,
+    ) -> Result<Self, gio::glib::Error> {
+        let proxy = gio::DBusProxy::new_future(
+            connection,
+            gio::DBusProxyFlags::NONE,
+            Some(&calculator_interface_info()),
+            name,
+            object_path,
+            Self::INTERFACE_NAME,
+        )
+        .await?;
+        Ok(Self(proxy))
+    }
+
+    /// Wraps an existing proxy for the `org.example.Calculator` interface.
+    pub fn from_proxy(proxy: gio::DBusProxy) -> Self {
+        Self(proxy)
+    }
+
+    pub fn proxy(&self) -> &gio::DBusProxy {
+        &self.0
+    }
+
+    /// Calls the `Add` method.
+    pub async fn add(&self, a: i32, b: i32) -> Result<i32, gio::glib::Error> {
+        let reply = gio::prelude::DBusProxyExt::call_future(
+            &self.0,
+            "Add",
+            Some(&gio::codegen_to_variant(&(a, b,), "(ii)")?),
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await?;
+        gio::codegen_from_variant::<(i32,)>(&reply, "(i)")
+            .map(|ret| ret.0)
+            .ok_or_else(gio::dbus_codegen_invalid_reply)
+    }
+
+    /// Calls the `Reset` method.
+    pub async fn reset(&self) -> Result<(), gio::glib::Error> {
+        let reply = gio::prelude::DBusProxyExt::call_future(
+            &self.0,
+            "Reset",
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await?;
+        let _ = reply;
+        Ok(())
+    }
+
+    /// Calls the `GetHistory` method.
+    pub async fn get_history(&self) -> Result<(Vec<(String, String,)>, std::collections::HashMap<String, gio::glib::Variant>,), gio::glib::Error> {
+        let reply = gio::prelude::DBusProxyExt::call_future(
+            &self.0,
+            "GetHistory",
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+        )
+        .await?;
+        gio::codegen_from_variant::<(Vec<(String, String,)>, std::collections::HashMap<String, gio::glib::Variant>,)>(&reply, "(a(so)a{sv})")
+            .ok_or_else(gio::dbus_codegen_invalid_reply)
+    }
+
+    /// Returns the cached value of the `Precision` property.
+    pub fn precision(&self) -> Option<u32> {
+        let value = gio::prelude::DBusProxyExt::get_cached_property(&self.0, "Precision")?;
+        gio::codegen_from_variant(&value, "u")
+    }
+
+    /// Sets the `Precision` property.
+    pub async fn set_precision(&self, value: u32) -> Result<(), gio::glib::Error> {
+        let connection = gio::prelude::DBusProxyExt::get_connection(&self.0);
+        let bus_name = gio::prelude::DBusProxyExt::get_property_g_name(&self.0);
+        let object_path = gio::prelude::DBusProxyExt::get_object_path(&self.0);
+        let parameters = gio::glib::ToVariant::to_variant(&(
+            Self::INTERFACE_NAME,
+            "Precision",
+            gio::codegen_to_variant(&value, "u")?,
+        ));
+        connection
+            .call_future(
+                bus_name.as_deref(),
+                &object_path,
+                "org.freedesktop.DBus.Properties",
+                "Set",
+                Some(&parameters),
+                None,
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the cached value of the `Name` property.
+    pub fn name(&self) -> Option<String> {
+        let value = gio::prelude::DBusProxyExt::get_cached_property(&self.0, "Name")?;
+        gio::codegen_from_variant(&value, "s")
+    }
+
+    /// Returns a stream of the arguments of the `ResultReady` signal.
+    pub fn receive_result_ready(&self) -> gio::DBusSignalStream<(i32,)> {
+        gio::DBusSignalStream::for_proxy(&self.0, "ResultReady", "(i)")
+    }
+}
+
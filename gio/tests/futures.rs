@@ -6,7 +6,7 @@
 // TODO: merge to the test module of the corresponding source files once the crate
 // has been ported to 2018.
 
-use futures_util::io::{AsyncBufReadExt, AsyncReadExt};
+use futures_util::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 use gio::prelude::*;
 use gio::MemoryInputStream;
 use glib::Bytes;
@@ -84,3 +84,132 @@ fn async_buf_read() {
     let main_context = glib::MainContext::new();
     main_context.block_on(run()).unwrap();
 }
+
+#[test]
+fn async_read_write_socket() {
+    async fn run() -> Result<(), Box<dyn Error>> {
+        let listener = gio::SocketListener::new();
+        let port = listener.add_any_inet_port(None::<&glib::Object>)?;
+        let client = gio::SocketClient::new();
+
+        let (accepted, connected) = futures_util::future::join(
+            listener.accept_async_future(),
+            client.connect_to_host_async_future("127.0.0.1", port),
+        )
+        .await;
+        let (server, _) = accepted?;
+        let mut server = server
+            .into_async_read_write()
+            .map_err(|_| "server connection is not pollable")?;
+        let mut client = connected?
+            .into_async_read_write()
+            .map_err(|_| "client connection is not pollable")?;
+
+        client.write_all(b"ping").await?;
+        let mut buf = [0u8; 4];
+        server.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"ping");
+
+        server.write_all(b"pong").await?;
+        client.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"pong");
+
+        Ok(())
+    }
+
+    let main_context = glib::MainContext::new();
+    main_context.block_on(run()).unwrap();
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_io {
+    use futures_util::future::poll_fn;
+    use gio::prelude::*;
+    use std::error::Error;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::Poll;
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    // Without tokio's `io-util` feature, the traits are only used through their poll functions
+    async fn read<R: AsyncRead + Unpin>(read: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+        poll_fn(|cx| {
+            let mut buf = ReadBuf::new(buf);
+            match Pin::new(&mut *read).poll_read(cx, &mut buf) {
+                Poll::Ready(Ok(())) => Poll::Ready(Ok(buf.filled().len())),
+                Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    async fn write<W: AsyncWrite + Unpin>(write: &mut W, buf: &[u8]) -> io::Result<usize> {
+        poll_fn(|cx| Pin::new(&mut *write).poll_write(cx, buf)).await
+    }
+
+    async fn shutdown<W: AsyncWrite + Unpin>(write: &mut W) -> io::Result<()> {
+        poll_fn(|cx| Pin::new(&mut *write).poll_shutdown(cx)).await
+    }
+
+    #[test]
+    fn read_write_memory() {
+        async fn run() -> Result<(), Box<dyn Error>> {
+            let b = glib::Bytes::from_owned(vec![1, 2, 3]);
+            let mut input = gio::MemoryInputStream::from_bytes(&b)
+                .into_async_read()
+                .map_err(|_| "memory input stream is not pollable")?;
+            let mut buf = [0u8; 4];
+            assert_eq!(read(&mut input, &mut buf).await?, 3);
+            assert_eq!(buf, [1, 2, 3, 0]);
+            assert_eq!(read(&mut input, &mut buf).await?, 0);
+
+            let mut output = gio::MemoryOutputStream::new_resizable()
+                .into_async_write()
+                .map_err(|_| "memory output stream is not pollable")?;
+            assert_eq!(write(&mut output, &[1, 2, 3]).await?, 3);
+            shutdown(&mut output).await?;
+            assert_eq!(&*output.output_stream().steal_as_bytes(), &[1, 2, 3]);
+
+            Ok(())
+        }
+
+        let main_context = glib::MainContext::new();
+        main_context.block_on(run()).unwrap();
+    }
+
+    #[test]
+    fn read_write_socket() {
+        async fn run() -> Result<(), Box<dyn Error>> {
+            let listener = gio::SocketListener::new();
+            let port = listener.add_any_inet_port(None::<&glib::Object>)?;
+            let client = gio::SocketClient::new();
+
+            let (accepted, connected) = futures_util::future::join(
+                listener.accept_async_future(),
+                client.connect_to_host_async_future("127.0.0.1", port),
+            )
+            .await;
+            let (server, _) = accepted?;
+            let mut server = server
+                .into_async_read_write()
+                .map_err(|_| "server connection is not pollable")?;
+            let mut client = connected?
+                .into_async_read_write()
+                .map_err(|_| "client connection is not pollable")?;
+
+            assert_eq!(write(&mut client, b"ping").await?, 4);
+            let mut buf = [0u8; 4];
+            let mut len = 0;
+            while len < buf.len() {
+                len += read(&mut server, &mut buf[len..]).await?;
+            }
+            assert_eq!(&buf, b"ping");
+
+            Ok(())
+        }
+
+        let main_context = glib::MainContext::new();
+        main_context.block_on(run()).unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gio::prelude::*;
+use std::cell::Cell;
+use std::process::Command;
+use std::rc::Rc;
+
+// Generated from `settings_codegen/org.example.app.gschema.xml` with
+// `gio::generate_settings_schema`
+#[allow(dead_code)]
+mod settings {
+    include!("settings_codegen/settings.rs");
+}
+
+use settings::{AppSettings, ExportMode, Style, WindowMode};
+
+// Compiles the test schema and opens it with a memory backend, without touching the
+// environment of the other tests
+fn app_settings() -> AppSettings {
+    let schema_dir = concat!(env!("OUT_DIR"), "/settings_codegen");
+    std::fs::create_dir_all(schema_dir).unwrap();
+    let status = Command::new("glib-compile-schemas")
+        .args(&[
+            "--strict",
+            "--targetdir",
+            schema_dir,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/settings_codegen"),
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success(), "Can't test without GSchemas!");
+
+    let source = gio::SettingsSchemaSource::from_directory(schema_dir, None, false).unwrap();
+    let schema = source.lookup(AppSettings::SCHEMA_ID, false).unwrap();
+    let settings =
+        gio::Settings::new_full(&schema, Some(&gio::memory_settings_backend_new()), None);
+    AppSettings::from_settings(settings)
+}
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let code =
+        gio::generate_settings_schema(include_str!("settings_codegen/org.example.app.gschema.xml"))
+            .unwrap();
+    assert_eq!(code, include_str!("settings_codegen/settings.rs"));
+}
+
+#[test]
+fn read_write_keys() {
+    let settings = app_settings();
+
+    assert_eq!(settings.get_window_title(), "Example");
+    assert_eq!(settings.get_zoom(), 1.0);
+    assert_eq!(settings.get_window_mode(), WindowMode::Normal);
+    assert_eq!(settings.get_export_mode(), ExportMode::Fast);
+    assert_eq!(settings.get_style(), Style::empty());
+    assert!(settings.get_recent_objects().is_empty());
+
+    let changes = Rc::new(Cell::new(0));
+    let changes_clone = changes.clone();
+    settings.connect_window_title_changed(move |settings| {
+        assert_eq!(settings.get_window_title(), "Changed");
+        changes_clone.set(changes_clone.get() + 1);
+    });
+
+    settings.set_window_title("Changed").unwrap();
+    settings.set_zoom(2.5).unwrap();
+    settings.set_window_mode(WindowMode::FullScreen).unwrap();
+    settings.set_export_mode(ExportMode::Best).unwrap();
+    settings.set_style(Style::BOLD | Style::ITALIC).unwrap();
+    settings
+        .set_recent_objects(vec!["/org/example/a".to_string()])
+        .unwrap();
+    assert!(settings
+        .set_recent_objects(vec!["not a path".to_string()])
+        .is_err());
+
+    assert_eq!(changes.get(), 1);
+    assert_eq!(settings.get_window_title(), "Changed");
+    assert_eq!(settings.get_zoom(), 2.5);
+    assert_eq!(settings.get_window_mode(), WindowMode::FullScreen);
+    assert_eq!(settings.get_export_mode(), ExportMode::Best);
+    assert_eq!(settings.get_style(), Style::BOLD | Style::ITALIC);
+    assert_eq!(settings.get_recent_objects(), vec!["/org/example/a"]);
+    assert_eq!(
+        settings.settings().get_string("window-mode").as_str(),
+        WindowMode::FullScreen.nick()
+    );
+}
@@ -0,0 +1,227 @@
+/// Values of the `org.example.app.window.Mode` settings enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowMode {
+    Normal = 0,
+    FullScreen = 1,
+}
+
+impl WindowMode {
+    /// Returns the variant with the integer value `value`.
+    pub fn from_value(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Normal),
+            1 => Some(Self::FullScreen),
+            _ => None,
+        }
+    }
+
+    /// Returns the nick of the variant in the schema.
+    pub fn nick(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::FullScreen => "full-screen",
+        }
+    }
+}
+
+/// Values of the `org.example.app.export.Mode` settings enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportMode {
+    Fast = 0,
+    Best = 1,
+}
+
+impl ExportMode {
+    /// Returns the variant with the integer value `value`.
+    pub fn from_value(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Fast),
+            1 => Some(Self::Best),
+            _ => None,
+        }
+    }
+
+    /// Returns the nick of the variant in the schema.
+    pub fn nick(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Best => "best",
+        }
+    }
+}
+
+gio::glib::bitflags::bitflags! {
+    /// Flags of the `org.example.app.Style` settings flags type.
+    pub struct Style: u32 {
+        const BOLD = 1;
+        const ITALIC = 2;
+    }
+}
+
+/// Typed accessors for the `org.example.app` settings schema.
+#[derive(Debug, Clone)]
+pub struct AppSettings(gio::Settings);
+
+impl AppSettings {
+    pub const SCHEMA_ID: &'static str = "org.example.app";
+
+    pub fn new() -> Self {
+        Self(gio::Settings::new(Self::SCHEMA_ID))
+    }
+
+    pub fn from_settings(settings: gio::Settings) -> Self {
+        Self(settings)
+    }
+
+    pub fn settings(&self) -> &gio::Settings {
+        &self.0
+    }
+
+    /// Title of the main window
+    pub fn get_window_title(&self) -> String {
+        gio::prelude::SettingsExtManual::get(&self.0, "window-title")
+    }
+
+    pub fn set_window_title(&self, value: &str) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_string(&self.0, "window-title", value)
+    }
+
+    pub fn connect_window_title_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("window-title"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    pub fn bind_window_title<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {
+        gio::prelude::SettingsExtManual::bind(&self.0, "window-title", object, property)
+    }
+
+    pub fn get_zoom(&self) -> f64 {
+        gio::prelude::SettingsExtManual::get(&self.0, "zoom")
+    }
+
+    pub fn set_zoom(&self, value: f64) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExtManual::set(&self.0, "zoom", &value)
+    }
+
+    pub fn connect_zoom_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("zoom"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    pub fn bind_zoom<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {
+        gio::prelude::SettingsExtManual::bind(&self.0, "zoom", object, property)
+    }
+
+    pub fn get_window_mode(&self) -> WindowMode {
+        WindowMode::from_value(gio::prelude::SettingsExt::get_enum(&self.0, "window-mode"))
+            .expect("Invalid value of key 'window-mode'")
+    }
+
+    pub fn set_window_mode(&self, value: WindowMode) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_enum(&self.0, "window-mode", value as i32)
+    }
+
+    pub fn connect_window_mode_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("window-mode"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    pub fn bind_window_mode<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {
+        gio::prelude::SettingsExtManual::bind(&self.0, "window-mode", object, property)
+    }
+
+    pub fn get_export_mode(&self) -> ExportMode {
+        ExportMode::from_value(gio::prelude::SettingsExt::get_enum(&self.0, "export-mode"))
+            .expect("Invalid value of key 'export-mode'")
+    }
+
+    pub fn set_export_mode(&self, value: ExportMode) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_enum(&self.0, "export-mode", value as i32)
+    }
+
+    pub fn connect_export_mode_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("export-mode"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    pub fn bind_export_mode<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {
+        gio::prelude::SettingsExtManual::bind(&self.0, "export-mode", object, property)
+    }
+
+    pub fn get_style(&self) -> Style {
+        Style::from_bits_truncate(gio::prelude::SettingsExt::get_flags(&self.0, "style"))
+    }
+
+    pub fn set_style(&self, value: Style) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_flags(&self.0, "style", value.bits())
+    }
+
+    pub fn connect_style_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("style"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    pub fn bind_style<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {
+        gio::prelude::SettingsExtManual::bind(&self.0, "style", object, property)
+    }
+
+    pub fn get_recent_objects(&self) -> Vec<String> {
+        gio::codegen_from_variant(&gio::prelude::SettingsExt::get_value(&self.0, "recent-objects"), "ao")
+            .expect("Type mismatch of key 'recent-objects'")
+    }
+
+    pub fn set_recent_objects(&self, value: Vec<String>) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_value(
+            &self.0,
+            "recent-objects",
+            &gio::codegen_to_variant(&value, "ao")
+                .map_err(|err| gio::glib::bool_error!(err.to_string()))?,
+        )
+    }
+
+    pub fn connect_recent_objects_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("recent-objects"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    pub fn bind_recent_objects<'a, P: gio::glib::IsA<gio::glib::Object>>(
+        &'a self,
+        object: &'a P,
+        property: &'a str,
+    ) -> gio::BindingBuilder<'a> {
+        gio::prelude::SettingsExtManual::bind(&self.0, "recent-objects", object, property)
+    }
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
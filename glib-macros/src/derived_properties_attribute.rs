@@ -0,0 +1,97 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::abort_call_site;
+use quote::quote;
+
+pub const WRONG_PLACE_MSG: &str =
+    "This macro should be used on `impl` block for `glib::ObjectImpl` trait";
+
+pub fn impl_derived_properties(input: &syn::ItemImpl) -> TokenStream {
+    let mut has_properties = false;
+    let mut has_set_property = false;
+    let mut has_get_property = false;
+    for item in &input.items {
+        if let syn::ImplItem::Method(method) = item {
+            let name = method.sig.ident.to_string();
+            if name == "properties" {
+                has_properties = true;
+            } else if name == "set_property" {
+                has_set_property = true;
+            } else if name == "get_property" {
+                has_get_property = true;
+            }
+        }
+    }
+
+    let syn::ItemImpl {
+        attrs,
+        generics,
+        trait_,
+        self_ty,
+        items,
+        ..
+    } = &input;
+
+    let crate_ident = crate::utils::crate_ident_new();
+
+    let properties_opt = if has_properties {
+        None
+    } else {
+        Some(quote! {
+            fn properties() -> &'static [#crate_ident::ParamSpec] {
+                <Self as #crate_ident::subclass::property::DerivedObjectProperties>::derived_properties()
+            }
+        })
+    };
+
+    let set_property_opt = if has_set_property {
+        None
+    } else {
+        Some(quote! {
+            fn set_property(
+                &self,
+                obj: &<Self as #crate_ident::subclass::types::ObjectSubclass>::Type,
+                id: usize,
+                value: &#crate_ident::Value,
+                pspec: &#crate_ident::ParamSpec,
+            ) {
+                <Self as #crate_ident::subclass::property::DerivedObjectProperties>::derived_set_property(
+                    self, obj, id, value, pspec,
+                )
+            }
+        })
+    };
+
+    let get_property_opt = if has_get_property {
+        None
+    } else {
+        Some(quote! {
+            fn get_property(
+                &self,
+                obj: &<Self as #crate_ident::subclass::types::ObjectSubclass>::Type,
+                id: usize,
+                pspec: &#crate_ident::ParamSpec,
+            ) -> #crate_ident::Value {
+                <Self as #crate_ident::subclass::property::DerivedObjectProperties>::derived_get_property(
+                    self, obj, id, pspec,
+                )
+            }
+        })
+    };
+
+    let trait_path = match &trait_ {
+        Some(path) => &path.1,
+        None => abort_call_site!(WRONG_PLACE_MSG),
+    };
+
+    quote! {
+        #(#attrs)*
+        impl#generics #trait_path for #self_ty {
+            #properties_opt
+            #set_property_opt
+            #get_property_opt
+            #(#items)*
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 mod clone;
+mod derived_properties_attribute;
 mod downgrade_derive;
 mod gboxed_derive;
 mod genum_derive;
@@ -8,7 +9,9 @@ mod gerror_domain_derive;
 mod gflags_attribute;
 mod object_interface_attribute;
 mod object_subclass_attribute;
+mod properties_derive;
 mod utils;
+mod variant_derive;
 
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
@@ -467,3 +470,167 @@ pub fn downgrade(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     downgrade_derive::impl_downgrade(input)
 }
+
+/// Derive macro for declaring the properties of an [`ObjectSubclass`] implementation struct.
+///
+/// Each field annotated with `#[property(...)]` becomes a GObject property. The field has to be
+/// an interior mutable container implementing [`PropertyStorage`], e.g. `Cell<T>`, `RefCell<T>`,
+/// `Mutex<T>` or `RwLock<T>`, whose value type implements [`HasParamSpec`].
+///
+/// The macro implements [`DerivedObjectProperties`] for the struct and adds typed
+/// `get_*()`, `set_*()` and `connect_*_notify()` methods to the wrapper type given by
+/// `#[properties(wrapper_type = ...)]`.
+///
+/// The following attributes are supported on the fields:
+///
+/// - `get`, `set`: make the property readable or writable. Both optionally take the path of a
+///   function that is called instead of accessing the field, e.g. `get = Self::get_full_name`.
+/// - `name = "..."`: the property name, defaults to the field name in kebab-case.
+/// - `nick = "..."`, `blurb = "..."`: defaults to the property name and the field's doc comment.
+/// - `minimum = ..`, `maximum = ..`, `default = ..`: bounds and default value of the property.
+/// - `construct`, `construct_only`: add the corresponding `glib::ParamFlags`.
+///
+/// # Example
+///
+/// ```
+/// use glib::prelude::*;
+/// use glib::subclass::prelude::*;
+///
+/// mod imp {
+///     use super::*;
+///     use std::cell::{Cell, RefCell};
+///
+///     #[derive(Default, glib::Properties)]
+///     #[properties(wrapper_type = super::Author)]
+///     pub struct Author {
+///         /// Name of the author
+///         #[property(get, set)]
+///         name: RefCell<Option<String>>,
+///         #[property(get, set, minimum = 0, maximum = 150)]
+///         age: Cell<i32>,
+///     }
+///
+///     #[glib::object_subclass]
+///     impl ObjectSubclass for Author {
+///         const NAME: &'static str = "Author";
+///         type Type = super::Author;
+///         type ParentType = glib::Object;
+///     }
+///
+///     #[glib::derived_properties]
+///     impl ObjectImpl for Author {}
+/// }
+///
+/// glib::wrapper! {
+///     pub struct Author(ObjectSubclass<imp::Author>);
+/// }
+///
+/// fn main() {
+///     let author: Author = glib::Object::new(&[("age", &42)]).unwrap();
+///     author.set_name(Some("Marie".to_string()));
+///     assert_eq!(author.get_name().as_deref(), Some("Marie"));
+///     assert_eq!(author.get_age(), 42);
+/// }
+/// ```
+///
+/// [`ObjectSubclass`]: subclass/types/trait.ObjectSubclass.html
+/// [`PropertyStorage`]: subclass/property/trait.PropertyStorage.html
+/// [`HasParamSpec`]: subclass/property/trait.HasParamSpec.html
+/// [`DerivedObjectProperties`]: subclass/property/trait.DerivedObjectProperties.html
+#[proc_macro_derive(Properties, attributes(properties, property))]
+#[proc_macro_error]
+pub fn properties_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = properties_derive::impl_properties(&input);
+    gen.into()
+}
+
+/// Macro for forwarding the property methods of an [`ObjectImpl`] implementation to
+/// the ones generated by the [`Properties`] derive macro.
+///
+/// This adds implementations of `properties()`, `set_property()` and `get_property()` that call
+/// the corresponding methods of [`DerivedObjectProperties`], unless they are already defined.
+///
+/// [`ObjectImpl`]: subclass/object/trait.ObjectImpl.html
+/// [`Properties`]: derive.Properties.html
+/// [`DerivedObjectProperties`]: subclass/property/trait.DerivedObjectProperties.html
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn derived_properties(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    use proc_macro_error::abort_call_site;
+    match syn::parse::<syn::ItemImpl>(item) {
+        Ok(input) => derived_properties_attribute::impl_derived_properties(&input).into(),
+        Err(_) => abort_call_site!(derived_properties_attribute::WRONG_PLACE_MSG),
+    }
+}
+
+/// Derive macro for implementing [`StaticVariantType`], [`ToVariant`] and [`FromVariant`].
+///
+/// Structs are encoded as tuples of their fields, unless they are marked with
+/// `#[variant(dict)]`, in which case they are encoded as `a{sv}` dictionaries keyed by field
+/// name. Newtype structs are encoded like the wrapped value.
+///
+/// Enums without fields are encoded as strings containing the variant name, or as their `i32`
+/// discriminant when marked with `#[variant(repr)]`. Other enums are encoded as `(sv)` tuples
+/// containing the variant name and the boxed fields of the variant.
+///
+/// Dictionary keys and variant names can be changed with `#[variant(name = "...")]`.
+///
+/// # Example
+///
+/// ```
+/// use glib::prelude::*;
+///
+/// #[derive(Debug, PartialEq, glib::Variant)]
+/// struct Window {
+///     title: String,
+///     size: (i32, i32),
+/// }
+///
+/// #[derive(Debug, PartialEq, glib::Variant)]
+/// #[variant(dict)]
+/// struct Options {
+///     #[variant(name = "auto-save")]
+///     auto_save: bool,
+///     interval: u32,
+/// }
+///
+/// #[derive(Debug, PartialEq, glib::Variant)]
+/// enum Shape {
+///     Circle(f64),
+///     Rect { width: f64, height: f64 },
+/// }
+///
+/// let window = Window {
+///     title: String::from("glib"),
+///     size: (800, 600),
+/// };
+/// let variant = window.to_variant();
+/// assert_eq!(variant.type_().to_str(), "(s(ii))");
+/// assert_eq!(variant.get::<Window>(), Some(window));
+///
+/// let options = Options {
+///     auto_save: true,
+///     interval: 5,
+/// };
+/// let variant = options.to_variant();
+/// assert_eq!(variant.type_().to_str(), "a{sv}");
+/// let dict = glib::VariantDict::new(Some(&variant));
+/// assert_eq!(dict.lookup_value("auto-save", None), Some(true.to_variant()));
+/// assert_eq!(variant.get::<Options>(), Some(options));
+///
+/// let variant = Shape::Circle(1.0).to_variant();
+/// assert_eq!(variant.to_string(), "('Circle', <1.0>)");
+/// assert_eq!(variant.get::<Shape>(), Some(Shape::Circle(1.0)));
+/// ```
+///
+/// [`StaticVariantType`]: variant/trait.StaticVariantType.html
+/// [`ToVariant`]: variant/trait.ToVariant.html
+/// [`FromVariant`]: variant/trait.FromVariant.html
+#[proc_macro_derive(Variant, attributes(variant))]
+#[proc_macro_error]
+pub fn variant_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = variant_derive::impl_variant(&input);
+    gen.into()
+}
@@ -0,0 +1,419 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use heck::{KebabCase, SnakeCase};
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{abort, abort_call_site};
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, Expr, Field, Fields, Lit, LitStr, Meta, Path, Token, Type};
+
+use crate::utils::crate_ident_new;
+
+pub const WRONG_PLACE_MSG: &str =
+    "derive(Properties) only supports structs with named fields, such as the implementation struct of an `ObjectSubclass`";
+
+// Parse attribute such as:
+// #[properties(wrapper_type = super::Author)]
+struct PropertiesAttribute {
+    wrapper_type: Path,
+}
+
+impl Parse for PropertiesAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident != "wrapper_type" {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("Unknown properties meta {}", ident),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let wrapper_type = input.parse()?;
+
+        Ok(Self { wrapper_type })
+    }
+}
+
+enum PropertyAttribute {
+    Get(Option<Path>),
+    Set(Option<Path>),
+    Name(LitStr),
+    Nick(LitStr),
+    Blurb(LitStr),
+    Minimum(Expr),
+    Maximum(Expr),
+    Default(Expr),
+    Construct,
+    ConstructOnly,
+}
+
+impl Parse for PropertyAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        let name = ident.to_string();
+
+        let attr = match name.as_str() {
+            "get" | "set" => {
+                let path = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                if name == "get" {
+                    PropertyAttribute::Get(path)
+                } else {
+                    PropertyAttribute::Set(path)
+                }
+            }
+            "construct" => PropertyAttribute::Construct,
+            "construct_only" => PropertyAttribute::ConstructOnly,
+            "name" | "nick" | "blurb" | "minimum" | "maximum" | "default" => {
+                input.parse::<Token![=]>()?;
+                match name.as_str() {
+                    "name" => PropertyAttribute::Name(input.parse()?),
+                    "nick" => PropertyAttribute::Nick(input.parse()?),
+                    "blurb" => PropertyAttribute::Blurb(input.parse()?),
+                    "minimum" => PropertyAttribute::Minimum(input.parse()?),
+                    "maximum" => PropertyAttribute::Maximum(input.parse()?),
+                    _ => PropertyAttribute::Default(input.parse()?),
+                }
+            }
+            s => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("Unknown property meta {}", s),
+                ))
+            }
+        };
+
+        Ok(attr)
+    }
+}
+
+struct Property<'a> {
+    field: &'a Ident,
+    ty: &'a Type,
+    name: String,
+    nick: String,
+    blurb: String,
+    get: Option<Option<Path>>,
+    set: Option<Option<Path>>,
+    minimum: Option<Expr>,
+    maximum: Option<Expr>,
+    default: Option<Expr>,
+    construct: bool,
+    construct_only: bool,
+}
+
+// Collect the doc comment of a field, used as default blurb
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::NameValue(n)) => match n.lit {
+                Lit::Str(s) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+fn parse_property(field: &Field) -> Option<Property> {
+    let attr = field.attrs.iter().find(|a| a.path.is_ident("property"))?;
+    let attrs = match attr.parse_args_with(Punctuated::<PropertyAttribute, Token![,]>::parse_terminated) {
+        Ok(attrs) => attrs,
+        Err(e) => abort!(
+            e.span(),
+            "{}: supported attributes are #[property(get, set, name = \"..\", nick = \"..\", blurb = \"..\", minimum = .., maximum = .., default = .., construct, construct_only)]",
+            e
+        ),
+    };
+
+    let field_ident = field.ident.as_ref().unwrap();
+    let mut prop = Property {
+        field: field_ident,
+        ty: &field.ty,
+        name: field_ident.to_string().to_kebab_case(),
+        nick: String::new(),
+        blurb: String::new(),
+        get: None,
+        set: None,
+        minimum: None,
+        maximum: None,
+        default: None,
+        construct: false,
+        construct_only: false,
+    };
+    let mut nick = None;
+    let mut blurb = None;
+
+    for attr in attrs {
+        match attr {
+            PropertyAttribute::Get(p) => prop.get = Some(p),
+            PropertyAttribute::Set(p) => prop.set = Some(p),
+            PropertyAttribute::Name(n) => prop.name = n.value(),
+            PropertyAttribute::Nick(n) => nick = Some(n.value()),
+            PropertyAttribute::Blurb(b) => blurb = Some(b.value()),
+            PropertyAttribute::Minimum(e) => prop.minimum = Some(e),
+            PropertyAttribute::Maximum(e) => prop.maximum = Some(e),
+            PropertyAttribute::Default(e) => prop.default = Some(e),
+            PropertyAttribute::Construct => prop.construct = true,
+            PropertyAttribute::ConstructOnly => prop.construct_only = true,
+        }
+    }
+
+    if prop.get.is_none() && prop.set.is_none() {
+        abort!(
+            attr,
+            "property `{}` needs at least one of `get` or `set`",
+            prop.name
+        );
+    }
+    if (prop.construct || prop.construct_only) && prop.set.is_none() {
+        abort!(
+            attr,
+            "property `{}` can only be `construct` or `construct_only` if it has `set`",
+            prop.name
+        );
+    }
+
+    prop.nick = nick.unwrap_or_else(|| prop.name.clone());
+    prop.blurb = blurb
+        .or_else(|| doc_comment(&field.attrs))
+        .unwrap_or_else(|| prop.nick.clone());
+
+    Some(prop)
+}
+
+fn gen_option(expr: &Option<Expr>) -> TokenStream {
+    match expr {
+        Some(e) => quote_spanned! { e.span() => Some(#e) },
+        None => quote! { None },
+    }
+}
+
+fn gen_param_spec(prop: &Property, crate_ident: &Ident) -> TokenStream {
+    let Property {
+        ty,
+        name,
+        nick,
+        blurb,
+        ..
+    } = prop;
+
+    let mut flags = Vec::new();
+    if prop.get.is_some() {
+        flags.push(format_ident!("READABLE"));
+    }
+    if prop.set.is_some() {
+        flags.push(format_ident!("WRITABLE"));
+    }
+    if prop.construct {
+        flags.push(format_ident!("CONSTRUCT"));
+    }
+    if prop.construct_only {
+        flags.push(format_ident!("CONSTRUCT_ONLY"));
+    }
+
+    let minimum = gen_option(&prop.minimum);
+    let maximum = gen_option(&prop.maximum);
+    let default = gen_option(&prop.default);
+
+    quote_spanned! { ty.span() =>
+        <<#ty as #crate_ident::subclass::property::PropertyStorage>::Value
+            as #crate_ident::subclass::property::HasParamSpec>::param_spec(
+            #name,
+            #nick,
+            #blurb,
+            #crate_ident::subclass::property::ParamSpecArgs {
+                minimum: #minimum,
+                maximum: #maximum,
+                default: #default,
+            },
+            #crate_ident::ParamFlags::empty() #(| #crate_ident::ParamFlags::#flags)*,
+        )
+    }
+}
+
+fn gen_get_arm(prop: &Property, crate_ident: &Ident) -> Option<TokenStream> {
+    let Property {
+        field, ty, name, ..
+    } = prop;
+
+    let value = match prop.get.as_ref()? {
+        Some(path) => quote! { #path(self) },
+        None => quote! {
+            <#ty as #crate_ident::subclass::property::PropertyStorage>::get_value(&self.#field)
+        },
+    };
+
+    Some(quote! {
+        #name => #crate_ident::ToValue::to_value(&#value),
+    })
+}
+
+fn gen_set_arm(prop: &Property, crate_ident: &Ident) -> Option<TokenStream> {
+    let Property {
+        field, ty, name, ..
+    } = prop;
+
+    let set = match prop.set.as_ref()? {
+        Some(path) => quote! { #path(self, value) },
+        None => quote! {
+            <#ty as #crate_ident::subclass::property::PropertyStorage>::set_value(&self.#field, value)
+        },
+    };
+
+    Some(quote! {
+        #name => {
+            let value = <<#ty as #crate_ident::subclass::property::PropertyStorage>::Value
+                as #crate_ident::subclass::property::HasParamSpec>::from_property_value(value);
+            #set;
+        }
+    })
+}
+
+fn gen_wrapper_methods(prop: &Property, crate_ident: &Ident) -> TokenStream {
+    let Property { ty, name, .. } = prop;
+    let method_name = name.to_snake_case();
+    let value_ty = quote! { <#ty as #crate_ident::subclass::property::PropertyStorage>::Value };
+
+    let getter = if prop.get.is_some() {
+        let getter = format_ident!("get_{}", method_name);
+        quote! {
+            pub fn #getter(&self) -> #value_ty {
+                let value = #crate_ident::ObjectExt::get_property(self, #name)
+                    .expect("property declared by derive(Properties)");
+                <#value_ty as #crate_ident::subclass::property::HasParamSpec>::from_property_value(&value)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let setter = if prop.set.is_some() && !prop.construct_only {
+        let setter = format_ident!("set_{}", method_name);
+        quote! {
+            pub fn #setter(&self, value: #value_ty) {
+                #crate_ident::ObjectExt::set_property(self, #name, &value)
+                    .expect("property declared by derive(Properties)");
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let connect_notify = format_ident!("connect_{}_notify", method_name);
+
+    quote! {
+        #getter
+
+        #setter
+
+        pub fn #connect_notify<F: Fn(&Self) + 'static>(&self, f: F) -> #crate_ident::SignalHandlerId {
+            #crate_ident::ObjectExt::connect_notify_local(self, Some(#name), move |obj, _| f(obj))
+        }
+    }
+}
+
+pub fn impl_properties(input: &syn::DeriveInput) -> TokenStream {
+    let name = &input.ident;
+
+    let fields = match input.data {
+        Data::Struct(ref s) => match s.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => abort_call_site!(WRONG_PLACE_MSG),
+        },
+        _ => abort_call_site!(WRONG_PLACE_MSG),
+    };
+
+    if !input.generics.params.is_empty() {
+        abort!(
+            input.generics,
+            "derive(Properties) does not support generics"
+        );
+    }
+
+    let wrapper_type = match input.attrs.iter().find(|a| a.path.is_ident("properties")) {
+        Some(attr) => match attr.parse_args::<PropertiesAttribute>() {
+            Ok(attr) => attr.wrapper_type,
+            Err(e) => abort!(
+                e.span(),
+                "{}: derive(Properties) requires #[properties(wrapper_type = WrapperType)]",
+                e
+            ),
+        },
+        None => abort_call_site!(
+            "derive(Properties) requires #[properties(wrapper_type = WrapperType)]"
+        ),
+    };
+
+    let crate_ident = crate_ident_new();
+
+    let properties = fields.iter().filter_map(parse_property).collect::<Vec<_>>();
+
+    let param_specs = properties.iter().map(|p| gen_param_spec(p, &crate_ident));
+    let get_arms = properties
+        .iter()
+        .filter_map(|p| gen_get_arm(p, &crate_ident));
+    let set_arms = properties
+        .iter()
+        .filter_map(|p| gen_set_arm(p, &crate_ident));
+    let wrapper_methods = properties
+        .iter()
+        .map(|p| gen_wrapper_methods(p, &crate_ident));
+
+    quote! {
+        impl #crate_ident::subclass::property::DerivedObjectProperties for #name {
+            fn derived_properties() -> &'static [#crate_ident::ParamSpec] {
+                static PROPERTIES: #crate_ident::once_cell::sync::Lazy<Vec<#crate_ident::ParamSpec>> =
+                    #crate_ident::once_cell::sync::Lazy::new(|| {
+                        vec![#(#param_specs),*]
+                    });
+
+                PROPERTIES.as_ref()
+            }
+
+            #[allow(unused_variables)]
+            fn derived_set_property(
+                &self,
+                _obj: &<Self as #crate_ident::subclass::types::ObjectSubclass>::Type,
+                _id: usize,
+                value: &#crate_ident::Value,
+                pspec: &#crate_ident::ParamSpec,
+            ) {
+                match pspec.get_name() {
+                    #(#set_arms)*
+                    name => unreachable!("invalid property {}", name),
+                }
+            }
+
+            fn derived_get_property(
+                &self,
+                _obj: &<Self as #crate_ident::subclass::types::ObjectSubclass>::Type,
+                _id: usize,
+                pspec: &#crate_ident::ParamSpec,
+            ) -> #crate_ident::Value {
+                match pspec.get_name() {
+                    #(#get_arms)*
+                    name => unreachable!("invalid property {}", name),
+                }
+            }
+        }
+
+        impl #wrapper_type {
+            #(#wrapper_methods)*
+        }
+    }
+}
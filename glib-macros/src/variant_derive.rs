@@ -0,0 +1,471 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro_error::abort;
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, Generics, LitStr, Token};
+
+use crate::utils::crate_ident_new;
+
+enum VariantAttribute {
+    Dict,
+    Repr,
+    Name(LitStr),
+}
+
+impl Parse for VariantAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+
+        let attr = match ident.to_string().as_str() {
+            "dict" => VariantAttribute::Dict,
+            "repr" => VariantAttribute::Repr,
+            "name" => {
+                input.parse::<Token![=]>()?;
+                VariantAttribute::Name(input.parse()?)
+            }
+            s => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("Unknown variant meta {}", s),
+                ))
+            }
+        };
+
+        Ok(attr)
+    }
+}
+
+// Parse attributes such as:
+// #[variant(dict)]
+// #[variant(name = "my-field")]
+fn parse_variant_attributes(attrs: &[Attribute]) -> Vec<VariantAttribute> {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("variant"))
+        .flat_map(|a| {
+            match a.parse_args_with(Punctuated::<VariantAttribute, Token![,]>::parse_terminated) {
+                Ok(attrs) => attrs,
+                Err(e) => abort!(
+                    e.span(),
+                    "{}: supported attributes are #[variant(dict)], #[variant(repr)] and #[variant(name = \"..\")]",
+                    e
+                ),
+            }
+        })
+        .collect()
+}
+
+fn has_flag(attrs: &[VariantAttribute], flag: fn(&VariantAttribute) -> bool) -> bool {
+    attrs.iter().any(flag)
+}
+
+// Name given with #[variant(name = "..")], used as dictionary key or enum tag
+fn item_name(attrs: &[Attribute], ident: &Ident) -> String {
+    parse_variant_attributes(attrs)
+        .into_iter()
+        .find_map(|a| match a {
+            VariantAttribute::Name(name) => Some(name.value()),
+            _ => None,
+        })
+        .unwrap_or_else(|| ident.to_string())
+}
+
+// Adds `bounds` to all type parameters
+fn add_bounds(generics: &Generics, bounds: &[TokenStream]) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        for bound in bounds {
+            param.bounds.push(syn::parse2(bound.clone()).unwrap());
+        }
+    }
+
+    generics
+}
+
+// Signature of a tuple made of `types`, computed at runtime
+fn gen_tuple_signature<'a>(
+    types: impl Iterator<Item = &'a syn::Type>,
+    crate_ident: &Ident,
+) -> TokenStream {
+    let types = types.collect::<Vec<_>>();
+    if types.is_empty() {
+        return quote! {
+            ::std::borrow::Cow::Borrowed(#crate_ident::VariantTy::new("()").unwrap())
+        };
+    }
+
+    quote! {
+        let mut signature = String::with_capacity(255);
+        signature.push('(');
+        #(
+            signature.push_str(
+                <#types as #crate_ident::StaticVariantType>::static_variant_type().to_str()
+            );
+        )*
+        signature.push(')');
+
+        #crate_ident::VariantType::new(&signature)
+            .expect("incorrect signature")
+            .into()
+    }
+}
+
+// Converts the bindings of `fields` to a tuple `Variant`
+fn gen_tuple_to_variant(bindings: &[Ident], crate_ident: &Ident) -> TokenStream {
+    quote! {
+        #crate_ident::Variant::tuple(&[
+            #(#crate_ident::ToVariant::to_variant(#bindings)),*
+        ])
+    }
+}
+
+// Constructs `path` from the children of the tuple `Variant` `variant`
+fn gen_tuple_from_variant(
+    path: TokenStream,
+    fields: &Fields,
+    variant: &Ident,
+    crate_ident: &Ident,
+) -> TokenStream {
+    let values = fields.iter().enumerate().map(|(i, field)| {
+        let ty = &field.ty;
+        quote_spanned! { field.span() =>
+            <#ty as #crate_ident::FromVariant>::from_variant(&#variant.get_child_value(#i))?
+        }
+    });
+
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #values),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path(#(#values),*) },
+        Fields::Unit => path,
+    }
+}
+
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("field{}", i),
+        })
+        .collect()
+}
+
+fn destructure(path: TokenStream, fields: &Fields, bindings: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Named(_) => quote! { #path { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { #path(#(#bindings),*) },
+        Fields::Unit => path,
+    }
+}
+
+struct Impls {
+    static_variant_type: TokenStream,
+    to_variant: TokenStream,
+    from_variant: TokenStream,
+}
+
+fn impl_struct(input: &DeriveInput, fields: &Fields, crate_ident: &Ident) -> Impls {
+    let attrs = parse_variant_attributes(&input.attrs);
+    let dict = has_flag(&attrs, |a| matches!(a, VariantAttribute::Dict));
+    if has_flag(&attrs, |a| matches!(a, VariantAttribute::Repr)) {
+        abort!(input, "#[variant(repr)] is only supported on enums");
+    }
+
+    let variant = Ident::new("variant", Span::call_site());
+    let bindings = field_bindings(fields);
+    let pattern = destructure(quote! { Self }, fields, &bindings);
+
+    if dict {
+        let named = match fields {
+            Fields::Named(named) => named,
+            _ => abort!(
+                input,
+                "#[variant(dict)] is only supported on structs with named fields"
+            ),
+        };
+
+        let keys = named
+            .named
+            .iter()
+            .map(|field| item_name(&field.attrs, field.ident.as_ref().unwrap()))
+            .collect::<Vec<_>>();
+        let names = named.named.iter().map(|field| &field.ident);
+        let values = named.named.iter().zip(keys.iter()).map(|(field, key)| {
+            let ty = &field.ty;
+            quote_spanned! { field.span() =>
+                <#ty as #crate_ident::FromVariant>::from_variant(&dict.lookup_value(
+                    #key,
+                    Some(&*<#ty as #crate_ident::StaticVariantType>::static_variant_type()),
+                )?)?
+            }
+        });
+
+        return Impls {
+            static_variant_type: quote! {
+                ::std::borrow::Cow::Borrowed(#crate_ident::VariantTy::new("a{sv}").unwrap())
+            },
+            to_variant: quote! {
+                let #pattern = self;
+                let dict = #crate_ident::VariantDict::new(None);
+                #(
+                    dict.insert_value(#keys, &#crate_ident::ToVariant::to_variant(#bindings));
+                )*
+                dict.end()
+            },
+            from_variant: quote! {
+                if !#variant.is::<Self>() {
+                    return None;
+                }
+
+                let dict = #crate_ident::VariantDict::new(Some(#variant));
+                Some(Self { #(#names: #values),* })
+            },
+        };
+    }
+
+    // Newtypes have the representation of the wrapped type
+    if let Fields::Unnamed(unnamed) = fields {
+        if unnamed.unnamed.len() == 1 {
+            let ty = &unnamed.unnamed[0].ty;
+            return Impls {
+                static_variant_type: quote! {
+                    <#ty as #crate_ident::StaticVariantType>::static_variant_type()
+                },
+                to_variant: quote! {
+                    #crate_ident::ToVariant::to_variant(&self.0)
+                },
+                from_variant: quote! {
+                    Some(Self(<#ty as #crate_ident::FromVariant>::from_variant(#variant)?))
+                },
+            };
+        }
+    }
+
+    let to_tuple = gen_tuple_to_variant(&bindings, crate_ident);
+    let from_tuple = gen_tuple_from_variant(quote! { Self }, fields, &variant, crate_ident);
+
+    Impls {
+        static_variant_type: gen_tuple_signature(fields.iter().map(|f| &f.ty), crate_ident),
+        to_variant: quote! {
+            let #pattern = self;
+            #to_tuple
+        },
+        from_variant: quote! {
+            if !#variant.is::<Self>() {
+                return None;
+            }
+
+            Some(#from_tuple)
+        },
+    }
+}
+
+fn impl_enum(input: &DeriveInput, data: &syn::DataEnum, crate_ident: &Ident) -> Impls {
+    let attrs = parse_variant_attributes(&input.attrs);
+    if has_flag(&attrs, |a| matches!(a, VariantAttribute::Dict)) {
+        abort!(
+            input,
+            "#[variant(dict)] is only supported on structs with named fields"
+        );
+    }
+    let repr = has_flag(&attrs, |a| matches!(a, VariantAttribute::Repr));
+    let c_like = data
+        .variants
+        .iter()
+        .all(|v| matches!(v.fields, Fields::Unit));
+
+    let variant = Ident::new("variant", Span::call_site());
+    let idents = data.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let tags = data
+        .variants
+        .iter()
+        .map(|v| item_name(&v.attrs, &v.ident))
+        .collect::<Vec<_>>();
+
+    if repr {
+        if !c_like {
+            abort!(
+                input,
+                "#[variant(repr)] is only supported on enums without fields"
+            );
+        }
+
+        // Encoded as the `i32` discriminant
+        return Impls {
+            static_variant_type: quote! {
+                <i32 as #crate_ident::StaticVariantType>::static_variant_type()
+            },
+            to_variant: quote! {
+                let value = match self {
+                    #(Self::#idents => Self::#idents as i32,)*
+                };
+                #crate_ident::ToVariant::to_variant(&value)
+            },
+            from_variant: quote! {
+                let value = #variant.get::<i32>()?;
+                #(
+                    if value == Self::#idents as i32 {
+                        return Some(Self::#idents);
+                    }
+                )*
+                None
+            },
+        };
+    }
+
+    if c_like {
+        // Encoded as the variant name
+        return Impls {
+            static_variant_type: quote! {
+                <str as #crate_ident::StaticVariantType>::static_variant_type()
+            },
+            to_variant: quote! {
+                let tag = match self {
+                    #(Self::#idents => #tags,)*
+                };
+                #crate_ident::ToVariant::to_variant(tag)
+            },
+            from_variant: quote! {
+                match #variant.get_str()? {
+                    #(#tags => Some(Self::#idents),)*
+                    _ => None,
+                }
+            },
+        };
+    }
+
+    // Encoded as `(sv)`, the tag and the boxed content of the variant
+    let content = Ident::new("content", Span::call_site());
+    let to_arms = data.variants.iter().zip(tags.iter()).map(|(v, tag)| {
+        let ident = &v.ident;
+        let bindings = field_bindings(&v.fields);
+        let pattern = destructure(quote! { Self::#ident }, &v.fields, &bindings);
+        let content = if bindings.len() == 1 && matches!(v.fields, Fields::Unnamed(_)) {
+            let binding = &bindings[0];
+            quote! { #crate_ident::ToVariant::to_variant(#binding) }
+        } else {
+            gen_tuple_to_variant(&bindings, crate_ident)
+        };
+
+        quote! {
+            #pattern => (#tag, #content),
+        }
+    });
+    let from_arms = data.variants.iter().zip(tags.iter()).map(|(v, tag)| {
+        let ident = &v.ident;
+        let value = match &v.fields {
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                let ty = &unnamed.unnamed[0].ty;
+                quote! {
+                    Self::#ident(<#ty as #crate_ident::FromVariant>::from_variant(&#content)?)
+                }
+            }
+            fields => {
+                let n_fields = fields.len();
+                let from_tuple =
+                    gen_tuple_from_variant(quote! { Self::#ident }, fields, &content, crate_ident);
+                quote! {
+                    {
+                        if !#content.type_().to_str().starts_with('(')
+                            || #content.n_children() != #n_fields
+                        {
+                            return None;
+                        }
+
+                        #from_tuple
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #tag => Some(#value),
+        }
+    });
+
+    Impls {
+        static_variant_type: quote! {
+            ::std::borrow::Cow::Borrowed(#crate_ident::VariantTy::new("(sv)").unwrap())
+        },
+        to_variant: quote! {
+            let (tag, content) = match self {
+                #(#to_arms)*
+            };
+            #crate_ident::Variant::tuple(&[
+                #crate_ident::ToVariant::to_variant(tag),
+                #crate_ident::Variant::variant(&content),
+            ])
+        },
+        from_variant: quote! {
+            if !#variant.is::<Self>() {
+                return None;
+            }
+
+            let #content = #variant.get_child_value(1).get_variant()?;
+            match #variant.get_child_value(0).get_str()? {
+                #(#from_arms)*
+                _ => None,
+            }
+        },
+    }
+}
+
+pub fn impl_variant(input: &DeriveInput) -> TokenStream {
+    let crate_ident = crate_ident_new();
+    let name = &input.ident;
+
+    let impls = match &input.data {
+        Data::Struct(data) => impl_struct(input, &data.fields, &crate_ident),
+        Data::Enum(data) => impl_enum(input, data, &crate_ident),
+        Data::Union(_) => abort!(input, "derive(Variant) does not support unions"),
+    };
+    let Impls {
+        static_variant_type,
+        to_variant,
+        from_variant,
+    } = impls;
+
+    let static_generics = add_bounds(
+        &input.generics,
+        &[quote! { #crate_ident::StaticVariantType }],
+    );
+    let (static_impl_generics, ty_generics, static_where_clause) = static_generics.split_for_impl();
+    let to_generics = add_bounds(
+        &input.generics,
+        &[
+            quote! { #crate_ident::StaticVariantType },
+            quote! { #crate_ident::ToVariant },
+        ],
+    );
+    let (to_impl_generics, _, to_where_clause) = to_generics.split_for_impl();
+    let from_generics = add_bounds(&input.generics, &[quote! { #crate_ident::FromVariant }]);
+    let (from_impl_generics, _, from_where_clause) = from_generics.split_for_impl();
+
+    quote! {
+        impl #static_impl_generics #crate_ident::StaticVariantType for #name #ty_generics #static_where_clause {
+            fn static_variant_type() -> ::std::borrow::Cow<'static, #crate_ident::VariantTy> {
+                #static_variant_type
+            }
+        }
+
+        impl #to_impl_generics #crate_ident::ToVariant for #name #ty_generics #to_where_clause {
+            fn to_variant(&self) -> #crate_ident::Variant {
+                #to_variant
+            }
+        }
+
+        impl #from_impl_generics #crate_ident::FromVariant for #name #ty_generics #from_where_clause {
+            fn from_variant(variant: &#crate_ident::Variant) -> Option<Self> {
+                #from_variant
+            }
+        }
+    }
+}
@@ -173,3 +173,204 @@ fn attr_gflags() {
     assert!(e.get_value_by_nick("ab").is_none());
     assert!(e.get_value_by_nick("c").is_some());
 }
+
+mod properties {
+    pub mod imp {
+        use glib::subclass::prelude::*;
+        use std::cell::{Cell, RefCell};
+
+        #[derive(Default, glib::Properties)]
+        #[properties(wrapper_type = super::Author)]
+        pub struct Author {
+            /// Name of the author
+            #[property(get, set)]
+            name: RefCell<Option<String>>,
+            #[property(get, set, minimum = 0, maximum = 150, default = 0)]
+            age: Cell<i32>,
+            #[property(get, set, construct_only, name = "pen-name")]
+            alias: RefCell<String>,
+            #[property(get = Self::get_title_impl)]
+            title: RefCell<String>,
+        }
+
+        impl Author {
+            fn get_title_impl(&self) -> String {
+                format!(
+                    "{}{} ({})",
+                    self.title.borrow(),
+                    self.alias.borrow(),
+                    self.age.get()
+                )
+            }
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Author {
+            const NAME: &'static str = "DerivePropertiesAuthor";
+            type Type = super::Author;
+            type ParentType = glib::Object;
+        }
+
+        #[glib::derived_properties]
+        impl ObjectImpl for Author {}
+    }
+
+    glib::wrapper! {
+        pub struct Author(ObjectSubclass<imp::Author>);
+    }
+}
+
+#[test]
+fn derive_properties() {
+    use properties::Author;
+
+    let author: Author = glib::Object::new(&[("pen-name", &"Mark Twain")]).unwrap();
+
+    let pspec = author.find_property("name").unwrap();
+    assert_eq!(pspec.get_blurb(), "Name of the author");
+    assert_eq!(pspec.get_flags(), glib::ParamFlags::READWRITE);
+    let pspec = author.find_property("age").unwrap();
+    let pspec = pspec.downcast_ref::<glib::ParamSpecInt>().unwrap();
+    assert_eq!(pspec.get_minimum(), 0);
+    assert_eq!(pspec.get_maximum(), 150);
+    let pspec = author.find_property("title").unwrap();
+    assert_eq!(pspec.get_flags(), glib::ParamFlags::READABLE);
+
+    assert_eq!(author.get_name(), None);
+    author.set_name(Some("Samuel Clemens".to_string()));
+    assert_eq!(author.get_name().as_deref(), Some("Samuel Clemens"));
+    assert_eq!(
+        author.get_property("name").unwrap().get::<&str>(),
+        Ok(Some("Samuel Clemens"))
+    );
+
+    let notified = std::rc::Rc::new(std::cell::Cell::new(0));
+    author.connect_age_notify(glib::clone!(@strong notified => move |_| {
+        notified.set(notified.get() + 1);
+    }));
+    author.set_age(74);
+    assert_eq!(notified.get(), 1);
+    assert_eq!(author.get_age(), 74);
+
+    assert_eq!(author.get_pen_name(), "Mark Twain");
+    assert_eq!(author.get_title(), "Mark Twain (74)");
+    assert!(author.set_property("title", &"Dr.").is_err());
+}
+
+#[test]
+fn derive_variant() {
+    #[derive(Debug, PartialEq, glib::Variant)]
+    struct Unit;
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    struct Id(u64);
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    struct Entry {
+        id: Id,
+        name: String,
+        tags: Vec<String>,
+        parent: Option<u32>,
+    }
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    #[variant(dict)]
+    struct Config {
+        #[variant(name = "font-size")]
+        font_size: u32,
+        theme: String,
+    }
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    enum Mode {
+        Light,
+        #[variant(name = "dark")]
+        Dark,
+    }
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    #[variant(repr)]
+    enum Level {
+        Low = 1,
+        High = 10,
+    }
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    enum Event {
+        Quit,
+        Key(u32),
+        Move(i32, i32),
+        Resize { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, PartialEq, glib::Variant)]
+    struct Pair<T>(T, T);
+
+    assert_eq!(Unit::static_variant_type().to_str(), "()");
+    assert_eq!(Unit.to_variant().get::<Unit>(), Some(Unit));
+
+    assert_eq!(Id::static_variant_type().to_str(), "t");
+    assert_eq!(Id(5).to_variant(), 5u64.to_variant());
+
+    let entry = Entry {
+        id: Id(1),
+        name: "foo".into(),
+        tags: vec!["a".into(), "b".into()],
+        parent: None,
+    };
+    assert_eq!(Entry::static_variant_type().to_str(), "(tsasmu)");
+    let variant = entry.to_variant();
+    assert_eq!(variant.type_().to_str(), "(tsasmu)");
+    assert_eq!(variant.get::<Entry>(), Some(entry));
+    assert_eq!(variant.get::<Config>(), None);
+    assert_eq!("foo".to_variant().get::<Entry>(), None);
+
+    let config = Config {
+        font_size: 12,
+        theme: "adwaita".into(),
+    };
+    let variant = config.to_variant();
+    assert_eq!(variant.type_().to_str(), "a{sv}");
+    let dict = glib::VariantDict::new(Some(&variant));
+    assert_eq!(
+        dict.lookup_value("font-size", None),
+        Some(12u32.to_variant())
+    );
+    assert_eq!(variant.get::<Config>(), Some(config));
+    dict.remove("theme");
+    assert_eq!(dict.end().get::<Config>(), None);
+
+    assert_eq!(Mode::Light.to_variant(), "Light".to_variant());
+    assert_eq!(Mode::Dark.to_variant(), "dark".to_variant());
+    assert_eq!("dark".to_variant().get::<Mode>(), Some(Mode::Dark));
+    assert_eq!("Dark".to_variant().get::<Mode>(), None);
+
+    assert_eq!(Level::High.to_variant(), 10i32.to_variant());
+    assert_eq!(1i32.to_variant().get::<Level>(), Some(Level::Low));
+    assert_eq!(2i32.to_variant().get::<Level>(), None);
+
+    assert_eq!(Event::static_variant_type().to_str(), "(sv)");
+    for event in vec![
+        Event::Quit,
+        Event::Key(42),
+        Event::Move(-1, 1),
+        Event::Resize {
+            width: 10,
+            height: 20,
+        },
+    ] {
+        assert_eq!(event.to_variant().get::<Event>(), Some(event));
+    }
+    assert_eq!(
+        Event::Move(-1, 1).to_variant().to_string(),
+        "('Move', <(-1, 1)>)"
+    );
+    let variant = ("Key", "not a key".to_variant()).to_variant();
+    assert_eq!(variant.get::<Event>(), None);
+
+    assert_eq!(Pair::<u8>::static_variant_type().to_str(), "(yy)");
+    assert_eq!(
+        Pair(1u8, 2u8).to_variant().get::<Pair<u8>>(),
+        Some(Pair(1, 2))
+    );
+}
@@ -14,9 +14,10 @@ pub enum GlibLoggerFormat {
     Plain,
     /// A simple format, writing file, line and message on output.
     LineAndFile,
-    /// A logger using glib structured logging. Structured logging is available
-    /// only on features `v2_56` and later.
-    #[cfg(any(feature = "v2_56", feature = "dox"))]
+    /// A logger using glib structured logging, with the file, line and module
+    /// path of the record as separate fields. Structured logging is available
+    /// only on features `v2_50` and later.
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
     Structured,
 }
 
@@ -94,15 +95,15 @@ impl GlibLogger {
         GlibLogger { format, domain }
     }
 
-    fn level_to_glib(level: rs_log::Level) -> crate::ffi::GLogLevelFlags {
+    fn level_to_glib(level: rs_log::Level) -> glib_log::LogLevel {
         match level {
             // Errors are mapped to critical to avoid automatic termination
-            rs_log::Level::Error => crate::ffi::G_LOG_LEVEL_CRITICAL,
-            rs_log::Level::Warn => crate::ffi::G_LOG_LEVEL_WARNING,
-            rs_log::Level::Info => crate::ffi::G_LOG_LEVEL_INFO,
-            rs_log::Level::Debug => crate::ffi::G_LOG_LEVEL_DEBUG,
+            rs_log::Level::Error => glib_log::LogLevel::Critical,
+            rs_log::Level::Warn => glib_log::LogLevel::Warning,
+            rs_log::Level::Info => glib_log::LogLevel::Info,
+            rs_log::Level::Debug => glib_log::LogLevel::Debug,
             // There is no equivalent to trace level in glib
-            rs_log::Level::Trace => crate::ffi::G_LOG_LEVEL_DEBUG,
+            rs_log::Level::Trace => glib_log::LogLevel::Debug,
         }
     }
 
@@ -111,38 +112,38 @@ impl GlibLogger {
         unsafe {
             crate::ffi::g_log(
                 domain.to_glib_none().0,
-                GlibLogger::level_to_glib(level),
+                GlibLogger::level_to_glib(level).to_glib(),
                 message.replace("%", "%%").to_glib_none().0,
             );
         }
     }
 
-    #[cfg(any(feature = "v2_56", feature = "dox"))]
-    #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_56")))]
-    #[doc(alias = "g_log_structured_standard")]
+    #[cfg(any(feature = "v2_50", feature = "dox"))]
+    #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+    #[doc(alias = "g_log_structured_array")]
     fn write_log_structured(
         domain: Option<&str>,
         level: rs_log::Level,
         file: Option<&str>,
         line: Option<u32>,
-        func: Option<&str>,
+        module_path: Option<&str>,
         message: &str,
     ) {
-        let line_str = match line {
-            None => None,
-            Some(l) => Some(l.to_string()),
-        };
+        let line = line.map(|l| l.to_string());
 
-        unsafe {
-            crate::ffi::g_log_structured_standard(
-                domain.to_glib_none().0,
-                GlibLogger::level_to_glib(level),
-                file.to_glib_none().0,
-                line_str.to_glib_none().0,
-                func.to_glib_none().0,
-                message.replace("%", "%%").to_glib_none().0,
-            );
+        let mut fields: Vec<(&str, &[u8])> = Vec::with_capacity(4);
+        fields.push(("MESSAGE", message.as_bytes()));
+        if let Some(file) = file {
+            fields.push(("CODE_FILE", file.as_bytes()));
+        }
+        if let Some(line) = &line {
+            fields.push(("CODE_LINE", line.as_bytes()));
         }
+        if let Some(module_path) = module_path {
+            fields.push(("CODE_MODULE", module_path.as_bytes()));
+        }
+
+        glib_log::log_structured_fields(domain, GlibLogger::level_to_glib(level), &fields);
     }
 }
 
@@ -176,14 +177,14 @@ impl rs_log::Log for GlibLogger {
 
                 GlibLogger::write_log(domain, record.level(), &s);
             }
-            #[cfg(any(feature = "v2_56", feature = "dox"))]
+            #[cfg(any(feature = "v2_50", feature = "dox"))]
             GlibLoggerFormat::Structured => {
                 GlibLogger::write_log_structured(
                     domain,
                     record.level(),
                     record.file(),
                     record.line(),
-                    None,
+                    record.module_path(),
                     &format!("{}", record.args()),
                 );
             }
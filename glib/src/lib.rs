@@ -87,7 +87,8 @@ pub use bitflags;
 pub use once_cell;
 
 pub use glib_macros::{
-    clone, gflags, object_interface, object_subclass, Downgrade, GBoxed, GEnum, GErrorDomain,
+    clone, derived_properties, gflags, object_interface, object_subclass, Downgrade, GBoxed, GEnum,
+    GErrorDomain, Properties, Variant,
 };
 
 pub use self::byte_array::ByteArray;
@@ -112,7 +113,8 @@ pub use self::value::{SendValue, ToSendValue, ToValue, TypedValue, Value};
 pub use self::variant::{FromVariant, StaticVariantType, ToVariant, Variant};
 pub use self::variant_dict::VariantDict;
 pub use self::variant_iter::VariantIter;
-pub use self::variant_type::{VariantTy, VariantType};
+pub use self::variant_parse_error::VariantParseError;
+pub use self::variant_type::{VariantTy, VariantTyIterator, VariantType};
 
 pub mod clone;
 #[macro_use]
@@ -169,6 +171,9 @@ pub mod value;
 pub mod variant;
 mod variant_dict;
 mod variant_iter;
+mod variant_parse_error;
+#[cfg(feature = "serde")]
+pub mod variant_serde;
 mod variant_type;
 pub use self::main_context_channel::{Receiver, Sender, SyncSender};
 mod date;
@@ -184,13 +189,20 @@ mod log;
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 pub use self::log::log_set_handler;
 
-// #[cfg(any(feature = "v2_50", feature = "dox"))]
-// pub use log::log_variant;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[doc(hidden)]
+pub use self::log::log_structured_fields;
 pub use self::log::{
     log_default_handler, log_remove_handler, log_set_always_fatal, log_set_default_handler,
     log_set_fatal_mask, log_unset_default_handler, set_print_handler, set_printerr_handler,
     unset_print_handler, unset_printerr_handler, LogHandlerId, LogLevel, LogLevels,
 };
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+pub use self::log::{
+    log_set_writer_func, log_structured_array, log_variant, log_writer_default,
+    log_writer_format_fields, log_writer_is_journald, log_writer_journald,
+    log_writer_standard_streams, log_writer_supports_color, LogField, LogWriterOutput,
+};
 
 #[doc(hidden)]
 #[cfg(any(feature = "dox", feature = "log_macros"))]
@@ -2,10 +2,18 @@
 
 use crate::translate::*;
 use crate::GString;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use crate::Variant;
 use once_cell::sync::Lazy;
 #[cfg(any(feature = "v2_46", feature = "dox"))]
 use std::boxed::Box as Box_;
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::ffi::{CStr, CString};
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+use std::{fmt, slice, str};
 
 #[derive(Debug)]
 pub struct LogHandlerId(u32);
@@ -180,16 +188,305 @@ pub fn log_set_fatal_mask(log_domain: Option<&str>, fatal_levels: LogLevels) ->
     }
 }
 
-// #[cfg(any(feature = "v2_50", feature = "dox"))]
-// pub fn log_variant(log_domain: Option<&str>, log_level: LogLevel, fields: &Variant) {
-//     unsafe {
-//         ffi::g_log_variant(
-//             log_domain.to_glib_none().0,
-//             log_level.to_glib(),
-//             fields.to_glib_none().0,
-//         );
-//     }
-// }
+/// Logs a structured message whose fields are given by the `a{sv}` dictionary `fields`.
+///
+/// `fields` must contain a `MESSAGE` entry. String values are logged as strings, byte arrays
+/// (`ay`) as binary data, and all other values are skipped.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_variant")]
+pub fn log_variant(log_domain: Option<&str>, log_level: LogLevel, fields: &Variant) {
+    unsafe {
+        ffi::g_log_variant(
+            log_domain.to_glib_none().0,
+            log_level.to_glib(),
+            fields.to_glib_none().0,
+        );
+    }
+}
+
+/// A field of a structured log message.
+///
+/// See [`log_structured_array`] and [`log_set_writer_func`].
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[repr(transparent)]
+#[doc(alias = "GLogField")]
+pub struct LogField<'a>(ffi::GLogField, PhantomData<&'a [u8]>);
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+impl<'a> LogField<'a> {
+    /// Creates a field with the given key and value.
+    ///
+    /// Values are usually UTF-8 strings but can also be binary data.
+    pub fn new(key: &'a CStr, value: &'a [u8]) -> Self {
+        LogField(
+            ffi::GLogField {
+                key: key.as_ptr(),
+                value: value.as_ptr() as ffi::gconstpointer,
+                length: value.len() as _,
+            },
+            PhantomData,
+        )
+    }
+
+    /// Returns the key of the field, such as `MESSAGE` or `CODE_FILE`.
+    pub fn key(&self) -> &str {
+        unsafe { CStr::from_ptr(self.0.key) }
+            .to_str()
+            .unwrap_or_default()
+    }
+
+    /// Returns the raw value of the field.
+    pub fn value_bytes(&self) -> &[u8] {
+        unsafe {
+            if self.0.value.is_null() {
+                &[]
+            } else if self.0.length < 0 {
+                CStr::from_ptr(self.0.value as *const libc::c_char).to_bytes()
+            } else {
+                slice::from_raw_parts(self.0.value as *const u8, self.0.length as usize)
+            }
+        }
+    }
+
+    /// Returns the value of the field if it is a UTF-8 string.
+    pub fn value_str(&self) -> Option<&str> {
+        str::from_utf8(self.value_bytes()).ok()
+    }
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+impl<'a> fmt::Debug for LogField<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogField")
+            .field("key", &self.key())
+            .field("value", &String::from_utf8_lossy(self.value_bytes()))
+            .finish()
+    }
+}
+
+/// Result of a log writer function, see [`log_set_writer_func`].
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[doc(alias = "GLogWriterOutput")]
+pub enum LogWriterOutput {
+    /// The message was handled by the writer.
+    Handled,
+    /// The message could not be handled, GLib falls back to printing it to stderr.
+    Unhandled,
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[doc(hidden)]
+impl ToGlib for LogWriterOutput {
+    type GlibType = ffi::GLogWriterOutput;
+
+    fn to_glib(&self) -> ffi::GLogWriterOutput {
+        match *self {
+            LogWriterOutput::Handled => ffi::G_LOG_WRITER_HANDLED,
+            LogWriterOutput::Unhandled => ffi::G_LOG_WRITER_UNHANDLED,
+        }
+    }
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[doc(hidden)]
+impl FromGlib<ffi::GLogWriterOutput> for LogWriterOutput {
+    unsafe fn from_glib(value: ffi::GLogWriterOutput) -> LogWriterOutput {
+        if value == ffi::G_LOG_WRITER_HANDLED {
+            LogWriterOutput::Handled
+        } else {
+            LogWriterOutput::Unhandled
+        }
+    }
+}
+
+/// Logs a structured message made of `fields`.
+///
+/// `fields` must contain a `MESSAGE` field and should contain a `PRIORITY` field. The
+/// [`log_structured!`] macro takes care of the latter as well as of the source location.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_structured_array")]
+pub fn log_structured_array(log_level: LogLevel, fields: &[LogField]) {
+    unsafe {
+        ffi::g_log_structured_array(
+            log_level.to_glib(),
+            fields.as_ptr() as *const ffi::GLogField,
+            fields.len(),
+        );
+    }
+}
+
+// Syslog priority of a log level, as used by GLib
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+fn log_level_priority(log_level: LogLevel) -> &'static str {
+    match log_level {
+        LogLevel::Error => "3",
+        LogLevel::Critical | LogLevel::Warning => "4",
+        LogLevel::Message => "5",
+        LogLevel::Info => "6",
+        LogLevel::Debug => "7",
+    }
+}
+
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[doc(hidden)]
+pub fn log_structured_fields(
+    log_domain: Option<&str>,
+    log_level: LogLevel,
+    fields: &[(&str, &[u8])],
+) {
+    let priority_key = CStr::from_bytes_with_nul(b"PRIORITY\0").unwrap();
+    let domain_key = CStr::from_bytes_with_nul(b"GLIB_DOMAIN\0").unwrap();
+    let keys = fields
+        .iter()
+        .map(|(key, _)| CString::new(*key).expect("log field keys can't contain nul bytes"))
+        .collect::<Vec<_>>();
+
+    let mut log_fields = Vec::with_capacity(fields.len() + 2);
+    log_fields.push(LogField::new(
+        priority_key,
+        log_level_priority(log_level).as_bytes(),
+    ));
+    if let Some(log_domain) = log_domain {
+        log_fields.push(LogField::new(domain_key, log_domain.as_bytes()));
+    }
+    for (key, (_, value)) in keys.iter().zip(fields) {
+        log_fields.push(LogField::new(key, value));
+    }
+
+    log_structured_array(log_level, &log_fields);
+}
+
+/// Sets the function used to write all structured log messages.
+///
+/// This can only be called once per process, before any message is logged. The writer functions
+/// provided by GLib, such as [`log_writer_default`], can be called from `writer_func` to do the
+/// actual output.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_set_writer_func")]
+pub fn log_set_writer_func<
+    P: Fn(LogLevel, &[LogField]) -> LogWriterOutput + Send + Sync + 'static,
+>(
+    writer_func: P,
+) {
+    unsafe extern "C" fn writer_trampoline<
+        P: Fn(LogLevel, &[LogField]) -> LogWriterOutput + Send + Sync + 'static,
+    >(
+        log_level: ffi::GLogLevelFlags,
+        fields: *const ffi::GLogField,
+        n_fields: libc::size_t,
+        user_data: ffi::gpointer,
+    ) -> ffi::GLogWriterOutput {
+        let callback: &P = &*(user_data as *mut _);
+        let fields = if n_fields == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(fields as *const LogField, n_fields)
+        };
+        (*callback)(from_glib(log_level), fields).to_glib()
+    }
+    unsafe extern "C" fn destroy_func<
+        P: Fn(LogLevel, &[LogField]) -> LogWriterOutput + Send + Sync + 'static,
+    >(
+        data: ffi::gpointer,
+    ) {
+        let _callback: Box_<P> = Box_::from_raw(data as *mut _);
+    }
+    let writer_func: Box_<P> = Box_::new(writer_func);
+    unsafe {
+        ffi::g_log_set_writer_func(
+            Some(writer_trampoline::<P> as _),
+            Box_::into_raw(writer_func) as *mut _,
+            Some(destroy_func::<P> as _),
+        );
+    }
+}
+
+/// Writes the message to the systemd journal if stderr is connected to it, and to the standard
+/// streams otherwise. This is the default writer of GLib.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_writer_default")]
+pub fn log_writer_default(log_level: LogLevel, fields: &[LogField]) -> LogWriterOutput {
+    unsafe {
+        from_glib(ffi::g_log_writer_default(
+            log_level.to_glib(),
+            fields.as_ptr() as *const ffi::GLogField,
+            fields.len(),
+            std::ptr::null_mut(),
+        ))
+    }
+}
+
+/// Writes the message to stdout or stderr, depending on its level.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_writer_standard_streams")]
+pub fn log_writer_standard_streams(log_level: LogLevel, fields: &[LogField]) -> LogWriterOutput {
+    unsafe {
+        from_glib(ffi::g_log_writer_standard_streams(
+            log_level.to_glib(),
+            fields.as_ptr() as *const ffi::GLogField,
+            fields.len(),
+            std::ptr::null_mut(),
+        ))
+    }
+}
+
+/// Sends the message with all its fields to the systemd journal.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_writer_journald")]
+pub fn log_writer_journald(log_level: LogLevel, fields: &[LogField]) -> LogWriterOutput {
+    unsafe {
+        from_glib(ffi::g_log_writer_journald(
+            log_level.to_glib(),
+            fields.as_ptr() as *const ffi::GLogField,
+            fields.len(),
+            std::ptr::null_mut(),
+        ))
+    }
+}
+
+/// Checks whether `output_fd` is connected to the systemd journal.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_writer_is_journald")]
+pub fn log_writer_is_journald(output_fd: i32) -> bool {
+    unsafe { from_glib(ffi::g_log_writer_is_journald(output_fd)) }
+}
+
+/// Checks whether `output_fd` supports ANSI color escape sequences.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_writer_supports_color")]
+pub fn log_writer_supports_color(output_fd: i32) -> bool {
+    unsafe { from_glib(ffi::g_log_writer_supports_color(output_fd)) }
+}
+
+/// Formats the message the way [`log_writer_standard_streams`] prints it.
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[doc(alias = "g_log_writer_format_fields")]
+pub fn log_writer_format_fields(
+    log_level: LogLevel,
+    fields: &[LogField],
+    use_color: bool,
+) -> GString {
+    unsafe {
+        from_glib_full(ffi::g_log_writer_format_fields(
+            log_level.to_glib(),
+            fields.as_ptr() as *const ffi::GLogField,
+            fields.len(),
+            use_color.to_glib(),
+        ))
+    }
+}
 
 type PrintCallback = dyn Fn(&str) + Send + Sync + 'static;
 
@@ -730,36 +1027,48 @@ macro_rules! g_printerr {
     }};
 }
 
-// /// Macro used to log using GLib logging system. It uses [g_log_structured][gls].
-// ///
-// /// [gls]: https://developer.gnome.org/glib/stable/glib-Message-Logging.html#g-log-structured)
-// ///
-// /// Example:
-// ///
-// /// ```no_run
-// /// use glib::{LogLevel, g_log_structured};
-// ///
-// /// g_log_structured!("test", LogLevel::Debug, {"MESSAGE" => "tadam!"});
-// /// g_log_structured!("test", LogLevel::Debug, {"MESSAGE" => "tadam!", "random" => "yes"});
-// /// ```
-// #[cfg(any(feature = "v2_50", feature = "dox"))]
-// #[macro_export]
-// macro_rules! g_log_structured {
-//     ($log_domain:expr, $log_level:expr, {$($key:expr => $value:expr),+}) => {{
-//         use $crate::translate::{Stash, ToGlib, ToGlibPtr};
-//         use $crate::LogLevel;
-//         use std::ffi::CString;
-
-//         fn check_log_args(_log_domain: &str, _log_level: LogLevel) {}
-//         fn check_key(key: &str) -> Stash<*const i8, str> { key.to_glib_none() }
-
-//         check_log_args(&$log_domain, $log_level);
-//         unsafe {
-//             ffi::g_log_structured(
-//                 $log_domain.to_glib_none().0,
-//                 $log_level.to_glib(),
-//                 $(check_key($key).0, check_key(format!("{}", $value).as_str()).0 ),+
-//             )
-//         }
-//     }};
-// }
+/// Macro used to log structured messages using GLib logging system. It uses
+/// [g_log_structured_array][glsa].
+///
+/// [glsa]: https://developer.gnome.org/glib/stable/glib-Message-Logging.html#g-log-structured-array
+///
+/// Fields are given as `"KEY" => value` pairs, where values can be anything implementing
+/// `Display`. A `MESSAGE` field is required. The `PRIORITY`, `GLIB_DOMAIN`, `CODE_FILE`,
+/// `CODE_LINE` and `CODE_MODULE` fields are added automatically.
+///
+/// Example:
+///
+/// ```no_run
+/// use glib::{log_structured, LogLevel};
+///
+/// log_structured!("test", LogLevel::Debug, {"MESSAGE" => "tadam!"});
+/// log_structured!(
+///     "test",
+///     LogLevel::Message,
+///     {
+///         "MESSAGE" => format!("{} files copied", 12),
+///         "MY_APP_FILES" => 12,
+///     }
+/// );
+/// ```
+#[cfg(any(feature = "v2_50", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_50")))]
+#[macro_export]
+macro_rules! log_structured {
+    ($log_domain:expr, $log_level:expr, {$($key:expr => $value:expr),+ $(,)?}) => {{
+        // the next line is used to enforce the type for the macro checker...
+        let log_domain: Option<&str> = $log_domain.into();
+        let log_level: $crate::LogLevel = $log_level;
+
+        $crate::log_structured_fields(
+            log_domain,
+            log_level,
+            &[
+                ("CODE_FILE", file!().as_bytes()),
+                ("CODE_LINE", line!().to_string().as_bytes()),
+                ("CODE_MODULE", module_path!().as_bytes()),
+                $(($key, format!("{}", $value).as_bytes())),+
+            ],
+        );
+    }};
+}
@@ -0,0 +1,278 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+//! Module containing the traits used by the [`Properties`] derive macro.
+//!
+//! [`Properties`]: ../../derive.Properties.html
+
+use super::prelude::*;
+use crate::value::{FromValueOptional, SetValueOptional};
+use crate::{ObjectType, ParamFlags, ParamSpec, ToValue, Value};
+
+use std::cell::{Cell, RefCell};
+use std::sync::{Mutex, RwLock};
+
+/// Trait implemented by the [`Properties`] derive macro.
+///
+/// The methods of this trait can be forwarded to from the corresponding methods of
+/// [`ObjectImpl`], either manually or by using the [`derived_properties`] attribute macro.
+///
+/// [`Properties`]: ../../derive.Properties.html
+/// [`ObjectImpl`]: ../object/trait.ObjectImpl.html
+/// [`derived_properties`]: ../../attr.derived_properties.html
+pub trait DerivedObjectProperties: ObjectSubclass {
+    /// Properties declared with `#[property(...)]` attributes.
+    fn derived_properties() -> &'static [ParamSpec] {
+        &[]
+    }
+
+    /// Property setter for the properties declared with `#[property(...)]` attributes.
+    fn derived_set_property(
+        &self,
+        _obj: &Self::Type,
+        _id: usize,
+        _value: &Value,
+        pspec: &ParamSpec,
+    ) {
+        unreachable!("invalid property {}", pspec.get_name())
+    }
+
+    /// Property getter for the properties declared with `#[property(...)]` attributes.
+    fn derived_get_property(&self, _obj: &Self::Type, _id: usize, pspec: &ParamSpec) -> Value {
+        unreachable!("invalid property {}", pspec.get_name())
+    }
+}
+
+/// Optional `minimum`, `maximum` and `default` values of a property.
+#[derive(Debug, Clone, Copy)]
+pub struct ParamSpecArgs<T> {
+    pub minimum: Option<T>,
+    pub maximum: Option<T>,
+    pub default: Option<T>,
+}
+
+impl<T> Default for ParamSpecArgs<T> {
+    fn default() -> Self {
+        Self {
+            minimum: None,
+            maximum: None,
+            default: None,
+        }
+    }
+}
+
+/// Trait for types that can be used as the value of a property declared with the
+/// [`Properties`] derive macro.
+///
+/// [`Properties`]: ../../derive.Properties.html
+pub trait HasParamSpec: ToValue + Sized {
+    /// Type of the `minimum`, `maximum` and `default` values of the property.
+    type Arg;
+
+    /// Creates the `ParamSpec` for a property of this type.
+    fn param_spec(
+        name: &str,
+        nick: &str,
+        blurb: &str,
+        args: ParamSpecArgs<Self::Arg>,
+        flags: ParamFlags,
+    ) -> ParamSpec;
+
+    /// Extracts the property value from `value`.
+    ///
+    /// The type of `value` was already checked by `Object::set_property`.
+    fn from_property_value(value: &Value) -> Self;
+}
+
+macro_rules! numeric_has_param_spec {
+    ($name:ty, $ctor:ident) => {
+        impl HasParamSpec for $name {
+            type Arg = $name;
+
+            fn param_spec(
+                name: &str,
+                nick: &str,
+                blurb: &str,
+                args: ParamSpecArgs<Self::Arg>,
+                flags: ParamFlags,
+            ) -> ParamSpec {
+                let minimum = args.minimum.unwrap_or(<$name>::MIN);
+                let maximum = args.maximum.unwrap_or(<$name>::MAX);
+                // Keep the implicit default inside the allowed range
+                let default = args.default.unwrap_or_else(|| {
+                    let zero = <$name>::default();
+                    if zero < minimum {
+                        minimum
+                    } else if zero > maximum {
+                        maximum
+                    } else {
+                        zero
+                    }
+                });
+
+                ParamSpec::$ctor(name, nick, blurb, minimum, maximum, default, flags)
+            }
+
+            fn from_property_value(value: &Value) -> Self {
+                value
+                    .get_some::<$name>()
+                    .expect("type conformity checked by `Object::set_property`")
+            }
+        }
+    };
+}
+
+numeric_has_param_spec!(i8, char);
+numeric_has_param_spec!(u8, uchar);
+numeric_has_param_spec!(i32, int);
+numeric_has_param_spec!(u32, uint);
+numeric_has_param_spec!(i64, int64);
+numeric_has_param_spec!(u64, uint64);
+numeric_has_param_spec!(f32, float);
+numeric_has_param_spec!(f64, double);
+
+impl HasParamSpec for bool {
+    type Arg = bool;
+
+    fn param_spec(
+        name: &str,
+        nick: &str,
+        blurb: &str,
+        args: ParamSpecArgs<Self::Arg>,
+        flags: ParamFlags,
+    ) -> ParamSpec {
+        ParamSpec::boolean(name, nick, blurb, args.default.unwrap_or(false), flags)
+    }
+
+    fn from_property_value(value: &Value) -> Self {
+        value
+            .get_some::<bool>()
+            .expect("type conformity checked by `Object::set_property`")
+    }
+}
+
+impl HasParamSpec for String {
+    type Arg = &'static str;
+
+    fn param_spec(
+        name: &str,
+        nick: &str,
+        blurb: &str,
+        args: ParamSpecArgs<Self::Arg>,
+        flags: ParamFlags,
+    ) -> ParamSpec {
+        ParamSpec::string(name, nick, blurb, args.default, flags)
+    }
+
+    fn from_property_value(value: &Value) -> Self {
+        value
+            .get::<String>()
+            .expect("type conformity checked by `Object::set_property`")
+            .unwrap_or_default()
+    }
+}
+
+impl HasParamSpec for Option<String> {
+    type Arg = &'static str;
+
+    fn param_spec(
+        name: &str,
+        nick: &str,
+        blurb: &str,
+        args: ParamSpecArgs<Self::Arg>,
+        flags: ParamFlags,
+    ) -> ParamSpec {
+        ParamSpec::string(name, nick, blurb, args.default, flags)
+    }
+
+    fn from_property_value(value: &Value) -> Self {
+        value
+            .get::<String>()
+            .expect("type conformity checked by `Object::set_property`")
+    }
+}
+
+impl<T> HasParamSpec for Option<T>
+where
+    T: ObjectType + for<'a> FromValueOptional<'a> + SetValueOptional,
+{
+    type Arg = ();
+
+    fn param_spec(
+        name: &str,
+        nick: &str,
+        blurb: &str,
+        _args: ParamSpecArgs<Self::Arg>,
+        flags: ParamFlags,
+    ) -> ParamSpec {
+        ParamSpec::object(name, nick, blurb, T::static_type(), flags)
+    }
+
+    fn from_property_value(value: &Value) -> Self {
+        value
+            .get::<T>()
+            .expect("type conformity checked by `Object::set_property`")
+    }
+}
+
+/// Trait for the interior mutable containers that can store the value of a property
+/// declared with the [`Properties`] derive macro.
+///
+/// [`Properties`]: ../../derive.Properties.html
+pub trait PropertyStorage {
+    /// Type of the stored property value.
+    type Value: HasParamSpec;
+
+    /// Returns a copy of the stored value.
+    fn get_value(&self) -> Self::Value;
+
+    /// Replaces the stored value.
+    fn set_value(&self, value: Self::Value);
+}
+
+impl<T: HasParamSpec + Copy> PropertyStorage for Cell<T> {
+    type Value = T;
+
+    fn get_value(&self) -> T {
+        self.get()
+    }
+
+    fn set_value(&self, value: T) {
+        self.set(value);
+    }
+}
+
+impl<T: HasParamSpec + Clone> PropertyStorage for RefCell<T> {
+    type Value = T;
+
+    fn get_value(&self) -> T {
+        self.borrow().clone()
+    }
+
+    fn set_value(&self, value: T) {
+        self.replace(value);
+    }
+}
+
+impl<T: HasParamSpec + Clone> PropertyStorage for Mutex<T> {
+    type Value = T;
+
+    fn get_value(&self) -> T {
+        self.lock().unwrap().clone()
+    }
+
+    fn set_value(&self, value: T) {
+        *self.lock().unwrap() = value;
+    }
+}
+
+impl<T: HasParamSpec + Clone> PropertyStorage for RwLock<T> {
+    type Value = T;
+
+    fn get_value(&self) -> T {
+        self.read().unwrap().clone()
+    }
+
+    fn set_value(&self, value: T) {
+        *self.write().unwrap() = value;
+    }
+}
@@ -91,6 +91,7 @@ use crate::StaticType;
 use crate::Type;
 use crate::Value;
 use crate::VariantIter;
+use crate::VariantParseError;
 use crate::VariantTy;
 use crate::VariantType;
 use std::borrow::Cow;
@@ -98,6 +99,7 @@ use std::cmp::{Eq, Ordering, PartialEq, PartialOrd};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::{BuildHasher, Hash, Hasher};
+use std::ptr;
 use std::slice;
 use std::str;
 
@@ -317,6 +319,59 @@ impl Variant {
     pub fn is_container(&self) -> bool {
         unsafe { ffi::g_variant_is_container(self.to_glib_none().0) != ffi::GFALSE }
     }
+
+    /// Parses a `Variant` from its text format, as produced by `to_string` or
+    /// `print_with_type_annotations`.
+    ///
+    /// If `type_` is given, `text` is parsed as a value of that type and may omit the type
+    /// annotations which would otherwise be needed.
+    ///
+    /// On failure, the message of the returned [`VariantParseError`] points to the location of
+    /// the problem in `text`.
+    ///
+    /// [`VariantParseError`]: ../enum.VariantParseError.html
+    #[doc(alias = "g_variant_parse")]
+    pub fn parse(type_: Option<&VariantTy>, text: &str) -> Result<Self, crate::Error> {
+        unsafe {
+            let text = text.to_glib_none();
+            let mut error = ptr::null_mut();
+            let variant = ffi::g_variant_parse(
+                type_.map_or(ptr::null(), |type_| type_.as_ptr() as *const _),
+                text.0,
+                ptr::null(),
+                ptr::null_mut(),
+                &mut error,
+            );
+
+            if error.is_null() {
+                Ok(from_glib_full(variant))
+            } else {
+                let context: GString =
+                    from_glib_full(ffi::g_variant_parse_error_print_context(error, text.0));
+                let error: crate::Error = from_glib_full(error);
+                let kind = error
+                    .kind::<VariantParseError>()
+                    .unwrap_or(VariantParseError::Failed);
+
+                Err(crate::Error::new(kind, &context))
+            }
+        }
+    }
+
+    /// Pretty-prints the value, with type annotations wherever the type could not be inferred
+    /// from the text, so that `parse` returns a `Variant` of the same type.
+    #[doc(alias = "g_variant_print")]
+    pub fn print_with_type_annotations(&self) -> GString {
+        unsafe { from_glib_full(ffi::g_variant_print(self.to_glib_none().0, true.to_glib())) }
+    }
+}
+
+impl str::FromStr for Variant {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(None, s)
+    }
 }
 
 unsafe impl Send for Variant {}
@@ -750,6 +805,28 @@ where
     }
 }
 
+impl StaticVariantType for () {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        unsafe { VariantTy::from_str_unchecked("()").into() }
+    }
+}
+
+impl ToVariant for () {
+    fn to_variant(&self) -> Variant {
+        Variant::tuple(&[])
+    }
+}
+
+impl FromVariant for () {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if variant.is::<Self>() {
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
 macro_rules! tuple_impls {
     ($($len:expr => ($($n:tt $name:ident)+))+) => {
         $(
@@ -912,4 +989,52 @@ mod tests {
             "a(syu)"
         );
     }
+
+    #[test]
+    fn test_unit() {
+        let variant = ().to_variant();
+        assert_eq!(variant.type_().to_str(), "()");
+        assert_eq!(variant.get::<()>(), Some(()));
+        assert_eq!(1u8.to_variant().get::<()>(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let variant = Variant::parse(None, "(1, 'foo', [true])").unwrap();
+        assert_eq!(variant.type_().to_str(), "(isab)");
+        assert_eq!(variant.to_string(), "(1, 'foo', [true])");
+
+        let variant = Variant::parse(None, "@as []").unwrap();
+        assert_eq!(variant.type_().to_str(), "as");
+        assert_eq!(variant.print_with_type_annotations(), "@as []");
+        assert_eq!(variant.to_string(), "[]");
+
+        let type_ = VariantTy::new("a{sv}").unwrap();
+        let variant = Variant::parse(Some(type_), "{'a': <uint32 5>}").unwrap();
+        assert_eq!(variant.type_(), type_);
+        assert_eq!(
+            variant
+                .print_with_type_annotations()
+                .parse::<Variant>()
+                .unwrap(),
+            variant
+        );
+
+        let variant = Variant::parse(Some(VariantTy::new("(qt)").unwrap()), "(1, 2)").unwrap();
+        assert_eq!(variant.get::<(u16, u64)>(), Some((1, 2)));
+
+        let error = Variant::parse(None, "(1, 'foo").unwrap_err();
+        assert_eq!(
+            error.kind::<VariantParseError>(),
+            Some(VariantParseError::UnterminatedStringConstant)
+        );
+        assert!(error.to_string().contains("(1, 'foo"));
+
+        let error = Variant::parse(Some(VariantTy::new("y").unwrap()), "300").unwrap_err();
+        assert_eq!(
+            error.kind::<VariantParseError>(),
+            Some(VariantParseError::NumberOutOfRange)
+        );
+        assert!("[]".parse::<Variant>().is_err());
+    }
 }
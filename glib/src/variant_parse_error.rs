@@ -0,0 +1,104 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::error::ErrorDomain;
+use crate::translate::from_glib;
+use crate::Quark;
+
+/// Errors returned by [`Variant::parse`](variant/struct.Variant.html#method.parse).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[doc(alias = "GVariantParseError")]
+pub enum VariantParseError {
+    Failed,
+    BasicTypeExpected,
+    CannotInferType,
+    DefiniteTypeExpected,
+    InputNotAtEnd,
+    InvalidCharacter,
+    InvalidFormatString,
+    InvalidObjectPath,
+    InvalidSignature,
+    InvalidTypeString,
+    NoCommonType,
+    NumberOutOfRange,
+    NumberTooBig,
+    TypeError,
+    UnexpectedToken,
+    UnknownKeyword,
+    UnterminatedStringConstant,
+    ValueExpected,
+    Recursion,
+}
+
+impl ErrorDomain for VariantParseError {
+    fn domain() -> Quark {
+        unsafe { from_glib(ffi::g_variant_parse_error_quark()) }
+    }
+
+    fn code(self) -> i32 {
+        use self::VariantParseError::*;
+        match self {
+            Failed => ffi::G_VARIANT_PARSE_ERROR_FAILED as i32,
+            BasicTypeExpected => ffi::G_VARIANT_PARSE_ERROR_BASIC_TYPE_EXPECTED as i32,
+            CannotInferType => ffi::G_VARIANT_PARSE_ERROR_CANNOT_INFER_TYPE as i32,
+            DefiniteTypeExpected => ffi::G_VARIANT_PARSE_ERROR_DEFINITE_TYPE_EXPECTED as i32,
+            InputNotAtEnd => ffi::G_VARIANT_PARSE_ERROR_INPUT_NOT_AT_END as i32,
+            InvalidCharacter => ffi::G_VARIANT_PARSE_ERROR_INVALID_CHARACTER as i32,
+            InvalidFormatString => ffi::G_VARIANT_PARSE_ERROR_INVALID_FORMAT_STRING as i32,
+            InvalidObjectPath => ffi::G_VARIANT_PARSE_ERROR_INVALID_OBJECT_PATH as i32,
+            InvalidSignature => ffi::G_VARIANT_PARSE_ERROR_INVALID_SIGNATURE as i32,
+            InvalidTypeString => ffi::G_VARIANT_PARSE_ERROR_INVALID_TYPE_STRING as i32,
+            NoCommonType => ffi::G_VARIANT_PARSE_ERROR_NO_COMMON_TYPE as i32,
+            NumberOutOfRange => ffi::G_VARIANT_PARSE_ERROR_NUMBER_OUT_OF_RANGE as i32,
+            NumberTooBig => ffi::G_VARIANT_PARSE_ERROR_NUMBER_TOO_BIG as i32,
+            TypeError => ffi::G_VARIANT_PARSE_ERROR_TYPE_ERROR as i32,
+            UnexpectedToken => ffi::G_VARIANT_PARSE_ERROR_UNEXPECTED_TOKEN as i32,
+            UnknownKeyword => ffi::G_VARIANT_PARSE_ERROR_UNKNOWN_KEYWORD as i32,
+            UnterminatedStringConstant => {
+                ffi::G_VARIANT_PARSE_ERROR_UNTERMINATED_STRING_CONSTANT as i32
+            }
+            ValueExpected => ffi::G_VARIANT_PARSE_ERROR_VALUE_EXPECTED as i32,
+            Recursion => ffi::G_VARIANT_PARSE_ERROR_RECURSION as i32,
+        }
+    }
+
+    #[allow(clippy::cognitive_complexity)]
+    fn from(code: i32) -> Option<Self> {
+        use self::VariantParseError::*;
+        match code {
+            x if x == ffi::G_VARIANT_PARSE_ERROR_FAILED as i32 => Some(Failed),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_BASIC_TYPE_EXPECTED as i32 => {
+                Some(BasicTypeExpected)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_CANNOT_INFER_TYPE as i32 => Some(CannotInferType),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_DEFINITE_TYPE_EXPECTED as i32 => {
+                Some(DefiniteTypeExpected)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_INPUT_NOT_AT_END as i32 => Some(InputNotAtEnd),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_INVALID_CHARACTER as i32 => Some(InvalidCharacter),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_INVALID_FORMAT_STRING as i32 => {
+                Some(InvalidFormatString)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_INVALID_OBJECT_PATH as i32 => {
+                Some(InvalidObjectPath)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_INVALID_SIGNATURE as i32 => Some(InvalidSignature),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_INVALID_TYPE_STRING as i32 => {
+                Some(InvalidTypeString)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_NO_COMMON_TYPE as i32 => Some(NoCommonType),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_NUMBER_OUT_OF_RANGE as i32 => {
+                Some(NumberOutOfRange)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_NUMBER_TOO_BIG as i32 => Some(NumberTooBig),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_TYPE_ERROR as i32 => Some(TypeError),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_UNEXPECTED_TOKEN as i32 => Some(UnexpectedToken),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_UNKNOWN_KEYWORD as i32 => Some(UnknownKeyword),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_UNTERMINATED_STRING_CONSTANT as i32 => {
+                Some(UnterminatedStringConstant)
+            }
+            x if x == ffi::G_VARIANT_PARSE_ERROR_VALUE_EXPECTED as i32 => Some(ValueExpected),
+            x if x == ffi::G_VARIANT_PARSE_ERROR_RECURSION as i32 => Some(Recursion),
+            _ => Some(Failed),
+        }
+    }
+}
@@ -0,0 +1,1408 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+//! Conversion between [`Variant`](../struct.Variant.html) and types implementing `serde`'s
+//! `Serialize` and `Deserialize` traits.
+//!
+//! This module is only available with the `serde` feature.
+//!
+//! Rust values are mapped to the following `GVariant` types:
+//!
+//! | Rust                                  | `GVariant`                                   |
+//! |---------------------------------------|----------------------------------------------|
+//! | `bool`                                | `b`                                          |
+//! | `u8`                                  | `y`                                          |
+//! | `i8`, `i16`, `u16`                    | `n`, `n`, `q`                                |
+//! | `i32`, `u32`, `i64`, `u64`            | `i`, `u`, `x`, `t`                           |
+//! | `f32`, `f64`                          | `d`                                          |
+//! | `char`, `&str`, `String`              | `s`                                          |
+//! | byte slices (`serde_bytes`)           | `ay`                                         |
+//! | `Option<T>`                           | maybe type `mT`                              |
+//! | `()`, unit structs                    | `()`                                         |
+//! | newtype structs                       | the type of the wrapped value                |
+//! | sequences                             | array `aT`                                   |
+//! | tuples, tuple structs                 | tuple `(...)`                                |
+//! | structs                               | tuple `(...)` or dictionary `a{sv}`          |
+//! | maps                                  | dictionary `a{KV}`                           |
+//! | unit enum variants                    | `s` with the variant name                    |
+//! | other enum variants                   | `(sv)` with the variant name and its content |
+//!
+//! Without further information, the element type of an empty array is `v`, as is the one of
+//! arrays and dictionaries whose values are of different types. `None` becomes `mv` in that case.
+//!
+//! Passing the expected type to [`to_variant_with_type`](fn.to_variant_with_type.html) resolves
+//! these ambiguities and allows choosing other representations: integers are converted to the
+//! expected integer type, strings can become object paths (`o`) or signatures (`g`), structs are
+//! encoded as `a{sv}` dictionaries if such a type is expected, unit enum variants become their
+//! index if `u` is expected, and values are boxed wherever `v` is expected.
+//!
+//! Deserialization accepts all of these representations and transparently unboxes `v` values.
+//!
+//! # Examples
+//!
+//! ```
+//! use glib::variant_serde::{from_variant, to_variant, to_variant_with_type};
+//! use glib::VariantTy;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Window {
+//!     title: String,
+//!     size: (i32, i32),
+//!     maximized: bool,
+//! }
+//!
+//! let window = Window {
+//!     title: String::from("glib"),
+//!     size: (800, 600),
+//!     maximized: false,
+//! };
+//!
+//! let variant = to_variant(&window).unwrap();
+//! assert_eq!(variant.type_().to_str(), "(s(ii)b)");
+//! assert_eq!(from_variant::<Window>(&variant).unwrap(), window);
+//!
+//! let dict = to_variant_with_type(&window, VariantTy::new("a{sv}").unwrap()).unwrap();
+//! assert_eq!(
+//!     dict.to_string(),
+//!     "{'title': <'glib'>, 'size': <(800, 600)>, 'maximized': <false>}"
+//! );
+//! assert_eq!(from_variant::<Window>(&dict).unwrap(), window);
+//! ```
+
+use crate::translate::*;
+use crate::{Bytes, ToVariant, Variant, VariantIter, VariantTy, VariantType};
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, SerializeTuple};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Error returned when converting between `Variant` and `serde` types fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Serializes `value` into a `Variant`, inferring its type.
+pub fn to_variant<T: Serialize + ?Sized>(value: &T) -> Result<Variant, Error> {
+    value.serialize(Serializer { type_: None })
+}
+
+/// Serializes `value` into a `Variant` of the given type.
+///
+/// `type_` may be indefinite, e.g. `a{s*}`, in which case only its definite parts are enforced.
+pub fn to_variant_with_type<T: Serialize + ?Sized>(
+    value: &T,
+    type_: &VariantTy,
+) -> Result<Variant, Error> {
+    value.serialize(Serializer { type_: Some(type_) })
+}
+
+/// Deserializes a `T` from the contents of `variant`.
+pub fn from_variant<T: DeserializeOwned>(variant: &Variant) -> Result<T, Error> {
+    T::deserialize(Deserializer {
+        variant: variant.clone(),
+    })
+}
+
+fn variant_ty() -> &'static VariantTy {
+    unsafe { VariantTy::from_str_unchecked("v") }
+}
+
+// Checks `value` against the expected type, boxing it if a `v` is expected
+fn finish(type_: Option<&VariantTy>, value: Variant) -> Result<Variant, Error> {
+    match type_ {
+        None => Ok(value),
+        Some(type_) if type_.is_variant() => Ok(Variant::variant(&value)),
+        Some(type_) if value.type_().is_subtype_of(type_) => Ok(value),
+        Some(type_) => Err(Error(format!(
+            "expected a variant of type '{}', got '{}'",
+            type_,
+            value.type_()
+        ))),
+    }
+}
+
+// Type guiding the serialization of a value, `None` if the value is boxed anyway
+fn guide(type_: Option<&VariantTy>) -> Option<&VariantTy> {
+    type_.filter(|type_| !type_.is_variant())
+}
+
+fn maybe_element(type_: Option<&VariantTy>) -> Option<&VariantTy> {
+    guide(type_)
+        .filter(|type_| type_.is_maybe())
+        .map(VariantTy::element)
+}
+
+fn array_element(type_: Option<&VariantTy>) -> Option<&VariantTy> {
+    guide(type_)
+        .filter(|type_| type_.is_array())
+        .map(VariantTy::element)
+}
+
+fn tuple_items(type_: Option<&VariantTy>) -> Option<Vec<&VariantTy>> {
+    guide(type_)
+        .filter(|type_| type_.to_str().starts_with('('))
+        .map(|type_| type_.tuple_types().collect())
+}
+
+fn dict_entry_types(type_: Option<&VariantTy>) -> Option<(&VariantTy, &VariantTy)> {
+    array_element(type_)
+        .filter(|type_| type_.to_str().starts_with('{'))
+        .map(|type_| (type_.key(), type_.value()))
+}
+
+// Expected type of the content of an enum variant encoded as `(sv)`
+fn enum_content_type(type_: Option<&VariantTy>) -> &VariantTy {
+    match tuple_items(type_) {
+        Some(items) if items.len() == 2 => items[1],
+        _ => variant_ty(),
+    }
+}
+
+// Common type of all `children`, `None` if they differ
+fn element_type<'a>(
+    children: &'a [Variant],
+    expected: Option<&'a VariantTy>,
+) -> Option<&'a VariantTy> {
+    match children.split_first() {
+        Some((first, rest)) => {
+            let type_ = first.type_();
+            if rest.iter().all(|child| child.type_() == type_) {
+                Some(type_)
+            } else {
+                None
+            }
+        }
+        None => expected.filter(|type_| type_.is_definite()),
+    }
+}
+
+fn new_array(element_type: &VariantTy, children: &[Variant]) -> Variant {
+    unsafe {
+        from_glib_none(ffi::g_variant_new_array(
+            element_type.as_ptr() as *const _,
+            children.to_glib_none().0,
+            children.len(),
+        ))
+    }
+}
+
+// Creates an array of `children`, boxing them if they are of different types
+fn infer_array(children: &[Variant], expected: Option<&VariantTy>) -> Variant {
+    match element_type(children, expected) {
+        Some(type_) => new_array(type_, children),
+        None => {
+            let children = children.iter().map(Variant::variant).collect::<Vec<_>>();
+            new_array(variant_ty(), &children)
+        }
+    }
+}
+
+fn new_maybe(element_type: &VariantTy, child: Option<&Variant>) -> Variant {
+    let ptr = match child {
+        Some(child) => child.to_glib_none().0,
+        None => std::ptr::null(),
+    };
+    unsafe {
+        from_glib_none(ffi::g_variant_new_maybe(
+            element_type.as_ptr() as *const _,
+            ptr as *mut ffi::GVariant,
+        ))
+    }
+}
+
+fn new_dict_entry(key: &Variant, value: &Variant) -> Variant {
+    unsafe {
+        from_glib_none(ffi::g_variant_new_dict_entry(
+            key.to_glib_none().0,
+            value.to_glib_none().0,
+        ))
+    }
+}
+
+// Wraps the content of an enum variant into `(sv)`
+struct EnumWrap<'a> {
+    name: &'static str,
+    type_: Option<&'a VariantTy>,
+}
+
+impl<'a> EnumWrap<'a> {
+    fn finish(self, content: Variant) -> Result<Variant, Error> {
+        finish(
+            self.type_,
+            Variant::tuple(&[self.name.to_variant(), content]),
+        )
+    }
+}
+
+struct Serializer<'a> {
+    type_: Option<&'a VariantTy>,
+}
+
+impl<'a> Serializer<'a> {
+    fn integer(self, value: i128, default: &'static str) -> Result<Variant, Error> {
+        let type_ = match guide(self.type_).map(VariantTy::to_str) {
+            Some(type_ @ "y") | Some(type_ @ "n") | Some(type_ @ "q") | Some(type_ @ "i")
+            | Some(type_ @ "u") | Some(type_ @ "x") | Some(type_ @ "t") | Some(type_ @ "d") => {
+                type_
+            }
+            _ => default,
+        };
+
+        let variant = match type_ {
+            "y" => u8::try_from(value).map(|v| v.to_variant()),
+            "n" => i16::try_from(value).map(|v| v.to_variant()),
+            "q" => u16::try_from(value).map(|v| v.to_variant()),
+            "i" => i32::try_from(value).map(|v| v.to_variant()),
+            "u" => u32::try_from(value).map(|v| v.to_variant()),
+            "x" => i64::try_from(value).map(|v| v.to_variant()),
+            "t" => u64::try_from(value).map(|v| v.to_variant()),
+            _ => Ok((value as f64).to_variant()),
+        }
+        .map_err(|_| {
+            Error(format!(
+                "integer {} out of range for type '{}'",
+                value, type_
+            ))
+        })?;
+
+        finish(self.type_, variant)
+    }
+
+    fn string(&self, value: &str) -> Result<Variant, Error> {
+        unsafe {
+            match guide(self.type_).map(VariantTy::to_str) {
+                Some("o") => {
+                    if from_glib(ffi::g_variant_is_object_path(value.to_glib_none().0)) {
+                        Ok(from_glib_none(ffi::g_variant_new_object_path(
+                            value.to_glib_none().0,
+                        )))
+                    } else {
+                        Err(Error(format!("'{}' is not a valid object path", value)))
+                    }
+                }
+                Some("g") => {
+                    if from_glib(ffi::g_variant_is_signature(value.to_glib_none().0)) {
+                        Ok(from_glib_none(ffi::g_variant_new_signature(
+                            value.to_glib_none().0,
+                        )))
+                    } else {
+                        Err(Error(format!("'{}' is not a valid signature", value)))
+                    }
+                }
+                _ => Ok(value.to_variant()),
+            }
+        }
+    }
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeTuple = SeqSerializer<'a>;
+    type SerializeTupleStruct = SeqSerializer<'a>;
+    type SerializeTupleVariant = SeqSerializer<'a>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = StructSerializer<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<Variant, Error> {
+        finish(self.type_, v.to_variant())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Variant, Error> {
+        self.integer(v.into(), "n")
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Variant, Error> {
+        self.integer(v.into(), "n")
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Variant, Error> {
+        self.integer(v.into(), "i")
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Variant, Error> {
+        self.integer(v.into(), "x")
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Variant, Error> {
+        self.integer(v.into(), "y")
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Variant, Error> {
+        self.integer(v.into(), "q")
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Variant, Error> {
+        self.integer(v.into(), "u")
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Variant, Error> {
+        self.integer(v.into(), "t")
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Variant, Error> {
+        finish(self.type_, f64::from(v).to_variant())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Variant, Error> {
+        finish(self.type_, v.to_variant())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Variant, Error> {
+        finish(self.type_, v.to_string().to_variant())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Variant, Error> {
+        let variant = self.string(v)?;
+        finish(self.type_, variant)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variant, Error> {
+        finish(self.type_, Variant::from_bytes::<Vec<u8>>(&Bytes::from(v)))
+    }
+
+    fn serialize_none(self) -> Result<Variant, Error> {
+        let element_type = maybe_element(self.type_)
+            .filter(|type_| type_.is_definite())
+            .unwrap_or_else(variant_ty);
+        finish(self.type_, new_maybe(element_type, None))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Variant, Error> {
+        let child = value.serialize(Serializer {
+            type_: maybe_element(self.type_),
+        })?;
+        finish(self.type_, new_maybe(child.type_(), Some(&child)))
+    }
+
+    fn serialize_unit(self) -> Result<Variant, Error> {
+        finish(self.type_, Variant::tuple(&[]))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variant, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Variant, Error> {
+        match guide(self.type_).map(VariantTy::to_str) {
+            Some("u") => finish(self.type_, variant_index.to_variant()),
+            _ => finish(self.type_, variant.to_variant()),
+        }
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error> {
+        let content = value.serialize(Serializer {
+            type_: Some(enum_content_type(self.type_)),
+        })?;
+        EnumWrap {
+            name: variant,
+            type_: self.type_,
+        }
+        .finish(content)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSerializer<'a>, Error> {
+        Ok(SeqSerializer::new(self.type_, false, None))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<SeqSerializer<'a>, Error> {
+        Ok(SeqSerializer::new(self.type_, true, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<SeqSerializer<'a>, Error> {
+        Ok(SeqSerializer::new(self.type_, true, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SeqSerializer<'a>, Error> {
+        let wrap = EnumWrap {
+            name: variant,
+            type_: self.type_,
+        };
+        Ok(SeqSerializer::new(
+            Some(enum_content_type(self.type_)),
+            true,
+            Some(wrap),
+        ))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer<'a>, Error> {
+        Ok(MapSerializer::new(self.type_, None))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer<'a>, Error> {
+        Ok(StructSerializer::new(self.type_, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer<'a>, Error> {
+        let wrap = EnumWrap {
+            name: variant,
+            type_: self.type_,
+        };
+        Ok(StructSerializer::new(
+            Some(enum_content_type(self.type_)),
+            Some(wrap),
+        ))
+    }
+}
+
+#[doc(hidden)]
+pub struct SeqSerializer<'a> {
+    type_: Option<&'a VariantTy>,
+    tuple: bool,
+    item_types: Vec<&'a VariantTy>,
+    element_type: Option<&'a VariantTy>,
+    children: Vec<Variant>,
+    wrap: Option<EnumWrap<'a>>,
+}
+
+impl<'a> SeqSerializer<'a> {
+    fn new(type_: Option<&'a VariantTy>, tuple: bool, wrap: Option<EnumWrap<'a>>) -> Self {
+        let item_types = tuple_items(type_);
+        let element_type = array_element(type_);
+        // The expected type decides between arrays and tuples, e.g. for `[T; N]`
+        let tuple = if item_types.is_some() {
+            true
+        } else if element_type.is_some() {
+            false
+        } else {
+            tuple
+        };
+
+        Self {
+            type_,
+            tuple,
+            item_types: item_types.unwrap_or_default(),
+            element_type,
+            children: Vec::new(),
+            wrap,
+        }
+    }
+
+    fn serialize_child<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let type_ = if self.tuple {
+            self.item_types.get(self.children.len()).copied()
+        } else {
+            self.element_type
+        };
+        self.children.push(value.serialize(Serializer { type_ })?);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Variant, Error> {
+        let value = if self.tuple {
+            Variant::tuple(&self.children)
+        } else {
+            infer_array(&self.children, self.element_type)
+        };
+        let value = finish(self.type_, value)?;
+
+        match self.wrap {
+            Some(wrap) => wrap.finish(value),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<'a> SerializeSeq for SeqSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.serialize_child(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+impl<'a> SerializeTuple for SeqSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.serialize_child(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for SeqSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.serialize_child(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for SeqSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.serialize_child(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+#[doc(hidden)]
+pub struct MapSerializer<'a> {
+    type_: Option<&'a VariantTy>,
+    key_type: Option<&'a VariantTy>,
+    value_type: Option<&'a VariantTy>,
+    keys: Vec<Variant>,
+    values: Vec<Variant>,
+    wrap: Option<EnumWrap<'a>>,
+}
+
+impl<'a> MapSerializer<'a> {
+    fn new(type_: Option<&'a VariantTy>, wrap: Option<EnumWrap<'a>>) -> Self {
+        let (key_type, value_type) = match dict_entry_types(type_) {
+            Some((key_type, value_type)) => (Some(key_type), Some(value_type)),
+            None => (None, None),
+        };
+
+        Self {
+            type_,
+            key_type,
+            value_type,
+            keys: Vec::new(),
+            values: Vec::new(),
+            wrap,
+        }
+    }
+
+    fn finish(self) -> Result<Variant, Error> {
+        let key_type = match element_type(&self.keys, self.key_type) {
+            Some(key_type) if key_type.is_basic() => key_type.to_owned(),
+            Some(key_type) => {
+                return Err(Error(format!(
+                    "dictionary keys must be of a basic type, got '{}'",
+                    key_type
+                )))
+            }
+            None if self.keys.is_empty() => VariantType::new("s").unwrap(),
+            None => return Err(Error("dictionary keys are of different types".into())),
+        };
+
+        let (value_type, values) = match element_type(&self.values, self.value_type) {
+            Some(value_type) => (value_type.to_owned(), self.values.clone()),
+            None => (
+                variant_ty().to_owned(),
+                self.values.iter().map(Variant::variant).collect(),
+            ),
+        };
+
+        let entries = self
+            .keys
+            .iter()
+            .zip(values.iter())
+            .map(|(key, value)| new_dict_entry(key, value))
+            .collect::<Vec<_>>();
+        let entry_type = VariantType::new(&format!("{{{}{}}}", key_type, value_type))
+            .map_err(|e| Error(e.to_string()))?;
+        let value = finish(self.type_, new_array(&entry_type, &entries))?;
+
+        match self.wrap {
+            Some(wrap) => wrap.finish(value),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<'a> SerializeMap for MapSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.keys.push(key.serialize(Serializer {
+            type_: self.key_type,
+        })?);
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.values.push(value.serialize(Serializer {
+            type_: self.value_type,
+        })?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+#[doc(hidden)]
+pub enum StructSerializer<'a> {
+    Tuple(SeqSerializer<'a>),
+    Dict(MapSerializer<'a>),
+}
+
+impl<'a> StructSerializer<'a> {
+    fn new(type_: Option<&'a VariantTy>, wrap: Option<EnumWrap<'a>>) -> Self {
+        match dict_entry_types(type_) {
+            Some((key_type, _)) if key_type.to_str() == "s" => {
+                StructSerializer::Dict(MapSerializer::new(type_, wrap))
+            }
+            _ => StructSerializer::Tuple(SeqSerializer::new(type_, true, wrap)),
+        }
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        match self {
+            StructSerializer::Tuple(seq) => seq.serialize_child(value),
+            StructSerializer::Dict(map) => {
+                map.keys.push(key.to_variant());
+                map.serialize_value(value)
+            }
+        }
+    }
+
+    fn finish(self) -> Result<Variant, Error> {
+        match self {
+            StructSerializer::Tuple(seq) => seq.finish(),
+            StructSerializer::Dict(map) => map.finish(),
+        }
+    }
+}
+
+impl<'a> ser::SerializeStruct for StructSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for StructSerializer<'a> {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.finish()
+    }
+}
+
+struct Deserializer {
+    variant: Variant,
+}
+
+impl Deserializer {
+    // Removes any number of `v` boxes around the value
+    fn unboxed(self) -> Self {
+        let mut variant = self.variant;
+        while let Some(inner) = variant.get_variant() {
+            variant = inner;
+        }
+
+        Deserializer { variant }
+    }
+
+    fn maybe(&self) -> Option<Variant> {
+        unsafe { from_glib_full(ffi::g_variant_get_maybe(self.variant.to_glib_none().0)) }
+    }
+}
+
+macro_rules! deserialize_number {
+    ($variant:expr, $visitor:expr, $($type_str:expr => $ty:ty, $visit:ident;)*) => {
+        match $variant.type_().to_str() {
+            $(
+                $type_str => return $visitor.$visit($variant.get::<$ty>().unwrap()),
+            )*
+            _ => (),
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let this = self.unboxed();
+        let variant = &this.variant;
+
+        deserialize_number!(variant, visitor,
+            "b" => bool, visit_bool;
+            "y" => u8, visit_u8;
+            "n" => i16, visit_i16;
+            "q" => u16, visit_u16;
+            "i" => i32, visit_i32;
+            "u" => u32, visit_u32;
+            "x" => i64, visit_i64;
+            "t" => u64, visit_u64;
+            "d" => f64, visit_f64;
+        );
+
+        let type_ = variant.type_().to_str();
+        match type_.as_bytes()[0] {
+            b'h' => {
+                visitor.visit_i32(unsafe { ffi::g_variant_get_handle(variant.to_glib_none().0) })
+            }
+            b's' | b'o' | b'g' => visitor.visit_str(variant.get_str().unwrap()),
+            b'm' => match this.maybe() {
+                Some(variant) => visitor.visit_some(Deserializer { variant }),
+                None => visitor.visit_none(),
+            },
+            b'(' if type_ == "()" => visitor.visit_unit(),
+            b'a' if type_.starts_with("a{") => visitor.visit_map(MapAccess {
+                iter: variant.iter(),
+                value: None,
+            }),
+            b'a' | b'(' | b'{' => visitor.visit_seq(SeqAccess {
+                iter: variant.iter(),
+            }),
+            _ => Err(Error(format!("unsupported variant type '{}'", type_))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let this = self.unboxed();
+        if this.variant.type_().is_maybe() {
+            match this.maybe() {
+                Some(variant) => visitor.visit_some(Deserializer { variant }),
+                None => visitor.visit_none(),
+            }
+        } else {
+            visitor.visit_some(this)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let this = self.unboxed();
+        if this.variant.type_() == "ay" {
+            visitor.visit_bytes(&this.variant.get_data_as_bytes())
+        } else {
+            this.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let variant = self.unboxed().variant;
+        let type_ = variant.type_();
+
+        if type_ == "s" || type_ == "u" {
+            visitor.visit_enum(EnumAccess {
+                tag: variant,
+                content: None,
+            })
+        } else if type_.to_str().starts_with("(s") && variant.n_children() == 2 {
+            visitor.visit_enum(EnumAccess {
+                tag: variant.get_child_value(0),
+                content: Some(variant.get_child_value(1)),
+            })
+        } else {
+            Err(Error(format!(
+                "expected an enum variant of type 's', 'u' or '(sv)', got '{}'",
+                type_
+            )))
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess {
+    iter: VariantIter,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.iter
+            .next()
+            .map(|variant| seed.deserialize(Deserializer { variant }))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapAccess {
+    iter: VariantIter,
+    value: Option<Variant>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some(entry) => {
+                self.value = Some(entry.get_child_value(1));
+                seed.deserialize(Deserializer {
+                    variant: entry.get_child_value(0),
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(variant) => seed.deserialize(Deserializer { variant }),
+            None => Err(Error("dictionary value requested before its key".into())),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumAccess {
+    tag: Variant,
+    content: Option<Variant>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess), Error> {
+        let value = seed.deserialize(Deserializer { variant: self.tag })?;
+
+        Ok((
+            value,
+            VariantAccess {
+                content: self.content,
+            },
+        ))
+    }
+}
+
+struct VariantAccess {
+    content: Option<Variant>,
+}
+
+impl VariantAccess {
+    fn content(self) -> Result<Deserializer, Error> {
+        match self.content {
+            Some(variant) => Ok(Deserializer { variant }),
+            None => Err(Error("expected an enum variant with content".into())),
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.content {
+            Some(variant) => Deserialize::deserialize(Deserializer { variant }),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.content()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.content()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.content()?, visitor)
+    }
+}
+
+impl Serialize for Variant {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let type_ = self.type_().to_str();
+        match type_ {
+            "b" => serializer.serialize_bool(self.get().unwrap()),
+            "y" => serializer.serialize_u8(self.get().unwrap()),
+            "n" => serializer.serialize_i16(self.get().unwrap()),
+            "q" => serializer.serialize_u16(self.get().unwrap()),
+            "i" => serializer.serialize_i32(self.get().unwrap()),
+            "u" => serializer.serialize_u32(self.get().unwrap()),
+            "x" => serializer.serialize_i64(self.get().unwrap()),
+            "t" => serializer.serialize_u64(self.get().unwrap()),
+            "d" => serializer.serialize_f64(self.get().unwrap()),
+            "h" => serializer
+                .serialize_i32(unsafe { ffi::g_variant_get_handle(self.to_glib_none().0) }),
+            "s" | "o" | "g" => serializer.serialize_str(self.get_str().unwrap()),
+            "v" => self.get_variant().unwrap().serialize(serializer),
+            "()" => serializer.serialize_unit(),
+            _ if self.type_().is_maybe() => {
+                let child: Option<Variant> =
+                    unsafe { from_glib_full(ffi::g_variant_get_maybe(self.to_glib_none().0)) };
+                match child {
+                    Some(child) => serializer.serialize_some(&child),
+                    None => serializer.serialize_none(),
+                }
+            }
+            _ if type_.starts_with("a{") => {
+                let mut map = serializer.serialize_map(Some(self.n_children()))?;
+                for entry in self.iter() {
+                    map.serialize_entry(&entry.get_child_value(0), &entry.get_child_value(1))?;
+                }
+                map.end()
+            }
+            _ if self.type_().is_array() => {
+                let mut seq = serializer.serialize_seq(Some(self.n_children()))?;
+                for child in self.iter() {
+                    seq.serialize_element(&child)?;
+                }
+                seq.end()
+            }
+            _ => {
+                let mut tuple = serializer.serialize_tuple(self.n_children())?;
+                for child in self.iter() {
+                    tuple.serialize_element(&child)?;
+                }
+                tuple.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(VariantVisitor)
+    }
+}
+
+struct VariantVisitor;
+
+impl<'de> Visitor<'de> for VariantVisitor {
+    type Value = Variant;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value representable as a GVariant")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_i16<E: de::Error>(self, v: i16) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_i32<E: de::Error>(self, v: i32) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_u8<E: de::Error>(self, v: u8) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_u16<E: de::Error>(self, v: u16) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_u32<E: de::Error>(self, v: u32) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Variant, E> {
+        Ok(v.to_variant())
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Variant, E> {
+        Ok(Variant::from_bytes::<Vec<u8>>(&Bytes::from(v)))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Variant, E> {
+        Ok(new_maybe(variant_ty(), None))
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Variant, D::Error> {
+        let child = Variant::deserialize(deserializer)?;
+        Ok(new_maybe(child.type_(), Some(&child)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Variant, E> {
+        Ok(Variant::tuple(&[]))
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Variant, D::Error> {
+        Variant::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Variant, A::Error> {
+        let mut children = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(child) = seq.next_element::<Variant>()? {
+            children.push(child);
+        }
+
+        Ok(infer_array(&children, None))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Variant, A::Error> {
+        let mut serializer = MapSerializer::new(None, None);
+        while let Some((key, value)) = map.next_entry::<Variant, Variant>()? {
+            serializer.keys.push(key);
+            serializer.values.push(value);
+        }
+
+        serializer.finish().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for VariantTy {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_str())
+    }
+}
+
+impl Serialize for VariantType {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for VariantType {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let type_string = String::deserialize(deserializer)?;
+        VariantType::new(&type_string).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        name: String,
+        position: Point,
+        tags: Vec<String>,
+        parent: Option<String>,
+        scale: f64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Line(Point, Point),
+        Rect { width: u32, height: u32 },
+    }
+
+    fn ty(type_: &str) -> &VariantTy {
+        VariantTy::new(type_).unwrap()
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(to_variant(&true).unwrap(), true.to_variant());
+        assert_eq!(to_variant(&5u8).unwrap(), 5u8.to_variant());
+        assert_eq!(to_variant(&-5i8).unwrap(), (-5i16).to_variant());
+        assert_eq!(to_variant(&5i64).unwrap(), 5i64.to_variant());
+        assert_eq!(to_variant(&1.5f32).unwrap(), 1.5f64.to_variant());
+        assert_eq!(to_variant("foo").unwrap(), "foo".to_variant());
+        assert_eq!(to_variant(&()).unwrap().type_(), "()");
+
+        assert_eq!(from_variant::<i8>(&(-5i16).to_variant()), Ok(-5));
+        assert_eq!(from_variant::<u64>(&5u32.to_variant()), Ok(5));
+        assert_eq!(from_variant::<char>(&"c".to_variant()), Ok('c'));
+        assert!(from_variant::<u8>(&1000i32.to_variant()).is_err());
+    }
+
+    #[test]
+    fn struct_as_tuple() {
+        let settings = Settings {
+            name: "main".into(),
+            position: Point { x: 1, y: -2 },
+            tags: vec!["a".into(), "b".into()],
+            parent: None,
+            scale: 2.0,
+        };
+
+        let variant = to_variant(&settings).unwrap();
+        assert_eq!(variant.type_(), "(s(ii)asmvd)");
+        assert_eq!(from_variant::<Settings>(&variant).unwrap(), settings);
+
+        let variant = to_variant_with_type(&settings, ty("(s(ii)asmsd)")).unwrap();
+        assert_eq!(variant.type_(), "(s(ii)asmsd)");
+        assert_eq!(from_variant::<Settings>(&variant).unwrap(), settings);
+
+        assert!(to_variant_with_type(&settings, ty("(s(ii)asmsi)")).is_err());
+    }
+
+    #[test]
+    fn struct_as_dict() {
+        let settings = Settings {
+            name: "main".into(),
+            position: Point { x: 1, y: -2 },
+            tags: vec![],
+            parent: Some("root".into()),
+            scale: 2.0,
+        };
+
+        let variant = to_variant_with_type(&settings, ty("a{sv}")).unwrap();
+        assert_eq!(variant.type_(), "a{sv}");
+        assert_eq!(variant.n_children(), 5);
+        let entry = variant.get_child_value(2);
+        assert_eq!(entry.get_child_value(0).get_str(), Some("tags"));
+        assert_eq!(
+            entry.get_child_value(1).get_variant().unwrap().type_(),
+            "av"
+        );
+        assert_eq!(from_variant::<Settings>(&variant).unwrap(), settings);
+    }
+
+    #[test]
+    fn integer_conversion() {
+        let variant = to_variant_with_type(&(1u8, 2u64), ty("(ix)")).unwrap();
+        assert_eq!(variant.type_(), "(ix)");
+        assert!(to_variant_with_type(&300u32, ty("y")).is_err());
+        assert_eq!(from_variant::<(i64, u8)>(&variant), Ok((1, 2)));
+    }
+
+    #[test]
+    fn strings() {
+        let variant = to_variant_with_type(&"/org/gtk_rs", ty("o")).unwrap();
+        assert_eq!(variant.type_(), "o");
+        assert!(to_variant_with_type(&"not a path", ty("o")).is_err());
+        assert_eq!(
+            from_variant::<String>(&variant).unwrap(),
+            String::from("/org/gtk_rs")
+        );
+    }
+
+    #[test]
+    fn maps_and_arrays() {
+        let mut map = HashMap::new();
+        map.insert("one".to_string(), 1u32);
+        map.insert("two".to_string(), 2u32);
+
+        let variant = to_variant(&map).unwrap();
+        assert_eq!(variant.type_(), "a{su}");
+        assert_eq!(from_variant::<HashMap<String, u32>>(&variant).unwrap(), map);
+
+        let variant = to_variant_with_type(&map, ty("a{sv}")).unwrap();
+        assert_eq!(variant.type_(), "a{sv}");
+        assert_eq!(from_variant::<HashMap<String, u32>>(&variant).unwrap(), map);
+
+        let empty: Vec<u32> = Vec::new();
+        assert_eq!(to_variant(&empty).unwrap().type_(), "av");
+        assert_eq!(
+            to_variant_with_type(&empty, ty("au")).unwrap().type_(),
+            "au"
+        );
+        assert_eq!(
+            from_variant::<Vec<u32>>(&to_variant(&empty).unwrap()),
+            Ok(vec![])
+        );
+
+        let bytes = serde_bytes::Bytes::new(b"abc");
+        let variant = to_variant(&bytes).unwrap();
+        assert_eq!(variant.type_(), "ay");
+        assert_eq!(
+            from_variant::<serde_bytes::ByteBuf>(&variant)
+                .unwrap()
+                .as_slice(),
+            b"abc"
+        );
+        assert_eq!(from_variant::<Vec<u8>>(&variant).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn enums() {
+        let variant = to_variant(&Shape::Empty).unwrap();
+        assert_eq!(variant.get_str(), Some("Empty"));
+        assert_eq!(from_variant::<Shape>(&variant), Ok(Shape::Empty));
+
+        let variant = to_variant_with_type(&Shape::Empty, ty("u")).unwrap();
+        assert_eq!(variant.get::<u32>(), Some(0));
+        assert_eq!(from_variant::<Shape>(&variant), Ok(Shape::Empty));
+
+        for shape in vec![
+            Shape::Circle(3),
+            Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 }),
+            Shape::Rect {
+                width: 1,
+                height: 2,
+            },
+        ] {
+            let variant = to_variant(&shape).unwrap();
+            assert_eq!(variant.type_(), "(sv)");
+            assert_eq!(from_variant::<Shape>(&variant), Ok(shape));
+        }
+
+        let variant = to_variant_with_type(&Shape::Circle(3), ty("(su)")).unwrap();
+        assert_eq!(variant.type_(), "(su)");
+        assert_eq!(from_variant::<Shape>(&variant), Ok(Shape::Circle(3)));
+    }
+
+    #[test]
+    fn variant_roundtrip() {
+        let variant = to_variant_with_type(
+            &Settings {
+                name: "main".into(),
+                position: Point { x: 1, y: -2 },
+                tags: vec!["a".into()],
+                parent: Some("root".into()),
+                scale: 2.0,
+            },
+            ty("(s(ii)asmsd)"),
+        )
+        .unwrap();
+
+        assert_eq!(to_variant(&variant).unwrap(), variant);
+        assert_eq!(from_variant::<Variant>(&variant).unwrap(), variant);
+
+        let type_ = VariantType::new("a{sv}").unwrap();
+        assert_eq!(to_variant(&type_).unwrap().get_str(), Some("a{sv}"));
+        assert_eq!(
+            from_variant::<VariantType>(&"a{sv}".to_variant()).unwrap(),
+            type_
+        );
+        assert!(from_variant::<VariantType>(&"a{".to_variant()).is_err());
+    }
+}
@@ -180,8 +180,164 @@ impl VariantTy {
     pub fn to_str(&self) -> &str {
         &self.inner
     }
+
+    /// Check if this variant type is a definite type.
+    #[doc(alias = "g_variant_type_is_definite")]
+    pub fn is_definite(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_definite(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is a container type.
+    #[doc(alias = "g_variant_type_is_container")]
+    pub fn is_container(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_container(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is a basic type.
+    #[doc(alias = "g_variant_type_is_basic")]
+    pub fn is_basic(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_basic(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is an array type.
+    #[doc(alias = "g_variant_type_is_array")]
+    pub fn is_array(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_array(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is a maybe type.
+    #[doc(alias = "g_variant_type_is_maybe")]
+    pub fn is_maybe(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_maybe(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is a tuple type.
+    #[doc(alias = "g_variant_type_is_tuple")]
+    pub fn is_tuple(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_tuple(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is a dict entry type.
+    #[doc(alias = "g_variant_type_is_dict_entry")]
+    pub fn is_dict_entry(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_dict_entry(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is the variant type `v`.
+    #[doc(alias = "g_variant_type_is_variant")]
+    pub fn is_variant(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_type_is_variant(self.to_glib_none().0)) }
+    }
+
+    /// Check if this variant type is a subtype of `supertype`.
+    #[doc(alias = "g_variant_type_is_subtype_of")]
+    pub fn is_subtype_of(&self, supertype: &Self) -> bool {
+        unsafe {
+            from_glib(ffi::g_variant_type_is_subtype_of(
+                self.to_glib_none().0,
+                supertype.to_glib_none().0,
+            ))
+        }
+    }
+
+    /// Return the element type of this variant type.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if not called with an array or maybe type.
+    #[doc(alias = "g_variant_type_element")]
+    pub fn element(&self) -> &VariantTy {
+        assert!(self.is_array() || self.is_maybe());
+
+        unsafe { VariantTy::from_ptr(ffi::g_variant_type_element(self.to_glib_none().0)) }
+    }
+
+    /// Iterate over the types of this tuple or dict entry type.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if not called with a definite tuple or dict entry type.
+    pub fn tuple_types(&self) -> VariantTyIterator {
+        VariantTyIterator::new(self).expect("VariantTy does not represent a tuple")
+    }
+
+    /// Return the key type of this dict entry type.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if not called with a dict entry type.
+    #[doc(alias = "g_variant_type_key")]
+    pub fn key(&self) -> &VariantTy {
+        assert!(self.is_dict_entry());
+
+        unsafe { VariantTy::from_ptr(ffi::g_variant_type_key(self.to_glib_none().0)) }
+    }
+
+    /// Return the value type of this dict entry type.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if not called with a dict entry type.
+    #[doc(alias = "g_variant_type_value")]
+    pub fn value(&self) -> &VariantTy {
+        assert!(self.is_dict_entry());
+
+        unsafe { VariantTy::from_ptr(ffi::g_variant_type_value(self.to_glib_none().0)) }
+    }
+}
+
+/// Iterator over the item types of a tuple or dict entry `VariantTy`.
+#[derive(Debug, Copy, Clone)]
+pub struct VariantTyIterator<'a> {
+    elem: Option<&'a VariantTy>,
 }
 
+impl<'a> VariantTyIterator<'a> {
+    /// Creates a new iterator over the types of the specified `VariantTy`.
+    ///
+    /// Returns `Ok` if the type is a definite tuple or dict entry type,
+    /// `Err` otherwise.
+    pub fn new(ty: &'a VariantTy) -> Result<Self, BoolError> {
+        if (ty.is_tuple() && ty.to_str() != "r") || ty.is_dict_entry() {
+            let elem = unsafe {
+                let ptr = ffi::g_variant_type_first(ty.to_glib_none().0);
+                if ptr.is_null() {
+                    None
+                } else {
+                    Some(VariantTy::from_ptr(ptr))
+                }
+            };
+
+            Ok(Self { elem })
+        } else {
+            Err(bool_error!(
+                "Expected a definite tuple or dictionary entry type"
+            ))
+        }
+    }
+}
+
+impl<'a> Iterator for VariantTyIterator<'a> {
+    type Item = &'a VariantTy;
+
+    #[doc(alias = "g_variant_type_next")]
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem = self.elem?;
+        self.elem = unsafe {
+            let ptr = ffi::g_variant_type_next(elem.to_glib_none().0);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(VariantTy::from_ptr(ptr))
+            }
+        };
+
+        Some(elem)
+    }
+}
+
+impl<'a> std::iter::FusedIterator for VariantTyIterator<'a> {}
+
 unsafe impl Sync for VariantTy {}
 
 #[doc(hidden)]
@@ -444,4 +600,29 @@ mod tests {
 
         assert_eq!(VariantTy::static_type(), VariantTy::static_type());
     }
+
+    #[test]
+    fn container_types() {
+        let ty = VariantTy::new("a{s(iv)}").unwrap();
+        assert!(ty.is_array());
+        assert!(ty.is_container());
+        assert!(ty.is_definite());
+
+        let entry = ty.element();
+        assert!(entry.is_dict_entry());
+        assert_eq!(entry.key(), "s");
+        assert!(entry.key().is_basic());
+        assert_eq!(entry.value(), "(iv)");
+
+        let types = entry.value().tuple_types().collect::<Vec<_>>();
+        assert_eq!(types, ["i", "v"]);
+        assert!(types[1].is_variant());
+        assert_eq!(VariantTy::new("()").unwrap().tuple_types().count(), 0);
+
+        assert!(VariantTy::new("mi")
+            .unwrap()
+            .element()
+            .is_subtype_of(VariantTy::new("*").unwrap()));
+        assert!(VariantTyIterator::new(VariantTy::new("r").unwrap()).is_err());
+    }
 }
@@ -0,0 +1,81 @@
+#![cfg(feature = "v2_50")]
+
+use glib::*;
+
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    level: LogLevel,
+    fields: Vec<(String, String)>,
+}
+
+impl Record {
+    fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[test]
+fn check_structured_log() {
+    let records = Arc::new(Mutex::new(Vec::<Record>::new()));
+    log_set_writer_func(clone!(@strong records => move |level, fields| {
+        let fields = fields
+            .iter()
+            .map(|field| {
+                (
+                    field.key().to_string(),
+                    field.value_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+        records.lock().unwrap().push(Record { level, fields });
+        LogWriterOutput::Handled
+    }));
+
+    log_structured!(
+        "domain",
+        LogLevel::Message,
+        {
+            "MESSAGE" => format!("{}% done", 50),
+            "MY_FIELD" => 42,
+        }
+    );
+
+    {
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.level, LogLevel::Message);
+        assert_eq!(record.field("MESSAGE"), Some("50% done"));
+        assert_eq!(record.field("MY_FIELD"), Some("42"));
+        assert_eq!(record.field("GLIB_DOMAIN"), Some("domain"));
+        assert_eq!(record.field("PRIORITY"), Some("5"));
+        assert_eq!(record.field("CODE_FILE"), Some(file!()));
+        assert_eq!(record.field("CODE_MODULE"), Some(module_path!()));
+        assert!(record.field("CODE_LINE").unwrap().parse::<u32>().is_ok());
+    }
+
+    let fields = [
+        LogField::new(
+            std::ffi::CStr::from_bytes_with_nul(b"MESSAGE\0").unwrap(),
+            b"formatted",
+        ),
+        LogField::new(
+            std::ffi::CStr::from_bytes_with_nul(b"GLIB_DOMAIN\0").unwrap(),
+            b"domain",
+        ),
+    ];
+    let formatted = log_writer_format_fields(LogLevel::Warning, &fields, false);
+    assert!(formatted.contains("domain"));
+    assert!(formatted.contains("formatted"));
+
+    log_structured_array(LogLevel::Info, &fields);
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].level, LogLevel::Info);
+    assert_eq!(records[1].field("MESSAGE"), Some("formatted"));
+}
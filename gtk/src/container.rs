@@ -0,0 +1,110 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::Container;
+use crate::Widget;
+use glib::object::IsA;
+use glib::translate::*;
+use glib::{ObjectExt, ToValue};
+
+extern "C" {
+    // Not available in gtk-sys, as it takes the class struct as `GObjectClass`
+    fn gtk_container_class_find_child_property(
+        cclass: *mut glib::gobject_ffi::GObjectClass,
+        property_name: *const libc::c_char,
+    ) -> *mut glib::gobject_ffi::GParamSpec;
+}
+
+pub trait ContainerExtManual: 'static {
+    // rustdoc-stripper-ignore-next
+    /// Returns the child property `property_name` of the container class.
+    #[doc(alias = "gtk_container_class_find_child_property")]
+    fn find_child_property(&self, property_name: &str) -> Option<glib::ParamSpec>;
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the child property `property_name` of `child` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has no such child property or `value` has the wrong type.
+    #[doc(alias = "gtk_container_child_set_property")]
+    fn child_set_property<P: IsA<Widget>>(
+        &self,
+        child: &P,
+        property_name: &str,
+        value: &dyn ToValue,
+    );
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the child property `property_name` of `child`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the container has no such child property.
+    #[doc(alias = "gtk_container_child_get_property")]
+    fn child_get_property<P: IsA<Widget>>(&self, child: &P, property_name: &str) -> glib::Value;
+}
+
+impl<O: IsA<Container>> ContainerExtManual for O {
+    fn find_child_property(&self, property_name: &str) -> Option<glib::ParamSpec> {
+        unsafe {
+            let instance = self.as_ref().to_glib_none().0 as *mut glib::gobject_ffi::GTypeInstance;
+            from_glib_none(gtk_container_class_find_child_property(
+                (*instance).g_class as *mut _,
+                property_name.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn child_set_property<P: IsA<Widget>>(
+        &self,
+        child: &P,
+        property_name: &str,
+        value: &dyn ToValue,
+    ) {
+        let pspec = self.find_child_property(property_name).unwrap_or_else(|| {
+            panic!(
+                "Container {} has no child property '{}'",
+                self.as_ref().get_type(),
+                property_name
+            )
+        });
+        let value = value.to_value();
+        assert!(
+            value.type_().is_a(pspec.get_value_type()),
+            "Child property '{}' is of type {}, got {}",
+            property_name,
+            pspec.get_value_type(),
+            value.type_()
+        );
+
+        unsafe {
+            ffi::gtk_container_child_set_property(
+                self.as_ref().to_glib_none().0,
+                child.as_ref().to_glib_none().0,
+                property_name.to_glib_none().0,
+                value.to_glib_none().0,
+            );
+        }
+    }
+
+    fn child_get_property<P: IsA<Widget>>(&self, child: &P, property_name: &str) -> glib::Value {
+        let pspec = self.find_child_property(property_name).unwrap_or_else(|| {
+            panic!(
+                "Container {} has no child property '{}'",
+                self.as_ref().get_type(),
+                property_name
+            )
+        });
+        let mut value = glib::Value::from_type(pspec.get_value_type());
+
+        unsafe {
+            ffi::gtk_container_child_get_property(
+                self.as_ref().to_glib_none().0,
+                child.as_ref().to_glib_none().0,
+                property_name.to_glib_none().0,
+                value.to_glib_none_mut().0,
+            );
+        }
+        value
+    }
+}
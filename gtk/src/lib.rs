@@ -202,6 +202,7 @@ mod clipboard;
 mod color_button;
 mod color_chooser;
 mod combo_box;
+mod container;
 mod dialog;
 mod drag_context;
 mod entry;
@@ -0,0 +1,562 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, GString, ObjectExt};
+use libc::{c_char, size_t};
+
+use std::any::{Any, TypeId};
+use std::ffi::CStr;
+use std::mem;
+use std::slice;
+use std::str;
+
+use crate::Buildable;
+use crate::Builder;
+
+/// Parser for the content of a custom tag in a [`Builder`](crate::Builder) file, returned from
+/// [`BuildableImpl::custom_tag_start`].
+///
+/// Returning an error aborts building.
+pub trait BuildableParser: Any {
+    fn start_element(
+        &mut self,
+        _element_name: &str,
+        _attributes: &[(&str, &str)],
+    ) -> Result<(), glib::Error> {
+        Ok(())
+    }
+
+    fn end_element(&mut self, _element_name: &str) -> Result<(), glib::Error> {
+        Ok(())
+    }
+
+    fn text(&mut self, _text: &str) -> Result<(), glib::Error> {
+        Ok(())
+    }
+}
+
+impl dyn BuildableParser {
+    pub fn is<T: BuildableParser>(&self) -> bool {
+        Any::type_id(self) == TypeId::of::<T>()
+    }
+
+    pub fn downcast_ref<T: BuildableParser>(&self) -> Option<&T> {
+        if self.is::<T>() {
+            unsafe { Some(&*(self as *const dyn BuildableParser as *const T)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn downcast_mut<T: BuildableParser>(&mut self) -> Option<&mut T> {
+        if self.is::<T>() {
+            unsafe { Some(&mut *(self as *mut dyn BuildableParser as *mut T)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn downcast<T: BuildableParser>(self: Box<Self>) -> Result<Box<T>, Box<Self>> {
+        if self.is::<T>() {
+            unsafe { Ok(Box::from_raw(Box::into_raw(self) as *mut T)) }
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Implementation of the [`Buildable`](crate::Buildable) interface.
+///
+/// Widgets already implement `Buildable`, and all methods chain up to the implementation of
+/// the parent type by default.
+pub trait BuildableImpl: ObjectImpl {
+    /// Adds a `<child>` to the object, with the value of its `type` attribute.
+    fn add_child(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        child: &glib::Object,
+        type_: Option<&str>,
+    ) {
+        self.parent_add_child(buildable, builder, child, type_)
+    }
+
+    fn set_buildable_property(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        name: &str,
+        value: &glib::Value,
+    ) {
+        self.parent_set_buildable_property(buildable, builder, name, value)
+    }
+
+    /// Called for unknown tags inside the object, or inside a `<child>` of it if `child` is
+    /// set.
+    ///
+    /// Returns the parser for the content of the tag, or `None` to pass the tag on to the
+    /// implementation of the parent type.
+    fn custom_tag_start(
+        &self,
+        _buildable: &Self::Type,
+        _builder: &Builder,
+        _child: Option<&glib::Object>,
+        _tagname: &str,
+    ) -> Option<Box<dyn BuildableParser>> {
+        None
+    }
+
+    /// Called at the end of a custom tag with the parser returned by `custom_tag_start`.
+    fn custom_tag_end(
+        &self,
+        _buildable: &Self::Type,
+        _builder: &Builder,
+        _child: Option<&glib::Object>,
+        _tagname: &str,
+        _parser: &mut dyn BuildableParser,
+    ) {
+    }
+
+    /// Called once the whole file is parsed for every custom tag handled by this object.
+    fn custom_finished(
+        &self,
+        _buildable: &Self::Type,
+        _builder: &Builder,
+        _child: Option<&glib::Object>,
+        _tagname: &str,
+        _parser: Box<dyn BuildableParser>,
+    ) {
+    }
+
+    fn parser_finished(&self, buildable: &Self::Type, builder: &Builder) {
+        self.parent_parser_finished(buildable, builder)
+    }
+
+    /// Returns the internal child `childname`, as referenced by `<child internal-child="...">`.
+    fn get_internal_child(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        childname: &str,
+    ) -> Option<glib::Object> {
+        self.parent_get_internal_child(buildable, builder, childname)
+    }
+}
+
+pub trait BuildableImplExt: ObjectSubclass {
+    fn parent_add_child(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        child: &glib::Object,
+        type_: Option<&str>,
+    );
+    fn parent_set_buildable_property(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        name: &str,
+        value: &glib::Value,
+    );
+    fn parent_parser_finished(&self, buildable: &Self::Type, builder: &Builder);
+    fn parent_get_internal_child(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        childname: &str,
+    ) -> Option<glib::Object>;
+}
+
+impl<T: BuildableImpl> BuildableImplExt for T {
+    fn parent_add_child(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        child: &glib::Object,
+        type_: Option<&str>,
+    ) {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data.as_ref().get_parent_interface::<Buildable>()
+                as *const ffi::GtkBuildableIface;
+
+            match (*parent_iface).add_child {
+                Some(func) => func(
+                    buildable.unsafe_cast_ref::<Buildable>().to_glib_none().0,
+                    builder.to_glib_none().0,
+                    child.to_glib_none().0,
+                    type_.to_glib_none().0,
+                ),
+                None => glib::g_critical!(
+                    "Gtk",
+                    "Can't add a child to {}, its parent type doesn't implement add_child",
+                    buildable.get_type()
+                ),
+            }
+        }
+    }
+
+    fn parent_set_buildable_property(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        name: &str,
+        value: &glib::Value,
+    ) {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data.as_ref().get_parent_interface::<Buildable>()
+                as *const ffi::GtkBuildableIface;
+
+            // Without an implementation GTK sets the object property of the same name
+            match (*parent_iface).set_buildable_property {
+                Some(func) => func(
+                    buildable.unsafe_cast_ref::<Buildable>().to_glib_none().0,
+                    builder.to_glib_none().0,
+                    name.to_glib_none().0,
+                    value.to_glib_none().0,
+                ),
+                None => {
+                    if let Err(err) = buildable
+                        .unsafe_cast_ref::<glib::Object>()
+                        .set_property_from_value(name, value)
+                    {
+                        glib::g_warning!(
+                            "Gtk",
+                            "Failed to set buildable property {} of {}: {}",
+                            name,
+                            buildable.get_type(),
+                            err
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn parent_parser_finished(&self, buildable: &Self::Type, builder: &Builder) {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data.as_ref().get_parent_interface::<Buildable>()
+                as *const ffi::GtkBuildableIface;
+
+            if let Some(func) = (*parent_iface).parser_finished {
+                func(
+                    buildable.unsafe_cast_ref::<Buildable>().to_glib_none().0,
+                    builder.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_get_internal_child(
+        &self,
+        buildable: &Self::Type,
+        builder: &Builder,
+        childname: &str,
+    ) -> Option<glib::Object> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data.as_ref().get_parent_interface::<Buildable>()
+                as *const ffi::GtkBuildableIface;
+
+            let func = (*parent_iface).get_internal_child?;
+            from_glib_none(func(
+                buildable.unsafe_cast_ref::<Buildable>().to_glib_none().0,
+                builder.to_glib_none().0,
+                childname.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+unsafe impl<T: BuildableImpl> IsImplementable<T> for Buildable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.add_child = Some(buildable_add_child::<T>);
+        iface.set_buildable_property = Some(buildable_set_buildable_property::<T>);
+        iface.custom_tag_start = Some(buildable_custom_tag_start::<T>);
+        iface.custom_tag_end = Some(buildable_custom_tag_end::<T>);
+        iface.custom_finished = Some(buildable_custom_finished::<T>);
+        iface.parser_finished = Some(buildable_parser_finished::<T>);
+        iface.get_internal_child = Some(buildable_get_internal_child::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe extern "C" fn buildable_add_child<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+    child: *mut glib::gobject_ffi::GObject,
+    type_: *const c_char,
+) {
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+    let type_: Borrowed<Option<GString>> = from_glib_borrow(type_);
+
+    imp.add_child(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow(builder),
+        &from_glib_borrow(child),
+        type_.as_ref().as_ref().map(|s| s.as_str()),
+    )
+}
+
+unsafe extern "C" fn buildable_set_buildable_property<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+    name: *const c_char,
+    value: *const glib::gobject_ffi::GValue,
+) {
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+
+    imp.set_buildable_property(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow(builder),
+        &GString::from_glib_borrow(name),
+        &*(value as *const glib::Value),
+    )
+}
+
+// Data of the custom tags handled by a `BuildableImpl`. GTK passes the data of the custom tags of
+// the parent implementations to the same functions, which is told apart by the magic value and
+// the type that handled the tag.
+#[repr(C)]
+struct ParserData {
+    magic: usize,
+    type_: glib::Type,
+    parser: Box<dyn BuildableParser>,
+}
+
+const PARSER_DATA_MAGIC: usize = 0x6275_696c;
+
+impl ParserData {
+    unsafe fn from_data<'a>(data: glib::ffi::gpointer, type_: glib::Type) -> Option<&'a mut Self> {
+        let parser_data = data as *mut ParserData;
+        if parser_data.is_null()
+            || (*parser_data).magic != PARSER_DATA_MAGIC
+            || (*parser_data).type_ != type_
+        {
+            return None;
+        }
+        Some(&mut *parser_data)
+    }
+}
+
+static MARKUP_PARSER: glib::ffi::GMarkupParser = glib::ffi::GMarkupParser {
+    start_element: Some(parser_start_element),
+    end_element: Some(parser_end_element),
+    text: Some(parser_text),
+    passthrough: None,
+    error: None,
+};
+
+unsafe extern "C" fn buildable_custom_tag_start<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+    child: *mut glib::gobject_ffi::GObject,
+    tagname: *const c_char,
+    parser: *mut glib::ffi::GMarkupParser,
+    data: *mut glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+    let child_wrap: Borrowed<Option<glib::Object>> = from_glib_borrow(child);
+
+    match imp.custom_tag_start(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow(builder),
+        child_wrap.as_ref().as_ref(),
+        &GString::from_glib_borrow(tagname),
+    ) {
+        Some(custom_parser) => {
+            let parser_data = Box::new(ParserData {
+                magic: PARSER_DATA_MAGIC,
+                type_: T::get_type(),
+                parser: custom_parser,
+            });
+            *parser = MARKUP_PARSER;
+            *data = Box::into_raw(parser_data) as glib::ffi::gpointer;
+            glib::ffi::GTRUE
+        }
+        None => {
+            let parent_iface = T::type_data().as_ref().get_parent_interface::<Buildable>()
+                as *const ffi::GtkBuildableIface;
+            match (*parent_iface).custom_tag_start {
+                Some(func) => func(buildable, builder, child, tagname, parser, data),
+                None => glib::ffi::GFALSE,
+            }
+        }
+    }
+}
+
+// GTK passes the data pointer itself here, not a pointer to it
+unsafe extern "C" fn buildable_custom_tag_end<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+    child: *mut glib::gobject_ffi::GObject,
+    tagname: *const c_char,
+    data: glib::ffi::gpointer,
+) {
+    let parser_data = match ParserData::from_data(data, T::get_type()) {
+        Some(parser_data) => parser_data,
+        None => {
+            let parent_iface = T::type_data().as_ref().get_parent_interface::<Buildable>()
+                as *const ffi::GtkBuildableIface;
+            if let Some(func) = (*parent_iface).custom_tag_end {
+                func(buildable, builder, child, tagname, data);
+            }
+            return;
+        }
+    };
+
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+    let child: Borrowed<Option<glib::Object>> = from_glib_borrow(child);
+
+    imp.custom_tag_end(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow(builder),
+        child.as_ref().as_ref(),
+        &GString::from_glib_borrow(tagname),
+        &mut *parser_data.parser,
+    )
+}
+
+unsafe extern "C" fn buildable_custom_finished<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+    child: *mut glib::gobject_ffi::GObject,
+    tagname: *const c_char,
+    data: glib::ffi::gpointer,
+) {
+    if ParserData::from_data(data, T::get_type()).is_none() {
+        let parent_iface = T::type_data().as_ref().get_parent_interface::<Buildable>()
+            as *const ffi::GtkBuildableIface;
+        if let Some(func) = (*parent_iface).custom_finished {
+            func(buildable, builder, child, tagname, data);
+        }
+        return;
+    }
+
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+    let child: Borrowed<Option<glib::Object>> = from_glib_borrow(child);
+    let parser_data = Box::from_raw(data as *mut ParserData);
+
+    imp.custom_finished(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow(builder),
+        child.as_ref().as_ref(),
+        &GString::from_glib_borrow(tagname),
+        parser_data.parser,
+    )
+}
+
+unsafe extern "C" fn buildable_parser_finished<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+) {
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+
+    imp.parser_finished(wrap.unsafe_cast_ref(), &from_glib_borrow(builder))
+}
+
+unsafe extern "C" fn buildable_get_internal_child<T: BuildableImpl>(
+    buildable: *mut ffi::GtkBuildable,
+    builder: *mut ffi::GtkBuilder,
+    childname: *const c_char,
+) -> *mut glib::gobject_ffi::GObject {
+    let instance = &*(buildable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Buildable> = from_glib_borrow(buildable);
+
+    let ret = imp.get_internal_child(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow(builder),
+        &GString::from_glib_borrow(childname),
+    );
+
+    // Transfer none, the object is kept alive by the buildable
+    ret.to_glib_none().0
+}
+
+unsafe fn set_error(err: *mut *mut glib::ffi::GError, e: glib::Error) {
+    if !err.is_null() {
+        let mut e = mem::ManuallyDrop::new(e);
+        *err = e.to_glib_none_mut().0;
+    }
+}
+
+unsafe extern "C" fn parser_start_element(
+    _context: *mut glib::ffi::GMarkupParseContext,
+    element_name: *const c_char,
+    attribute_names: *mut *const c_char,
+    attribute_values: *mut *const c_char,
+    user_data: glib::ffi::gpointer,
+    err: *mut *mut glib::ffi::GError,
+) {
+    let custom_parser = &mut *(*(user_data as *mut ParserData)).parser;
+
+    let mut attributes = Vec::new();
+    if !attribute_names.is_null() {
+        let mut i = 0;
+        while !(*attribute_names.add(i)).is_null() {
+            attributes.push((
+                CStr::from_ptr(*attribute_names.add(i)).to_str().unwrap(),
+                CStr::from_ptr(*attribute_values.add(i)).to_str().unwrap(),
+            ));
+            i += 1;
+        }
+    }
+
+    if let Err(e) =
+        custom_parser.start_element(CStr::from_ptr(element_name).to_str().unwrap(), &attributes)
+    {
+        set_error(err, e);
+    }
+}
+
+unsafe extern "C" fn parser_end_element(
+    _context: *mut glib::ffi::GMarkupParseContext,
+    element_name: *const c_char,
+    user_data: glib::ffi::gpointer,
+    err: *mut *mut glib::ffi::GError,
+) {
+    let custom_parser = &mut *(*(user_data as *mut ParserData)).parser;
+
+    if let Err(e) = custom_parser.end_element(CStr::from_ptr(element_name).to_str().unwrap()) {
+        set_error(err, e);
+    }
+}
+
+unsafe extern "C" fn parser_text(
+    _context: *mut glib::ffi::GMarkupParseContext,
+    text: *const c_char,
+    text_len: size_t,
+    user_data: glib::ffi::gpointer,
+    err: *mut *mut glib::ffi::GError,
+) {
+    let custom_parser = &mut *(*(user_data as *mut ParserData)).parser;
+
+    let text = if text.is_null() {
+        ""
+    } else {
+        str::from_utf8(slice::from_raw_parts(text as *const u8, text_len)).unwrap()
+    };
+
+    if let Err(e) = custom_parser.text(text) {
+        set_error(err, e);
+    }
+}
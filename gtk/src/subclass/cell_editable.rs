@@ -0,0 +1,106 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, ToValue};
+
+use super::interface_properties::override_interface_properties;
+use super::widget::WidgetImpl;
+use crate::CellEditable;
+
+/// Implementation of the [`CellEditable`](crate::CellEditable) interface, for widgets returned
+/// from [`CellRendererImpl::start_editing`](super::cell_renderer::CellRendererImpl::start_editing).
+pub trait CellEditableImpl: WidgetImpl {
+    /// Called when editing starts, with the event that started it, if any.
+    ///
+    /// The widget has to emit the `editing-done` and `remove-widget` signals once editing is
+    /// finished.
+    fn start_editing(&self, cell_editable: &Self::Type, event: Option<&gdk::Event>);
+
+    /// Default handler of the `editing-done` signal.
+    fn editing_done(&self, _cell_editable: &Self::Type) {}
+
+    /// Default handler of the `remove-widget` signal.
+    fn remove_widget(&self, _cell_editable: &Self::Type) {}
+
+    fn get_editing_canceled(&self, cell_editable: &Self::Type) -> bool;
+
+    fn set_editing_canceled(&self, cell_editable: &Self::Type, editing_canceled: bool);
+}
+
+static PROPERTIES: [&str; 1] = ["editing-canceled"];
+
+unsafe impl<T: CellEditableImpl> IsImplementable<T> for CellEditable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.start_editing = Some(cell_editable_start_editing::<T>);
+        iface.editing_done = Some(cell_editable_editing_done::<T>);
+        iface.remove_widget = Some(cell_editable_remove_widget::<T>);
+
+        unsafe {
+            override_interface_properties::<T, CellEditable>(
+                &iface.g_iface,
+                &PROPERTIES,
+                cell_editable_set_property::<T>,
+                cell_editable_get_property::<T>,
+            );
+        }
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+fn cell_editable_set_property<T: CellEditableImpl>(
+    imp: &T,
+    cell_editable: &T::Type,
+    _index: usize,
+    value: &glib::Value,
+) {
+    imp.set_editing_canceled(
+        cell_editable,
+        value
+            .get_some()
+            .expect("type conformity checked by `Object::set_property`"),
+    );
+}
+
+fn cell_editable_get_property<T: CellEditableImpl>(
+    imp: &T,
+    cell_editable: &T::Type,
+    _index: usize,
+) -> glib::Value {
+    imp.get_editing_canceled(cell_editable).to_value()
+}
+
+unsafe extern "C" fn cell_editable_start_editing<T: CellEditableImpl>(
+    cell_editable: *mut ffi::GtkCellEditable,
+    evtptr: *mut gdk::ffi::GdkEvent,
+) {
+    let instance = &*(cell_editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<CellEditable> = from_glib_borrow(cell_editable);
+    let evt: Borrowed<Option<gdk::Event>> = from_glib_borrow(evtptr);
+
+    imp.start_editing(wrap.unsafe_cast_ref(), evt.as_ref().as_ref());
+}
+
+unsafe extern "C" fn cell_editable_editing_done<T: CellEditableImpl>(
+    cell_editable: *mut ffi::GtkCellEditable,
+) {
+    let instance = &*(cell_editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<CellEditable> = from_glib_borrow(cell_editable);
+
+    imp.editing_done(wrap.unsafe_cast_ref());
+}
+
+unsafe extern "C" fn cell_editable_remove_widget<T: CellEditableImpl>(
+    cell_editable: *mut ffi::GtkCellEditable,
+) {
+    let instance = &*(cell_editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<CellEditable> = from_glib_borrow(cell_editable);
+
+    imp.remove_widget(wrap.unsafe_cast_ref());
+}
@@ -0,0 +1,87 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use libc::c_char;
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, GString};
+
+use super::bin::BinImpl;
+use crate::Bin;
+use crate::ComboBox;
+
+pub trait ComboBoxImpl: ComboBoxImplExt + BinImpl {
+    fn changed(&self, combo_box: &Self::Type) {
+        self.parent_changed(combo_box)
+    }
+
+    /// Returns the text shown in the entry of the combo box for the row at `path`.
+    fn format_entry_text(&self, combo_box: &Self::Type, path: &str) -> GString {
+        self.parent_format_entry_text(combo_box, path)
+    }
+}
+
+pub trait ComboBoxImplExt: ObjectSubclass {
+    fn parent_changed(&self, combo_box: &Self::Type);
+    fn parent_format_entry_text(&self, combo_box: &Self::Type, path: &str) -> GString;
+}
+
+impl<T: ComboBoxImpl> ComboBoxImplExt for T {
+    fn parent_changed(&self, combo_box: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkComboBoxClass;
+            if let Some(f) = (*parent_class).changed {
+                f(combo_box.unsafe_cast_ref::<ComboBox>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_format_entry_text(&self, combo_box: &Self::Type, path: &str) -> GString {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkComboBoxClass;
+            let f = (*parent_class)
+                .format_entry_text
+                .expect("No parent class impl for \"format_entry_text\"");
+            from_glib_full(f(
+                combo_box.unsafe_cast_ref::<ComboBox>().to_glib_none().0,
+                path.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+unsafe impl<T: ComboBoxImpl> IsSubclassable<T> for ComboBox {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Bin as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.changed = Some(combo_box_changed::<T>);
+        klass.format_entry_text = Some(combo_box_format_entry_text::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Bin as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn combo_box_changed<T: ComboBoxImpl>(ptr: *mut ffi::GtkComboBox) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<ComboBox> = from_glib_borrow(ptr);
+
+    imp.changed(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn combo_box_format_entry_text<T: ComboBoxImpl>(
+    ptr: *mut ffi::GtkComboBox,
+    pathptr: *const c_char,
+) -> *mut c_char {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<ComboBox> = from_glib_borrow(ptr);
+
+    imp.format_entry_text(wrap.unsafe_cast_ref(), &GString::from_glib_borrow(pathptr))
+        .to_glib_full()
+}
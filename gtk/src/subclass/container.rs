@@ -1,5 +1,8 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use std::mem;
+use std::ptr;
+
 use glib::translate::*;
 
 use glib::subclass::prelude::*;
@@ -39,6 +42,43 @@ pub trait ContainerImpl: ContainerImplExt + WidgetImpl {
     fn forall(&self, container: &Self::Type, include_internals: bool, callback: &Callback) {
         self.parent_forall(container, include_internals, callback);
     }
+
+    /// Child properties installed for this type.
+    ///
+    /// These can be set on the children of the container with
+    /// [`ContainerExtManual::child_set_property`](crate::prelude::ContainerExtManual::child_set_property).
+    fn child_properties() -> &'static [glib::ParamSpec] {
+        &[]
+    }
+
+    /// Child property setter.
+    ///
+    /// This is called whenever the child property of this specific subclass with the
+    /// given index is set on `child`. The new value is passed as `glib::Value`.
+    fn set_child_property(
+        &self,
+        _container: &Self::Type,
+        _child: &Widget,
+        _id: usize,
+        _value: &glib::Value,
+        _pspec: &glib::ParamSpec,
+    ) {
+        unimplemented!()
+    }
+
+    /// Child property getter.
+    ///
+    /// This is called whenever the child property value of the specific subclass with the
+    /// given index should be returned for `child`.
+    fn get_child_property(
+        &self,
+        _container: &Self::Type,
+        _child: &Widget,
+        _id: usize,
+        _pspec: &glib::ParamSpec,
+    ) -> glib::Value {
+        unimplemented!()
+    }
 }
 
 pub trait ContainerImplExt: ObjectSubclass {
@@ -155,6 +195,21 @@ unsafe impl<T: ContainerImpl> IsSubclassable<T> for Container {
         klass.child_type = Some(container_child_type::<T>);
         klass.get_path_for_child = Some(container_get_path_for_child::<T>);
         klass.forall = Some(container_forall::<T>);
+
+        // Child property handlers are only called for the child properties installed by this
+        // class, so they don't need to chain up.
+        klass.set_child_property = Some(container_set_child_property::<T>);
+        klass.get_child_property = Some(container_get_child_property::<T>);
+
+        for (i, pspec) in <T as ContainerImpl>::child_properties().iter().enumerate() {
+            unsafe {
+                ffi::gtk_container_class_install_child_property(
+                    klass,
+                    i as u32 + 1,
+                    pspec.to_glib_none().0,
+                );
+            }
+        }
     }
 
     fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
@@ -253,6 +308,52 @@ unsafe extern "C" fn container_forall<T: ObjectSubclass>(
     )
 }
 
+unsafe extern "C" fn container_set_child_property<T: ContainerImpl>(
+    ptr: *mut ffi::GtkContainer,
+    wdgtptr: *mut ffi::GtkWidget,
+    id: u32,
+    value: *mut glib::gobject_ffi::GValue,
+    pspec: *mut glib::gobject_ffi::GParamSpec,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Container> = from_glib_borrow(ptr);
+    let widget: Borrowed<Widget> = from_glib_borrow(wdgtptr);
+
+    imp.set_child_property(
+        wrap.unsafe_cast_ref(),
+        &widget,
+        id as usize,
+        &*(value as *mut glib::Value),
+        &from_glib_borrow(pspec),
+    )
+}
+
+unsafe extern "C" fn container_get_child_property<T: ContainerImpl>(
+    ptr: *mut ffi::GtkContainer,
+    wdgtptr: *mut ffi::GtkWidget,
+    id: u32,
+    value: *mut glib::gobject_ffi::GValue,
+    pspec: *mut glib::gobject_ffi::GParamSpec,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Container> = from_glib_borrow(ptr);
+    let widget: Borrowed<Widget> = from_glib_borrow(wdgtptr);
+
+    let v = imp.get_child_property(
+        wrap.unsafe_cast_ref(),
+        &widget,
+        id as usize,
+        &from_glib_borrow(pspec),
+    );
+
+    // Pass ownership of the returned value to the C GValue, like for object properties
+    glib::gobject_ffi::g_value_unset(value);
+    let v = mem::ManuallyDrop::new(v);
+    ptr::write(value, ptr::read(v.to_glib_none().0));
+}
+
 #[derive(Debug)]
 pub struct Callback {
     callback: ffi::GtkCallback,
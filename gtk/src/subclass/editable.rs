@@ -0,0 +1,223 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, GString, ObjectExt};
+use libc::{c_char, c_int, c_uchar};
+
+use std::ffi::CStr;
+use std::slice;
+use std::str;
+
+use super::widget::WidgetImpl;
+use crate::Editable;
+
+/// Implementation of the [`Editable`](crate::Editable) interface.
+///
+/// Inserting and deleting text through the `Editable` API emits the `insert-text` and
+/// `delete-text` signals, whose default handlers are [`insert_text`] and [`delete_text`].
+/// Implementations have to emit the `changed` signal themselves after modifying the text.
+///
+/// Positions are in characters. An end position of `-1` means the end of the text.
+///
+/// [`insert_text`]: Self::insert_text
+/// [`delete_text`]: Self::delete_text
+pub trait EditableImpl: WidgetImpl {
+    /// Inserts `text` at `position` and moves `position` after the inserted text.
+    fn insert_text(&self, editable: &Self::Type, text: &str, position: &mut i32);
+
+    fn delete_text(&self, editable: &Self::Type, start_pos: i32, end_pos: i32);
+
+    fn changed(&self, _editable: &Self::Type) {}
+
+    fn get_chars(&self, editable: &Self::Type, start_pos: i32, end_pos: i32) -> GString;
+
+    fn set_selection_bounds(&self, editable: &Self::Type, start_pos: i32, end_pos: i32);
+
+    /// Returns the bounds of the selection, or `None` if nothing is selected.
+    fn get_selection_bounds(&self, editable: &Self::Type) -> Option<(i32, i32)>;
+
+    fn set_position(&self, editable: &Self::Type, position: i32);
+
+    fn get_position(&self, editable: &Self::Type) -> i32;
+}
+
+unsafe impl<T: EditableImpl> IsImplementable<T> for Editable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.insert_text = Some(editable_insert_text::<T>);
+        iface.delete_text = Some(editable_delete_text::<T>);
+        iface.changed = Some(editable_changed::<T>);
+        iface.do_insert_text = Some(editable_do_insert_text);
+        iface.do_delete_text = Some(editable_do_delete_text);
+        iface.get_chars = Some(editable_get_chars::<T>);
+        iface.set_selection_bounds = Some(editable_set_selection_bounds::<T>);
+        iface.get_selection_bounds = Some(editable_get_selection_bounds::<T>);
+        iface.set_position = Some(editable_set_position::<T>);
+        iface.get_position = Some(editable_get_position::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+unsafe extern "C" fn editable_insert_text<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+    text: *const c_char,
+    length: c_int,
+    position: *mut c_int,
+) {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    let buf = if length >= 0 {
+        slice::from_raw_parts(text as *const c_uchar, length as usize)
+    } else {
+        CStr::from_ptr(text).to_bytes()
+    };
+    let text = match str::from_utf8(buf) {
+        Ok(text) => text,
+        Err(err) => {
+            glib::g_critical!(
+                "Gtk",
+                "Text inserted into {} is not valid UTF-8: {}",
+                wrap.get_type(),
+                err
+            );
+            return;
+        }
+    };
+
+    imp.insert_text(wrap.unsafe_cast_ref(), text, &mut *position);
+}
+
+unsafe extern "C" fn editable_delete_text<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+    start_pos: c_int,
+    end_pos: c_int,
+) {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    imp.delete_text(wrap.unsafe_cast_ref(), start_pos, end_pos);
+}
+
+unsafe extern "C" fn editable_changed<T: EditableImpl>(editable: *mut ffi::GtkEditable) {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    imp.changed(wrap.unsafe_cast_ref());
+}
+
+// `gtk_editable_insert_text()` and `gtk_editable_delete_text()` call these, which only emit the
+// signals so that the text is changed by their default handlers, the same way as `GtkEntry` does
+unsafe extern "C" fn editable_do_insert_text(
+    editable: *mut ffi::GtkEditable,
+    text: *const c_char,
+    length: c_int,
+    position: *mut c_int,
+) {
+    let obj = editable as *mut glib::gobject_ffi::GObject;
+    glib::gobject_ffi::g_object_ref(obj);
+    glib::gobject_ffi::g_signal_emit_by_name(
+        obj,
+        b"insert-text\0".as_ptr() as *const _,
+        text,
+        length,
+        position,
+    );
+    glib::gobject_ffi::g_object_unref(obj);
+}
+
+unsafe extern "C" fn editable_do_delete_text(
+    editable: *mut ffi::GtkEditable,
+    start_pos: c_int,
+    end_pos: c_int,
+) {
+    let obj = editable as *mut glib::gobject_ffi::GObject;
+    glib::gobject_ffi::g_object_ref(obj);
+    glib::gobject_ffi::g_signal_emit_by_name(
+        obj,
+        b"delete-text\0".as_ptr() as *const _,
+        start_pos,
+        end_pos,
+    );
+    glib::gobject_ffi::g_object_unref(obj);
+}
+
+unsafe extern "C" fn editable_get_chars<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+    start_pos: c_int,
+    end_pos: c_int,
+) -> *mut c_char {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    imp.get_chars(wrap.unsafe_cast_ref(), start_pos, end_pos)
+        .to_glib_full()
+}
+
+unsafe extern "C" fn editable_set_selection_bounds<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+    start_pos: c_int,
+    end_pos: c_int,
+) {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    imp.set_selection_bounds(wrap.unsafe_cast_ref(), start_pos, end_pos);
+}
+
+unsafe extern "C" fn editable_get_selection_bounds<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+    start_pos: *mut c_int,
+    end_pos: *mut c_int,
+) -> glib::ffi::gboolean {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    // Without a selection both bounds are the cursor position
+    let (ret, (start, end)) = match imp.get_selection_bounds(wrap.unsafe_cast_ref()) {
+        Some(bounds) => (true, bounds),
+        None => {
+            let position = imp.get_position(wrap.unsafe_cast_ref());
+            (false, (position, position))
+        }
+    };
+
+    if !start_pos.is_null() {
+        *start_pos = start;
+    }
+    if !end_pos.is_null() {
+        *end_pos = end;
+    }
+
+    ret.to_glib()
+}
+
+unsafe extern "C" fn editable_set_position<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+    position: c_int,
+) {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    imp.set_position(wrap.unsafe_cast_ref(), position);
+}
+
+unsafe extern "C" fn editable_get_position<T: EditableImpl>(
+    editable: *mut ffi::GtkEditable,
+) -> c_int {
+    let instance = &*(editable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Editable> = from_glib_borrow(editable);
+
+    imp.get_position(wrap.unsafe_cast_ref())
+}
@@ -0,0 +1,438 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use libc::{c_char, c_int};
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, GString};
+
+use super::widget::WidgetImpl;
+use crate::DeleteType;
+use crate::Entry;
+use crate::MovementStep;
+use crate::Widget;
+
+pub trait EntryImpl: EntryImplExt + WidgetImpl {
+    fn populate_popup(&self, entry: &Self::Type, popup: &Widget) {
+        self.parent_populate_popup(entry, popup)
+    }
+
+    fn activate(&self, entry: &Self::Type) {
+        self.parent_activate(entry)
+    }
+
+    fn move_cursor(
+        &self,
+        entry: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    ) {
+        self.parent_move_cursor(entry, step, count, extend_selection)
+    }
+
+    fn insert_at_cursor(&self, entry: &Self::Type, text: &str) {
+        self.parent_insert_at_cursor(entry, text)
+    }
+
+    fn delete_from_cursor(&self, entry: &Self::Type, type_: DeleteType, count: i32) {
+        self.parent_delete_from_cursor(entry, type_, count)
+    }
+
+    fn backspace(&self, entry: &Self::Type) {
+        self.parent_backspace(entry)
+    }
+
+    fn cut_clipboard(&self, entry: &Self::Type) {
+        self.parent_cut_clipboard(entry)
+    }
+
+    fn copy_clipboard(&self, entry: &Self::Type) {
+        self.parent_copy_clipboard(entry)
+    }
+
+    fn paste_clipboard(&self, entry: &Self::Type) {
+        self.parent_paste_clipboard(entry)
+    }
+
+    fn toggle_overwrite(&self, entry: &Self::Type) {
+        self.parent_toggle_overwrite(entry)
+    }
+
+    /// Returns the position and size of the text area inside the entry.
+    fn get_text_area_size(&self, entry: &Self::Type) -> (i32, i32, i32, i32) {
+        self.parent_get_text_area_size(entry)
+    }
+
+    /// Returns the position and size of the frame around the entry.
+    fn get_frame_size(&self, entry: &Self::Type) -> (i32, i32, i32, i32) {
+        self.parent_get_frame_size(entry)
+    }
+
+    fn insert_emoji(&self, entry: &Self::Type) {
+        self.parent_insert_emoji(entry)
+    }
+}
+
+pub trait EntryImplExt: ObjectSubclass {
+    fn parent_populate_popup(&self, entry: &Self::Type, popup: &Widget);
+    fn parent_activate(&self, entry: &Self::Type);
+    fn parent_move_cursor(
+        &self,
+        entry: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    );
+    fn parent_insert_at_cursor(&self, entry: &Self::Type, text: &str);
+    fn parent_delete_from_cursor(&self, entry: &Self::Type, type_: DeleteType, count: i32);
+    fn parent_backspace(&self, entry: &Self::Type);
+    fn parent_cut_clipboard(&self, entry: &Self::Type);
+    fn parent_copy_clipboard(&self, entry: &Self::Type);
+    fn parent_paste_clipboard(&self, entry: &Self::Type);
+    fn parent_toggle_overwrite(&self, entry: &Self::Type);
+    fn parent_get_text_area_size(&self, entry: &Self::Type) -> (i32, i32, i32, i32);
+    fn parent_get_frame_size(&self, entry: &Self::Type) -> (i32, i32, i32, i32);
+    fn parent_insert_emoji(&self, entry: &Self::Type);
+}
+
+impl<T: EntryImpl> EntryImplExt for T {
+    fn parent_populate_popup(&self, entry: &Self::Type, popup: &Widget) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).populate_popup {
+                f(
+                    entry.unsafe_cast_ref::<Entry>().to_glib_none().0,
+                    popup.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_activate(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).activate {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_move_cursor(
+        &self,
+        entry: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    ) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).move_cursor {
+                f(
+                    entry.unsafe_cast_ref::<Entry>().to_glib_none().0,
+                    step.to_glib(),
+                    count,
+                    extend_selection.to_glib(),
+                )
+            }
+        }
+    }
+
+    fn parent_insert_at_cursor(&self, entry: &Self::Type, text: &str) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).insert_at_cursor {
+                f(
+                    entry.unsafe_cast_ref::<Entry>().to_glib_none().0,
+                    text.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_delete_from_cursor(&self, entry: &Self::Type, type_: DeleteType, count: i32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).delete_from_cursor {
+                f(
+                    entry.unsafe_cast_ref::<Entry>().to_glib_none().0,
+                    type_.to_glib(),
+                    count,
+                )
+            }
+        }
+    }
+
+    fn parent_backspace(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).backspace {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_cut_clipboard(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).cut_clipboard {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_copy_clipboard(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).copy_clipboard {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_paste_clipboard(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).paste_clipboard {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_toggle_overwrite(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).toggle_overwrite {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_get_text_area_size(&self, entry: &Self::Type) -> (i32, i32, i32, i32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            let (mut x, mut y, mut width, mut height) = (0, 0, 0, 0);
+            if let Some(f) = (*parent_class).get_text_area_size {
+                f(
+                    entry.unsafe_cast_ref::<Entry>().to_glib_none().0,
+                    &mut x,
+                    &mut y,
+                    &mut width,
+                    &mut height,
+                )
+            }
+            (x, y, width, height)
+        }
+    }
+
+    fn parent_get_frame_size(&self, entry: &Self::Type) -> (i32, i32, i32, i32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            let (mut x, mut y, mut width, mut height) = (0, 0, 0, 0);
+            if let Some(f) = (*parent_class).get_frame_size {
+                f(
+                    entry.unsafe_cast_ref::<Entry>().to_glib_none().0,
+                    &mut x,
+                    &mut y,
+                    &mut width,
+                    &mut height,
+                )
+            }
+            (x, y, width, height)
+        }
+    }
+
+    fn parent_insert_emoji(&self, entry: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkEntryClass;
+            if let Some(f) = (*parent_class).insert_emoji {
+                f(entry.unsafe_cast_ref::<Entry>().to_glib_none().0)
+            }
+        }
+    }
+}
+
+unsafe impl<T: EntryImpl> IsSubclassable<T> for Entry {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Widget as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.populate_popup = Some(entry_populate_popup::<T>);
+        klass.activate = Some(entry_activate::<T>);
+        klass.move_cursor = Some(entry_move_cursor::<T>);
+        klass.insert_at_cursor = Some(entry_insert_at_cursor::<T>);
+        klass.delete_from_cursor = Some(entry_delete_from_cursor::<T>);
+        klass.backspace = Some(entry_backspace::<T>);
+        klass.cut_clipboard = Some(entry_cut_clipboard::<T>);
+        klass.copy_clipboard = Some(entry_copy_clipboard::<T>);
+        klass.paste_clipboard = Some(entry_paste_clipboard::<T>);
+        klass.toggle_overwrite = Some(entry_toggle_overwrite::<T>);
+        klass.get_text_area_size = Some(entry_get_text_area_size::<T>);
+        klass.get_frame_size = Some(entry_get_frame_size::<T>);
+        klass.insert_emoji = Some(entry_insert_emoji::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Widget as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn entry_populate_popup<T: EntryImpl>(
+    ptr: *mut ffi::GtkEntry,
+    popupptr: *mut ffi::GtkWidget,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+    let popup: Borrowed<Widget> = from_glib_borrow(popupptr);
+
+    imp.populate_popup(wrap.unsafe_cast_ref(), &popup)
+}
+
+unsafe extern "C" fn entry_activate<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.activate(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn entry_move_cursor<T: EntryImpl>(
+    ptr: *mut ffi::GtkEntry,
+    step: ffi::GtkMovementStep,
+    count: c_int,
+    extend_selection: glib::ffi::gboolean,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.move_cursor(
+        wrap.unsafe_cast_ref(),
+        from_glib(step),
+        count,
+        from_glib(extend_selection),
+    )
+}
+
+unsafe extern "C" fn entry_insert_at_cursor<T: EntryImpl>(
+    ptr: *mut ffi::GtkEntry,
+    textptr: *const c_char,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.insert_at_cursor(wrap.unsafe_cast_ref(), &GString::from_glib_borrow(textptr))
+}
+
+unsafe extern "C" fn entry_delete_from_cursor<T: EntryImpl>(
+    ptr: *mut ffi::GtkEntry,
+    type_: ffi::GtkDeleteType,
+    count: c_int,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.delete_from_cursor(wrap.unsafe_cast_ref(), from_glib(type_), count)
+}
+
+unsafe extern "C" fn entry_backspace<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.backspace(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn entry_cut_clipboard<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.cut_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn entry_copy_clipboard<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.copy_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn entry_paste_clipboard<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.paste_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn entry_toggle_overwrite<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.toggle_overwrite(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn entry_get_text_area_size<T: EntryImpl>(
+    ptr: *mut ffi::GtkEntry,
+    x: *mut c_int,
+    y: *mut c_int,
+    width: *mut c_int,
+    height: *mut c_int,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    let ret = imp.get_text_area_size(wrap.unsafe_cast_ref());
+    for (out, value) in [(x, ret.0), (y, ret.1), (width, ret.2), (height, ret.3)].iter() {
+        if !out.is_null() {
+            **out = *value;
+        }
+    }
+}
+
+unsafe extern "C" fn entry_get_frame_size<T: EntryImpl>(
+    ptr: *mut ffi::GtkEntry,
+    x: *mut c_int,
+    y: *mut c_int,
+    width: *mut c_int,
+    height: *mut c_int,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    let ret = imp.get_frame_size(wrap.unsafe_cast_ref());
+    for (out, value) in [(x, ret.0), (y, ret.1), (width, ret.2), (height, ret.3)].iter() {
+        if !out.is_null() {
+            **out = *value;
+        }
+    }
+}
+
+unsafe extern "C" fn entry_insert_emoji<T: EntryImpl>(ptr: *mut ffi::GtkEntry) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Entry> = from_glib_borrow(ptr);
+
+    imp.insert_emoji(wrap.unsafe_cast_ref())
+}
@@ -0,0 +1,152 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+//! Properties of interfaces that are implemented by the methods of the `*Impl` trait.
+//!
+//! Interfaces like `GtkOrientable` require the implementing class to install their properties.
+//! The subclassing traits of these interfaces override the properties automatically and route
+//! them to their methods, so they must not be returned from `ObjectImpl::properties` again.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, StaticType};
+
+use std::mem;
+use std::ptr;
+use std::slice;
+
+type PropertyFn = unsafe extern "C" fn(
+    *mut glib::gobject_ffi::GObject,
+    u32,
+    *mut glib::gobject_ffi::GValue,
+    *mut glib::gobject_ffi::GParamSpec,
+);
+
+// Properties of an interface that are overridden on the class of the implementing type and
+// dispatched to the interface implementation instead of `ObjectImpl`
+struct InterfaceProperties<T: ObjectSubclass> {
+    first_id: u32,
+    n_properties: u32,
+    set: fn(&T, &T::Type, usize, &glib::Value),
+    get: fn(&T, &T::Type, usize) -> glib::Value,
+    parent_set_property: Option<PropertyFn>,
+    parent_get_property: Option<PropertyFn>,
+}
+
+/// Overrides the properties `names` of the interface `I` on the class of `T`.
+///
+/// Reading and writing them calls `get` and `set` with the index of the property in `names`,
+/// all other properties are still handled by the `ObjectImpl` of `T`.
+///
+/// # Safety
+///
+/// Must only be called from `IsImplementable::interface_init` of `I` with the interface struct
+/// that is being initialized.
+pub(crate) unsafe fn override_interface_properties<T: ObjectSubclass, I: StaticType>(
+    iface: &glib::gobject_ffi::GTypeInterface,
+    names: &[&str],
+    set: fn(&T, &T::Type, usize, &glib::Value),
+    get: fn(&T, &T::Type, usize) -> glib::Value,
+) {
+    // The class is already initialized at this point, GObject itself looks it up the same way
+    // to check the interface properties afterwards
+    let klass = glib::gobject_ffi::g_type_class_ref(iface.g_instance_type)
+        as *mut glib::gobject_ffi::GObjectClass;
+
+    // Continue after the ids of the properties installed by the class itself or by other
+    // interfaces
+    let mut n_pspecs = 0;
+    let pspecs = glib::gobject_ffi::g_object_class_list_properties(klass, &mut n_pspecs);
+    let first_id = if pspecs.is_null() {
+        1
+    } else {
+        let max_id = slice::from_raw_parts(pspecs, n_pspecs as usize)
+            .iter()
+            .filter(|pspec| (***pspec).owner_type == iface.g_instance_type)
+            .map(|pspec| (**pspec).param_id)
+            .max()
+            .unwrap_or(0);
+        glib::ffi::g_free(pspecs as glib::ffi::gpointer);
+        max_id + 1
+    };
+
+    for (i, name) in names.iter().enumerate() {
+        glib::gobject_ffi::g_object_class_override_property(
+            klass,
+            first_id + i as u32,
+            name.to_glib_none().0,
+        );
+    }
+
+    let data = InterfaceProperties::<T> {
+        first_id,
+        n_properties: names.len() as u32,
+        set,
+        get,
+        parent_set_property: (*klass).set_property,
+        parent_get_property: (*klass).get_property,
+    };
+    let mut type_data = T::type_data();
+    type_data.as_mut().set_class_data(I::static_type(), data);
+
+    (*klass).set_property = Some(interface_set_property::<T, I>);
+    (*klass).get_property = Some(interface_get_property::<T, I>);
+
+    glib::gobject_ffi::g_type_class_unref(klass as glib::ffi::gpointer);
+}
+
+unsafe fn get_data<T: ObjectSubclass, I: StaticType>() -> &'static InterfaceProperties<T> {
+    T::type_data()
+        .as_ref()
+        .get_class_data::<InterfaceProperties<T>>(I::static_type())
+        .expect("Interface properties not registered")
+}
+
+unsafe extern "C" fn interface_set_property<T: ObjectSubclass, I: StaticType>(
+    obj: *mut glib::gobject_ffi::GObject,
+    id: u32,
+    value: *mut glib::gobject_ffi::GValue,
+    pspec: *mut glib::gobject_ffi::GParamSpec,
+) {
+    let data = get_data::<T, I>();
+    if id < data.first_id || id >= data.first_id + data.n_properties {
+        if let Some(f) = data.parent_set_property {
+            f(obj, id, value, pspec);
+        }
+        return;
+    }
+
+    let instance = &*(obj as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<glib::Object> = from_glib_borrow(obj);
+    (data.set)(
+        imp,
+        wrap.unsafe_cast_ref(),
+        (id - data.first_id) as usize,
+        &*(value as *const glib::Value),
+    );
+}
+
+unsafe extern "C" fn interface_get_property<T: ObjectSubclass, I: StaticType>(
+    obj: *mut glib::gobject_ffi::GObject,
+    id: u32,
+    value: *mut glib::gobject_ffi::GValue,
+    pspec: *mut glib::gobject_ffi::GParamSpec,
+) {
+    let data = get_data::<T, I>();
+    if id < data.first_id || id >= data.first_id + data.n_properties {
+        if let Some(f) = data.parent_get_property {
+            f(obj, id, value, pspec);
+        }
+        return;
+    }
+
+    let instance = &*(obj as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<glib::Object> = from_glib_borrow(obj);
+    let v = (data.get)(imp, wrap.unsafe_cast_ref(), (id - data.first_id) as usize);
+
+    // See the `get_property` trampoline of `ObjectImpl`
+    glib::gobject_ffi::g_value_unset(value);
+    let v = mem::ManuallyDrop::new(v);
+    ptr::write(value, ptr::read(v.to_glib_none().0));
+}
@@ -0,0 +1,178 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use libc::{c_char, c_int};
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, GString};
+
+use super::widget::WidgetImpl;
+use crate::Label;
+use crate::Menu;
+use crate::MovementStep;
+use crate::Widget;
+
+pub trait LabelImpl: LabelImplExt + WidgetImpl {
+    fn move_cursor(
+        &self,
+        label: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    ) {
+        self.parent_move_cursor(label, step, count, extend_selection)
+    }
+
+    fn copy_clipboard(&self, label: &Self::Type) {
+        self.parent_copy_clipboard(label)
+    }
+
+    fn populate_popup(&self, label: &Self::Type, menu: &Menu) {
+        self.parent_populate_popup(label, menu)
+    }
+
+    /// Called when a link in the label is activated, returns `true` if the link was handled.
+    fn activate_link(&self, label: &Self::Type, uri: &str) -> bool {
+        self.parent_activate_link(label, uri)
+    }
+}
+
+pub trait LabelImplExt: ObjectSubclass {
+    fn parent_move_cursor(
+        &self,
+        label: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    );
+    fn parent_copy_clipboard(&self, label: &Self::Type);
+    fn parent_populate_popup(&self, label: &Self::Type, menu: &Menu);
+    fn parent_activate_link(&self, label: &Self::Type, uri: &str) -> bool;
+}
+
+impl<T: LabelImpl> LabelImplExt for T {
+    fn parent_move_cursor(
+        &self,
+        label: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    ) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkLabelClass;
+            if let Some(f) = (*parent_class).move_cursor {
+                f(
+                    label.unsafe_cast_ref::<Label>().to_glib_none().0,
+                    step.to_glib(),
+                    count,
+                    extend_selection.to_glib(),
+                )
+            }
+        }
+    }
+
+    fn parent_copy_clipboard(&self, label: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkLabelClass;
+            if let Some(f) = (*parent_class).copy_clipboard {
+                f(label.unsafe_cast_ref::<Label>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_populate_popup(&self, label: &Self::Type, menu: &Menu) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkLabelClass;
+            if let Some(f) = (*parent_class).populate_popup {
+                f(
+                    label.unsafe_cast_ref::<Label>().to_glib_none().0,
+                    menu.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_activate_link(&self, label: &Self::Type, uri: &str) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkLabelClass;
+            if let Some(f) = (*parent_class).activate_link {
+                from_glib(f(
+                    label.unsafe_cast_ref::<Label>().to_glib_none().0,
+                    uri.to_glib_none().0,
+                ))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+unsafe impl<T: LabelImpl> IsSubclassable<T> for Label {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Widget as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.move_cursor = Some(label_move_cursor::<T>);
+        klass.copy_clipboard = Some(label_copy_clipboard::<T>);
+        klass.populate_popup = Some(label_populate_popup::<T>);
+        klass.activate_link = Some(label_activate_link::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Widget as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn label_move_cursor<T: LabelImpl>(
+    ptr: *mut ffi::GtkLabel,
+    step: ffi::GtkMovementStep,
+    count: c_int,
+    extend_selection: glib::ffi::gboolean,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Label> = from_glib_borrow(ptr);
+
+    imp.move_cursor(
+        wrap.unsafe_cast_ref(),
+        from_glib(step),
+        count,
+        from_glib(extend_selection),
+    )
+}
+
+unsafe extern "C" fn label_copy_clipboard<T: LabelImpl>(ptr: *mut ffi::GtkLabel) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Label> = from_glib_borrow(ptr);
+
+    imp.copy_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn label_populate_popup<T: LabelImpl>(
+    ptr: *mut ffi::GtkLabel,
+    menuptr: *mut ffi::GtkMenu,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Label> = from_glib_borrow(ptr);
+    let menu: Borrowed<Menu> = from_glib_borrow(menuptr);
+
+    imp.populate_popup(wrap.unsafe_cast_ref(), &menu)
+}
+
+unsafe extern "C" fn label_activate_link<T: LabelImpl>(
+    ptr: *mut ffi::GtkLabel,
+    uriptr: *const c_char,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Label> = from_glib_borrow(ptr);
+
+    imp.activate_link(wrap.unsafe_cast_ref(), &GString::from_glib_borrow(uriptr))
+        .to_glib()
+}
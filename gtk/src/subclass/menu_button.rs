@@ -0,0 +1,19 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+
+use super::toggle_button::ToggleButtonImpl;
+use crate::MenuButton;
+use crate::ToggleButton;
+
+pub trait MenuButtonImpl: ToggleButtonImpl {}
+
+unsafe impl<T: MenuButtonImpl> IsSubclassable<T> for MenuButton {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <ToggleButton as IsSubclassable<T>>::class_init(class);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <ToggleButton as IsSubclassable<T>>::instance_init(instance);
+    }
+}
@@ -4,7 +4,9 @@ pub mod application;
 pub mod application_window;
 pub mod bin;
 pub mod box_;
+pub mod buildable;
 pub mod button;
+pub mod cell_editable;
 pub mod cell_renderer;
 pub mod cell_renderer_accel;
 pub mod cell_renderer_combo;
@@ -14,20 +16,39 @@ pub mod cell_renderer_spin;
 pub mod cell_renderer_spinner;
 pub mod cell_renderer_text;
 pub mod cell_renderer_toggle;
+pub mod combo_box;
 pub mod container;
 pub mod dialog;
 pub mod drawing_area;
+pub mod editable;
+pub mod entry;
 pub mod event_box;
 pub mod fixed;
+pub mod grid;
 pub mod header_bar;
 pub mod icon_view;
+mod interface_properties;
+pub mod label;
 pub mod list_box;
 pub mod list_box_row;
+pub mod menu_button;
+pub mod notebook;
+pub mod orientable;
+pub mod overlay;
+pub mod paned;
 #[cfg(any(gdk_backend = "x11", feature = "dox"))]
 pub mod plug;
+pub mod popover;
+pub mod range;
+pub mod revealer;
+pub mod scrollable;
+pub mod scrolled_window;
 #[cfg(any(gdk_backend = "x11", feature = "dox"))]
 pub mod socket;
 pub mod stack;
+pub mod text_view;
+pub mod toggle_button;
+pub mod tree_model;
 pub mod tree_view;
 pub mod widget;
 pub mod window;
@@ -42,7 +63,9 @@ pub mod prelude {
     pub use super::application_window::ApplicationWindowImpl;
     pub use super::bin::BinImpl;
     pub use super::box_::BoxImpl;
+    pub use super::buildable::{BuildableImpl, BuildableImplExt, BuildableParser};
     pub use super::button::ButtonImpl;
+    pub use super::cell_editable::CellEditableImpl;
     pub use super::cell_renderer::{CellRendererImpl, CellRendererImplExt};
     pub use super::cell_renderer_accel::{CellRendererAccelImpl, CellRendererAccelImplExt};
     pub use super::cell_renderer_combo::CellRendererComboImpl;
@@ -52,23 +75,42 @@ pub mod prelude {
     pub use super::cell_renderer_spinner::CellRendererSpinnerImpl;
     pub use super::cell_renderer_text::{CellRendererTextImpl, CellRendererTextImplExt};
     pub use super::cell_renderer_toggle::{CellRendererToggleImpl, CellRendererToggleImplExt};
+    pub use super::combo_box::{ComboBoxImpl, ComboBoxImplExt};
     pub use super::container::{ContainerImpl, ContainerImplExt};
     pub use super::dialog::{DialogImpl, DialogImplExt};
     pub use super::drawing_area::DrawingAreaImpl;
+    pub use super::editable::EditableImpl;
+    pub use super::entry::{EntryImpl, EntryImplExt};
     pub use super::event_box::EventBoxImpl;
     pub use super::fixed::FixedImpl;
+    pub use super::grid::GridImpl;
     pub use super::header_bar::HeaderBarImpl;
     pub use super::icon_view::{IconViewImpl, IconViewImplExt};
+    pub use super::label::{LabelImpl, LabelImplExt};
     pub use super::list_box::{ListBoxImpl, ListBoxImplExt};
     pub use super::list_box_row::{ListBoxRowImpl, ListBoxRowImplExt};
+    pub use super::menu_button::MenuButtonImpl;
+    pub use super::notebook::{NotebookImpl, NotebookImplExt};
+    pub use super::orientable::OrientableImpl;
+    pub use super::overlay::{OverlayImpl, OverlayImplExt};
+    pub use super::paned::{PanedImpl, PanedImplExt};
     #[cfg(any(gdk_backend = "x11", feature = "dox"))]
     pub use super::plug::{PlugImpl, PlugImplExt};
+    pub use super::popover::{PopoverImpl, PopoverImplExt};
+    pub use super::range::{RangeImpl, RangeImplExt};
+    pub use super::revealer::RevealerImpl;
+    pub use super::scrollable::ScrollableImpl;
+    pub use super::scrolled_window::{ScrolledWindowImpl, ScrolledWindowImplExt};
     #[cfg(any(gdk_backend = "x11", feature = "dox"))]
     pub use super::socket::{SocketImpl, SocketImplExt};
     pub use super::stack::StackImpl;
+    pub use super::text_view::{TextViewImpl, TextViewImplExt};
+    pub use super::toggle_button::{ToggleButtonImpl, ToggleButtonImplExt};
+    pub use super::tree_model::{TreeIterData, TreeModelImpl, TreeModelImplExt};
     pub use super::tree_view::TreeViewImpl;
     pub use super::widget::{
-        CompositeTemplate, TemplateChild, WidgetClassSubclassExt, WidgetImpl, WidgetImplExt,
+        CompositeTemplate, TemplateCallbacks, TemplateChild, WidgetClassSubclassExt, WidgetImpl,
+        WidgetImplExt,
     };
     pub use super::window::{WindowImpl, WindowImplExt};
 }
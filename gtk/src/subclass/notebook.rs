@@ -0,0 +1,475 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use libc::{c_int, c_uint};
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::Cast;
+
+use super::container::ContainerImpl;
+use crate::Container;
+use crate::DirectionType;
+use crate::Notebook;
+use crate::NotebookTab;
+use crate::Widget;
+
+pub trait NotebookImpl: NotebookImplExt + ContainerImpl {
+    fn switch_page(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        self.parent_switch_page(notebook, page, page_num)
+    }
+
+    fn select_page(&self, notebook: &Self::Type, move_focus: bool) -> bool {
+        self.parent_select_page(notebook, move_focus)
+    }
+
+    fn focus_tab(&self, notebook: &Self::Type, type_: NotebookTab) -> bool {
+        self.parent_focus_tab(notebook, type_)
+    }
+
+    fn change_current_page(&self, notebook: &Self::Type, offset: i32) -> bool {
+        self.parent_change_current_page(notebook, offset)
+    }
+
+    fn move_focus_out(&self, notebook: &Self::Type, direction: DirectionType) {
+        self.parent_move_focus_out(notebook, direction)
+    }
+
+    fn reorder_tab(
+        &self,
+        notebook: &Self::Type,
+        direction: DirectionType,
+        move_to_last: bool,
+    ) -> bool {
+        self.parent_reorder_tab(notebook, direction, move_to_last)
+    }
+
+    /// Inserts `child` at `position` and returns its index, or `-1` on failure.
+    fn insert_page(
+        &self,
+        notebook: &Self::Type,
+        child: &Widget,
+        tab_label: Option<&Widget>,
+        menu_label: Option<&Widget>,
+        position: i32,
+    ) -> i32 {
+        self.parent_insert_page(notebook, child, tab_label, menu_label, position)
+    }
+
+    /// Returns the notebook to move `page` to when it is dropped at `x`, `y` outside of any notebook.
+    fn create_window(
+        &self,
+        notebook: &Self::Type,
+        page: &Widget,
+        x: i32,
+        y: i32,
+    ) -> Option<Notebook> {
+        self.parent_create_window(notebook, page, x, y)
+    }
+
+    fn page_reordered(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        self.parent_page_reordered(notebook, page, page_num)
+    }
+
+    fn page_removed(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        self.parent_page_removed(notebook, page, page_num)
+    }
+
+    fn page_added(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        self.parent_page_added(notebook, page, page_num)
+    }
+}
+
+pub trait NotebookImplExt: ObjectSubclass {
+    fn parent_switch_page(&self, notebook: &Self::Type, page: &Widget, page_num: u32);
+    fn parent_select_page(&self, notebook: &Self::Type, move_focus: bool) -> bool;
+    fn parent_focus_tab(&self, notebook: &Self::Type, type_: NotebookTab) -> bool;
+    fn parent_change_current_page(&self, notebook: &Self::Type, offset: i32) -> bool;
+    fn parent_move_focus_out(&self, notebook: &Self::Type, direction: DirectionType);
+    fn parent_reorder_tab(
+        &self,
+        notebook: &Self::Type,
+        direction: DirectionType,
+        move_to_last: bool,
+    ) -> bool;
+    fn parent_insert_page(
+        &self,
+        notebook: &Self::Type,
+        child: &Widget,
+        tab_label: Option<&Widget>,
+        menu_label: Option<&Widget>,
+        position: i32,
+    ) -> i32;
+    fn parent_create_window(
+        &self,
+        notebook: &Self::Type,
+        page: &Widget,
+        x: i32,
+        y: i32,
+    ) -> Option<Notebook>;
+    fn parent_page_reordered(&self, notebook: &Self::Type, page: &Widget, page_num: u32);
+    fn parent_page_removed(&self, notebook: &Self::Type, page: &Widget, page_num: u32);
+    fn parent_page_added(&self, notebook: &Self::Type, page: &Widget, page_num: u32);
+}
+
+impl<T: NotebookImpl> NotebookImplExt for T {
+    fn parent_switch_page(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).switch_page {
+                f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    page.to_glib_none().0,
+                    page_num,
+                )
+            }
+        }
+    }
+
+    fn parent_select_page(&self, notebook: &Self::Type, move_focus: bool) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).select_page {
+                from_glib(f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    move_focus.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_focus_tab(&self, notebook: &Self::Type, type_: NotebookTab) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).focus_tab {
+                from_glib(f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    type_.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_change_current_page(&self, notebook: &Self::Type, offset: i32) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).change_current_page {
+                from_glib(f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    offset,
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_move_focus_out(&self, notebook: &Self::Type, direction: DirectionType) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).move_focus_out {
+                f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    direction.to_glib(),
+                )
+            }
+        }
+    }
+
+    fn parent_reorder_tab(
+        &self,
+        notebook: &Self::Type,
+        direction: DirectionType,
+        move_to_last: bool,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).reorder_tab {
+                from_glib(f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    direction.to_glib(),
+                    move_to_last.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_insert_page(
+        &self,
+        notebook: &Self::Type,
+        child: &Widget,
+        tab_label: Option<&Widget>,
+        menu_label: Option<&Widget>,
+        position: i32,
+    ) -> i32 {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).insert_page {
+                f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    child.to_glib_none().0,
+                    tab_label.to_glib_none().0,
+                    menu_label.to_glib_none().0,
+                    position,
+                )
+            } else {
+                -1
+            }
+        }
+    }
+
+    fn parent_create_window(
+        &self,
+        notebook: &Self::Type,
+        page: &Widget,
+        x: i32,
+        y: i32,
+    ) -> Option<Notebook> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).create_window {
+                from_glib_none(f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    page.to_glib_none().0,
+                    x,
+                    y,
+                ))
+            } else {
+                None
+            }
+        }
+    }
+
+    fn parent_page_reordered(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).page_reordered {
+                f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    page.to_glib_none().0,
+                    page_num,
+                )
+            }
+        }
+    }
+
+    fn parent_page_removed(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).page_removed {
+                f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    page.to_glib_none().0,
+                    page_num,
+                )
+            }
+        }
+    }
+
+    fn parent_page_added(&self, notebook: &Self::Type, page: &Widget, page_num: u32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkNotebookClass;
+            if let Some(f) = (*parent_class).page_added {
+                f(
+                    notebook.unsafe_cast_ref::<Notebook>().to_glib_none().0,
+                    page.to_glib_none().0,
+                    page_num,
+                )
+            }
+        }
+    }
+}
+
+unsafe impl<T: NotebookImpl> IsSubclassable<T> for Notebook {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Container as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.switch_page = Some(notebook_switch_page::<T>);
+        klass.select_page = Some(notebook_select_page::<T>);
+        klass.focus_tab = Some(notebook_focus_tab::<T>);
+        klass.change_current_page = Some(notebook_change_current_page::<T>);
+        klass.move_focus_out = Some(notebook_move_focus_out::<T>);
+        klass.reorder_tab = Some(notebook_reorder_tab::<T>);
+        klass.insert_page = Some(notebook_insert_page::<T>);
+        klass.create_window = Some(notebook_create_window::<T>);
+        klass.page_reordered = Some(notebook_page_reordered::<T>);
+        klass.page_removed = Some(notebook_page_removed::<T>);
+        klass.page_added = Some(notebook_page_added::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Container as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn notebook_switch_page<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    pageptr: *mut ffi::GtkWidget,
+    page_num: c_uint,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+    let page: Borrowed<Widget> = from_glib_borrow(pageptr);
+
+    imp.switch_page(wrap.unsafe_cast_ref(), &page, page_num)
+}
+
+unsafe extern "C" fn notebook_select_page<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    move_focus: glib::ffi::gboolean,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+
+    imp.select_page(wrap.unsafe_cast_ref(), from_glib(move_focus))
+        .to_glib()
+}
+
+unsafe extern "C" fn notebook_focus_tab<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    type_: ffi::GtkNotebookTab,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+
+    imp.focus_tab(wrap.unsafe_cast_ref(), from_glib(type_))
+        .to_glib()
+}
+
+unsafe extern "C" fn notebook_change_current_page<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    offset: c_int,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+
+    imp.change_current_page(wrap.unsafe_cast_ref(), offset)
+        .to_glib()
+}
+
+unsafe extern "C" fn notebook_move_focus_out<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    direction: ffi::GtkDirectionType,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+
+    imp.move_focus_out(wrap.unsafe_cast_ref(), from_glib(direction))
+}
+
+unsafe extern "C" fn notebook_reorder_tab<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    direction: ffi::GtkDirectionType,
+    move_to_last: glib::ffi::gboolean,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+
+    imp.reorder_tab(
+        wrap.unsafe_cast_ref(),
+        from_glib(direction),
+        from_glib(move_to_last),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn notebook_insert_page<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    childptr: *mut ffi::GtkWidget,
+    tab_labelptr: *mut ffi::GtkWidget,
+    menu_labelptr: *mut ffi::GtkWidget,
+    position: c_int,
+) -> c_int {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+    let child: Borrowed<Widget> = from_glib_borrow(childptr);
+    let tab_label: Borrowed<Option<Widget>> = from_glib_borrow(tab_labelptr);
+    let menu_label: Borrowed<Option<Widget>> = from_glib_borrow(menu_labelptr);
+
+    imp.insert_page(
+        wrap.unsafe_cast_ref(),
+        &child,
+        tab_label.as_ref().as_ref(),
+        menu_label.as_ref().as_ref(),
+        position,
+    )
+}
+
+unsafe extern "C" fn notebook_create_window<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    pageptr: *mut ffi::GtkWidget,
+    x: c_int,
+    y: c_int,
+) -> *mut ffi::GtkNotebook {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+    let page: Borrowed<Widget> = from_glib_borrow(pageptr);
+
+    imp.create_window(wrap.unsafe_cast_ref(), &page, x, y)
+        .to_glib_none()
+        .0
+}
+
+unsafe extern "C" fn notebook_page_reordered<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    pageptr: *mut ffi::GtkWidget,
+    page_num: c_uint,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+    let page: Borrowed<Widget> = from_glib_borrow(pageptr);
+
+    imp.page_reordered(wrap.unsafe_cast_ref(), &page, page_num)
+}
+
+unsafe extern "C" fn notebook_page_removed<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    pageptr: *mut ffi::GtkWidget,
+    page_num: c_uint,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+    let page: Borrowed<Widget> = from_glib_borrow(pageptr);
+
+    imp.page_removed(wrap.unsafe_cast_ref(), &page, page_num)
+}
+
+unsafe extern "C" fn notebook_page_added<T: NotebookImpl>(
+    ptr: *mut ffi::GtkNotebook,
+    pageptr: *mut ffi::GtkWidget,
+    page_num: c_uint,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Notebook> = from_glib_borrow(ptr);
+    let page: Borrowed<Widget> = from_glib_borrow(pageptr);
+
+    imp.page_added(wrap.unsafe_cast_ref(), &page, page_num)
+}
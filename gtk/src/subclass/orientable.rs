@@ -0,0 +1,56 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::ToValue;
+
+use super::interface_properties::override_interface_properties;
+use crate::Orientable;
+use crate::Orientation;
+
+/// Implementation of the [`Orientable`](crate::Orientable) interface.
+pub trait OrientableImpl: ObjectImpl {
+    fn get_orientation(&self, orientable: &Self::Type) -> Orientation;
+
+    fn set_orientation(&self, orientable: &Self::Type, orientation: Orientation);
+}
+
+static PROPERTIES: [&str; 1] = ["orientation"];
+
+unsafe impl<T: OrientableImpl> IsImplementable<T> for Orientable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        unsafe {
+            override_interface_properties::<T, Orientable>(
+                &iface.base_iface,
+                &PROPERTIES,
+                orientable_set_property::<T>,
+                orientable_get_property::<T>,
+            );
+        }
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+fn orientable_set_property<T: OrientableImpl>(
+    imp: &T,
+    orientable: &T::Type,
+    _index: usize,
+    value: &glib::Value,
+) {
+    imp.set_orientation(
+        orientable,
+        value
+            .get_some()
+            .expect("type conformity checked by `Object::set_property`"),
+    );
+}
+
+fn orientable_get_property<T: OrientableImpl>(
+    imp: &T,
+    orientable: &T::Type,
+    _index: usize,
+) -> glib::Value {
+    imp.get_orientation(orientable).to_value()
+}
@@ -0,0 +1,83 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::Cast;
+
+use super::bin::BinImpl;
+use crate::Allocation;
+use crate::Bin;
+use crate::Overlay;
+use crate::Widget;
+
+pub trait OverlayImpl: OverlayImplExt + BinImpl {
+    /// Returns the position of the overlay child `widget`, or `None` to let the overlay
+    /// position it according to its alignment.
+    fn get_child_position(&self, overlay: &Self::Type, widget: &Widget) -> Option<Allocation> {
+        self.parent_get_child_position(overlay, widget)
+    }
+}
+
+pub trait OverlayImplExt: ObjectSubclass {
+    fn parent_get_child_position(
+        &self,
+        overlay: &Self::Type,
+        widget: &Widget,
+    ) -> Option<Allocation>;
+}
+
+impl<T: OverlayImpl> OverlayImplExt for T {
+    fn parent_get_child_position(
+        &self,
+        overlay: &Self::Type,
+        widget: &Widget,
+    ) -> Option<Allocation> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkOverlayClass;
+            let f = (*parent_class).get_child_position?;
+            let mut allocation = Allocation::uninitialized();
+            if from_glib(f(
+                overlay.unsafe_cast_ref::<Overlay>().to_glib_none().0,
+                widget.to_glib_none().0,
+                allocation.to_glib_none_mut().0,
+            )) {
+                Some(allocation)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+unsafe impl<T: OverlayImpl> IsSubclassable<T> for Overlay {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Bin as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.get_child_position = Some(overlay_get_child_position::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Bin as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn overlay_get_child_position<T: OverlayImpl>(
+    ptr: *mut ffi::GtkOverlay,
+    wdgtptr: *mut ffi::GtkWidget,
+    allocptr: *mut ffi::GtkAllocation,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Overlay> = from_glib_borrow(ptr);
+    let widget: Borrowed<Widget> = from_glib_borrow(wdgtptr);
+
+    match imp.get_child_position(wrap.unsafe_cast_ref(), &widget) {
+        Some(allocation) => {
+            *allocptr = *allocation.to_glib_none().0;
+            glib::ffi::GTRUE
+        }
+        None => glib::ffi::GFALSE,
+    }
+}
@@ -0,0 +1,212 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::Cast;
+
+use super::container::ContainerImpl;
+use crate::Container;
+use crate::Paned;
+use crate::ScrollType;
+
+pub trait PanedImpl: PanedImplExt + ContainerImpl {
+    fn cycle_child_focus(&self, paned: &Self::Type, reverse: bool) -> bool {
+        self.parent_cycle_child_focus(paned, reverse)
+    }
+
+    fn toggle_handle_focus(&self, paned: &Self::Type) -> bool {
+        self.parent_toggle_handle_focus(paned)
+    }
+
+    fn move_handle(&self, paned: &Self::Type, scroll: ScrollType) -> bool {
+        self.parent_move_handle(paned, scroll)
+    }
+
+    fn cycle_handle_focus(&self, paned: &Self::Type, reverse: bool) -> bool {
+        self.parent_cycle_handle_focus(paned, reverse)
+    }
+
+    fn accept_position(&self, paned: &Self::Type) -> bool {
+        self.parent_accept_position(paned)
+    }
+
+    fn cancel_position(&self, paned: &Self::Type) -> bool {
+        self.parent_cancel_position(paned)
+    }
+}
+
+pub trait PanedImplExt: ObjectSubclass {
+    fn parent_cycle_child_focus(&self, paned: &Self::Type, reverse: bool) -> bool;
+    fn parent_toggle_handle_focus(&self, paned: &Self::Type) -> bool;
+    fn parent_move_handle(&self, paned: &Self::Type, scroll: ScrollType) -> bool;
+    fn parent_cycle_handle_focus(&self, paned: &Self::Type, reverse: bool) -> bool;
+    fn parent_accept_position(&self, paned: &Self::Type) -> bool;
+    fn parent_cancel_position(&self, paned: &Self::Type) -> bool;
+}
+
+impl<T: PanedImpl> PanedImplExt for T {
+    fn parent_cycle_child_focus(&self, paned: &Self::Type, reverse: bool) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPanedClass;
+            if let Some(f) = (*parent_class).cycle_child_focus {
+                from_glib(f(
+                    paned.unsafe_cast_ref::<Paned>().to_glib_none().0,
+                    reverse.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_toggle_handle_focus(&self, paned: &Self::Type) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPanedClass;
+            if let Some(f) = (*parent_class).toggle_handle_focus {
+                from_glib(f(paned.unsafe_cast_ref::<Paned>().to_glib_none().0))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_move_handle(&self, paned: &Self::Type, scroll: ScrollType) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPanedClass;
+            if let Some(f) = (*parent_class).move_handle {
+                from_glib(f(
+                    paned.unsafe_cast_ref::<Paned>().to_glib_none().0,
+                    scroll.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_cycle_handle_focus(&self, paned: &Self::Type, reverse: bool) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPanedClass;
+            if let Some(f) = (*parent_class).cycle_handle_focus {
+                from_glib(f(
+                    paned.unsafe_cast_ref::<Paned>().to_glib_none().0,
+                    reverse.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_accept_position(&self, paned: &Self::Type) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPanedClass;
+            if let Some(f) = (*parent_class).accept_position {
+                from_glib(f(paned.unsafe_cast_ref::<Paned>().to_glib_none().0))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_cancel_position(&self, paned: &Self::Type) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPanedClass;
+            if let Some(f) = (*parent_class).cancel_position {
+                from_glib(f(paned.unsafe_cast_ref::<Paned>().to_glib_none().0))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+unsafe impl<T: PanedImpl> IsSubclassable<T> for Paned {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Container as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.cycle_child_focus = Some(paned_cycle_child_focus::<T>);
+        klass.toggle_handle_focus = Some(paned_toggle_handle_focus::<T>);
+        klass.move_handle = Some(paned_move_handle::<T>);
+        klass.cycle_handle_focus = Some(paned_cycle_handle_focus::<T>);
+        klass.accept_position = Some(paned_accept_position::<T>);
+        klass.cancel_position = Some(paned_cancel_position::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Container as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn paned_cycle_child_focus<T: PanedImpl>(
+    ptr: *mut ffi::GtkPaned,
+    reverse: glib::ffi::gboolean,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Paned> = from_glib_borrow(ptr);
+
+    imp.cycle_child_focus(wrap.unsafe_cast_ref(), from_glib(reverse))
+        .to_glib()
+}
+
+unsafe extern "C" fn paned_toggle_handle_focus<T: PanedImpl>(
+    ptr: *mut ffi::GtkPaned,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Paned> = from_glib_borrow(ptr);
+
+    imp.toggle_handle_focus(wrap.unsafe_cast_ref()).to_glib()
+}
+
+unsafe extern "C" fn paned_move_handle<T: PanedImpl>(
+    ptr: *mut ffi::GtkPaned,
+    scroll: ffi::GtkScrollType,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Paned> = from_glib_borrow(ptr);
+
+    imp.move_handle(wrap.unsafe_cast_ref(), from_glib(scroll))
+        .to_glib()
+}
+
+unsafe extern "C" fn paned_cycle_handle_focus<T: PanedImpl>(
+    ptr: *mut ffi::GtkPaned,
+    reverse: glib::ffi::gboolean,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Paned> = from_glib_borrow(ptr);
+
+    imp.cycle_handle_focus(wrap.unsafe_cast_ref(), from_glib(reverse))
+        .to_glib()
+}
+
+unsafe extern "C" fn paned_accept_position<T: PanedImpl>(
+    ptr: *mut ffi::GtkPaned,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Paned> = from_glib_borrow(ptr);
+
+    imp.accept_position(wrap.unsafe_cast_ref()).to_glib()
+}
+
+unsafe extern "C" fn paned_cancel_position<T: PanedImpl>(
+    ptr: *mut ffi::GtkPaned,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Paned> = from_glib_borrow(ptr);
+
+    imp.cancel_position(wrap.unsafe_cast_ref()).to_glib()
+}
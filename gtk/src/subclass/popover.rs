@@ -0,0 +1,52 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::Cast;
+
+use super::bin::BinImpl;
+use crate::Bin;
+use crate::Popover;
+
+pub trait PopoverImpl: PopoverImplExt + BinImpl {
+    fn closed(&self, popover: &Self::Type) {
+        self.parent_closed(popover)
+    }
+}
+
+pub trait PopoverImplExt: ObjectSubclass {
+    fn parent_closed(&self, popover: &Self::Type);
+}
+
+impl<T: PopoverImpl> PopoverImplExt for T {
+    fn parent_closed(&self, popover: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkPopoverClass;
+            if let Some(f) = (*parent_class).closed {
+                f(popover.unsafe_cast_ref::<Popover>().to_glib_none().0)
+            }
+        }
+    }
+}
+
+unsafe impl<T: PopoverImpl> IsSubclassable<T> for Popover {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Bin as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.closed = Some(popover_closed::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Bin as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn popover_closed<T: PopoverImpl>(ptr: *mut ffi::GtkPopover) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Popover> = from_glib_borrow(ptr);
+
+    imp.closed(wrap.unsafe_cast_ref())
+}
@@ -0,0 +1,237 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use libc::{c_double, c_int};
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::Cast;
+
+use super::widget::WidgetImpl;
+use crate::Border;
+use crate::Orientation;
+use crate::Range;
+use crate::ScrollType;
+use crate::Widget;
+
+pub trait RangeImpl: RangeImplExt + WidgetImpl {
+    fn value_changed(&self, range: &Self::Type) {
+        self.parent_value_changed(range)
+    }
+
+    fn adjust_bounds(&self, range: &Self::Type, new_value: f64) {
+        self.parent_adjust_bounds(range, new_value)
+    }
+
+    fn move_slider(&self, range: &Self::Type, scroll: ScrollType) {
+        self.parent_move_slider(range, scroll)
+    }
+
+    /// Returns the space around the trough of the range.
+    fn get_range_border(&self, range: &Self::Type) -> Border {
+        self.parent_get_range_border(range)
+    }
+
+    /// Called when the value is changed by user interaction, returns `true` to prevent the
+    /// default handling.
+    fn change_value(&self, range: &Self::Type, scroll: ScrollType, new_value: f64) -> bool {
+        self.parent_change_value(range, scroll, new_value)
+    }
+
+    /// Returns the minimum and natural size of the range in `orientation`.
+    fn get_range_size_request(&self, range: &Self::Type, orientation: Orientation) -> (i32, i32) {
+        self.parent_get_range_size_request(range, orientation)
+    }
+}
+
+pub trait RangeImplExt: ObjectSubclass {
+    fn parent_value_changed(&self, range: &Self::Type);
+    fn parent_adjust_bounds(&self, range: &Self::Type, new_value: f64);
+    fn parent_move_slider(&self, range: &Self::Type, scroll: ScrollType);
+    fn parent_get_range_border(&self, range: &Self::Type) -> Border;
+    fn parent_change_value(&self, range: &Self::Type, scroll: ScrollType, new_value: f64) -> bool;
+    fn parent_get_range_size_request(
+        &self,
+        range: &Self::Type,
+        orientation: Orientation,
+    ) -> (i32, i32);
+}
+
+impl<T: RangeImpl> RangeImplExt for T {
+    fn parent_value_changed(&self, range: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkRangeClass;
+            if let Some(f) = (*parent_class).value_changed {
+                f(range.unsafe_cast_ref::<Range>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_adjust_bounds(&self, range: &Self::Type, new_value: f64) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkRangeClass;
+            if let Some(f) = (*parent_class).adjust_bounds {
+                f(range.unsafe_cast_ref::<Range>().to_glib_none().0, new_value)
+            }
+        }
+    }
+
+    fn parent_move_slider(&self, range: &Self::Type, scroll: ScrollType) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkRangeClass;
+            if let Some(f) = (*parent_class).move_slider {
+                f(
+                    range.unsafe_cast_ref::<Range>().to_glib_none().0,
+                    scroll.to_glib(),
+                )
+            }
+        }
+    }
+
+    fn parent_get_range_border(&self, range: &Self::Type) -> Border {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkRangeClass;
+            let mut border = Border::new();
+            if let Some(f) = (*parent_class).get_range_border {
+                f(
+                    range.unsafe_cast_ref::<Range>().to_glib_none().0,
+                    border.to_glib_none_mut().0,
+                )
+            }
+            border
+        }
+    }
+
+    fn parent_change_value(&self, range: &Self::Type, scroll: ScrollType, new_value: f64) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkRangeClass;
+            if let Some(f) = (*parent_class).change_value {
+                from_glib(f(
+                    range.unsafe_cast_ref::<Range>().to_glib_none().0,
+                    scroll.to_glib(),
+                    new_value,
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_get_range_size_request(
+        &self,
+        range: &Self::Type,
+        orientation: Orientation,
+    ) -> (i32, i32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkRangeClass;
+            let mut minimum = 0;
+            let mut natural = 0;
+            if let Some(f) = (*parent_class).get_range_size_request {
+                f(
+                    range.unsafe_cast_ref::<Range>().to_glib_none().0,
+                    orientation.to_glib(),
+                    &mut minimum,
+                    &mut natural,
+                )
+            }
+            (minimum, natural)
+        }
+    }
+}
+
+unsafe impl<T: RangeImpl> IsSubclassable<T> for Range {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Widget as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.value_changed = Some(range_value_changed::<T>);
+        klass.adjust_bounds = Some(range_adjust_bounds::<T>);
+        klass.move_slider = Some(range_move_slider::<T>);
+        klass.get_range_border = Some(range_get_range_border::<T>);
+        klass.change_value = Some(range_change_value::<T>);
+        klass.get_range_size_request = Some(range_get_range_size_request::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Widget as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn range_value_changed<T: RangeImpl>(ptr: *mut ffi::GtkRange) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Range> = from_glib_borrow(ptr);
+
+    imp.value_changed(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn range_adjust_bounds<T: RangeImpl>(
+    ptr: *mut ffi::GtkRange,
+    new_value: c_double,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Range> = from_glib_borrow(ptr);
+
+    imp.adjust_bounds(wrap.unsafe_cast_ref(), new_value)
+}
+
+unsafe extern "C" fn range_move_slider<T: RangeImpl>(
+    ptr: *mut ffi::GtkRange,
+    scroll: ffi::GtkScrollType,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Range> = from_glib_borrow(ptr);
+
+    imp.move_slider(wrap.unsafe_cast_ref(), from_glib(scroll))
+}
+
+unsafe extern "C" fn range_get_range_border<T: RangeImpl>(
+    ptr: *mut ffi::GtkRange,
+    borderptr: *mut ffi::GtkBorder,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Range> = from_glib_borrow(ptr);
+
+    let border = imp.get_range_border(wrap.unsafe_cast_ref());
+    *borderptr = *border.to_glib_none().0;
+}
+
+unsafe extern "C" fn range_change_value<T: RangeImpl>(
+    ptr: *mut ffi::GtkRange,
+    scroll: ffi::GtkScrollType,
+    new_value: c_double,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Range> = from_glib_borrow(ptr);
+
+    imp.change_value(wrap.unsafe_cast_ref(), from_glib(scroll), new_value)
+        .to_glib()
+}
+
+unsafe extern "C" fn range_get_range_size_request<T: RangeImpl>(
+    ptr: *mut ffi::GtkRange,
+    orientation: ffi::GtkOrientation,
+    minimum: *mut c_int,
+    natural: *mut c_int,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Range> = from_glib_borrow(ptr);
+
+    let (min, nat) = imp.get_range_size_request(wrap.unsafe_cast_ref(), from_glib(orientation));
+    if !minimum.is_null() {
+        *minimum = min;
+    }
+    if !natural.is_null() {
+        *natural = nat;
+    }
+}
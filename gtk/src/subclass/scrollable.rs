@@ -0,0 +1,132 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, ToValue};
+
+use super::interface_properties::override_interface_properties;
+use super::widget::WidgetImpl;
+use crate::Adjustment;
+use crate::Border;
+use crate::Scrollable;
+use crate::ScrollablePolicy;
+
+/// Implementation of the [`Scrollable`](crate::Scrollable) interface.
+pub trait ScrollableImpl: WidgetImpl {
+    fn get_hadjustment(&self, scrollable: &Self::Type) -> Option<Adjustment>;
+
+    /// Called by the scrolling container with the adjustment the widget has to connect to, or
+    /// `None` if the widget should create its own.
+    fn set_hadjustment(&self, scrollable: &Self::Type, hadjustment: Option<Adjustment>);
+
+    fn get_vadjustment(&self, scrollable: &Self::Type) -> Option<Adjustment>;
+
+    fn set_vadjustment(&self, scrollable: &Self::Type, vadjustment: Option<Adjustment>);
+
+    fn get_hscroll_policy(&self, scrollable: &Self::Type) -> ScrollablePolicy;
+
+    fn set_hscroll_policy(&self, scrollable: &Self::Type, policy: ScrollablePolicy);
+
+    fn get_vscroll_policy(&self, scrollable: &Self::Type) -> ScrollablePolicy;
+
+    fn set_vscroll_policy(&self, scrollable: &Self::Type, policy: ScrollablePolicy);
+
+    /// Returns the size of a non-scrolling border around the content, e.g. for column
+    /// headers.
+    fn get_border(&self, _scrollable: &Self::Type) -> Option<Border> {
+        None
+    }
+}
+
+static PROPERTIES: [&str; 4] = [
+    "hadjustment",
+    "vadjustment",
+    "hscroll-policy",
+    "vscroll-policy",
+];
+
+unsafe impl<T: ScrollableImpl> IsImplementable<T> for Scrollable {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.get_border = Some(scrollable_get_border::<T>);
+
+        unsafe {
+            override_interface_properties::<T, Scrollable>(
+                &iface.base_iface,
+                &PROPERTIES,
+                scrollable_set_property::<T>,
+                scrollable_get_property::<T>,
+            );
+        }
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+fn scrollable_set_property<T: ScrollableImpl>(
+    imp: &T,
+    scrollable: &T::Type,
+    index: usize,
+    value: &glib::Value,
+) {
+    match PROPERTIES[index] {
+        "hadjustment" => imp.set_hadjustment(
+            scrollable,
+            value
+                .get()
+                .expect("type conformity checked by `Object::set_property`"),
+        ),
+        "vadjustment" => imp.set_vadjustment(
+            scrollable,
+            value
+                .get()
+                .expect("type conformity checked by `Object::set_property`"),
+        ),
+        "hscroll-policy" => imp.set_hscroll_policy(
+            scrollable,
+            value
+                .get_some()
+                .expect("type conformity checked by `Object::set_property`"),
+        ),
+        "vscroll-policy" => imp.set_vscroll_policy(
+            scrollable,
+            value
+                .get_some()
+                .expect("type conformity checked by `Object::set_property`"),
+        ),
+        _ => unreachable!(),
+    }
+}
+
+fn scrollable_get_property<T: ScrollableImpl>(
+    imp: &T,
+    scrollable: &T::Type,
+    index: usize,
+) -> glib::Value {
+    match PROPERTIES[index] {
+        "hadjustment" => imp.get_hadjustment(scrollable).to_value(),
+        "vadjustment" => imp.get_vadjustment(scrollable).to_value(),
+        "hscroll-policy" => imp.get_hscroll_policy(scrollable).to_value(),
+        "vscroll-policy" => imp.get_vscroll_policy(scrollable).to_value(),
+        _ => unreachable!(),
+    }
+}
+
+unsafe extern "C" fn scrollable_get_border<T: ScrollableImpl>(
+    scrollable: *mut ffi::GtkScrollable,
+    border: *mut ffi::GtkBorder,
+) -> glib::ffi::gboolean {
+    let instance = &*(scrollable as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<Scrollable> = from_glib_borrow(scrollable);
+
+    match imp.get_border(wrap.unsafe_cast_ref()) {
+        Some(ret) => {
+            *border = *ret.to_glib_none().0;
+            true
+        }
+        None => false,
+    }
+    .to_glib()
+}
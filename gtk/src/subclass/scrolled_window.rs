@@ -0,0 +1,120 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::Cast;
+
+use super::bin::BinImpl;
+use crate::Bin;
+use crate::DirectionType;
+use crate::ScrollType;
+use crate::ScrolledWindow;
+
+pub trait ScrolledWindowImpl: ScrolledWindowImplExt + BinImpl {
+    fn scroll_child(
+        &self,
+        scrolled_window: &Self::Type,
+        scroll: ScrollType,
+        horizontal: bool,
+    ) -> bool {
+        self.parent_scroll_child(scrolled_window, scroll, horizontal)
+    }
+
+    fn move_focus_out(&self, scrolled_window: &Self::Type, direction: DirectionType) {
+        self.parent_move_focus_out(scrolled_window, direction)
+    }
+}
+
+pub trait ScrolledWindowImplExt: ObjectSubclass {
+    fn parent_scroll_child(
+        &self,
+        scrolled_window: &Self::Type,
+        scroll: ScrollType,
+        horizontal: bool,
+    ) -> bool;
+    fn parent_move_focus_out(&self, scrolled_window: &Self::Type, direction: DirectionType);
+}
+
+impl<T: ScrolledWindowImpl> ScrolledWindowImplExt for T {
+    fn parent_scroll_child(
+        &self,
+        scrolled_window: &Self::Type,
+        scroll: ScrollType,
+        horizontal: bool,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkScrolledWindowClass;
+            if let Some(f) = (*parent_class).scroll_child {
+                from_glib(f(
+                    scrolled_window
+                        .unsafe_cast_ref::<ScrolledWindow>()
+                        .to_glib_none()
+                        .0,
+                    scroll.to_glib(),
+                    horizontal.to_glib(),
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_move_focus_out(&self, scrolled_window: &Self::Type, direction: DirectionType) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkScrolledWindowClass;
+            if let Some(f) = (*parent_class).move_focus_out {
+                f(
+                    scrolled_window
+                        .unsafe_cast_ref::<ScrolledWindow>()
+                        .to_glib_none()
+                        .0,
+                    direction.to_glib(),
+                )
+            }
+        }
+    }
+}
+
+unsafe impl<T: ScrolledWindowImpl> IsSubclassable<T> for ScrolledWindow {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Bin as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.scroll_child = Some(scrolled_window_scroll_child::<T>);
+        klass.move_focus_out = Some(scrolled_window_move_focus_out::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Bin as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn scrolled_window_scroll_child<T: ScrolledWindowImpl>(
+    ptr: *mut ffi::GtkScrolledWindow,
+    scroll: ffi::GtkScrollType,
+    horizontal: glib::ffi::gboolean,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<ScrolledWindow> = from_glib_borrow(ptr);
+
+    imp.scroll_child(
+        wrap.unsafe_cast_ref(),
+        from_glib(scroll),
+        from_glib(horizontal),
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn scrolled_window_move_focus_out<T: ScrolledWindowImpl>(
+    ptr: *mut ffi::GtkScrolledWindow,
+    direction: ffi::GtkDirectionType,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<ScrolledWindow> = from_glib_borrow(ptr);
+
+    imp.move_focus_out(wrap.unsafe_cast_ref(), from_glib(direction))
+}
@@ -0,0 +1,498 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use libc::{c_char, c_int};
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, GString};
+
+use super::container::ContainerImpl;
+use crate::Container;
+use crate::DeleteType;
+use crate::MovementStep;
+use crate::TextBuffer;
+#[cfg(any(feature = "v3_16", feature = "dox"))]
+use crate::TextExtendSelection;
+use crate::TextIter;
+use crate::TextView;
+use crate::TextViewLayer;
+use crate::Widget;
+
+pub trait TextViewImpl: TextViewImplExt + ContainerImpl {
+    fn populate_popup(&self, text_view: &Self::Type, popup: &Widget) {
+        self.parent_populate_popup(text_view, popup)
+    }
+
+    fn move_cursor(
+        &self,
+        text_view: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    ) {
+        self.parent_move_cursor(text_view, step, count, extend_selection)
+    }
+
+    fn set_anchor(&self, text_view: &Self::Type) {
+        self.parent_set_anchor(text_view)
+    }
+
+    fn insert_at_cursor(&self, text_view: &Self::Type, text: &str) {
+        self.parent_insert_at_cursor(text_view, text)
+    }
+
+    fn delete_from_cursor(&self, text_view: &Self::Type, type_: DeleteType, count: i32) {
+        self.parent_delete_from_cursor(text_view, type_, count)
+    }
+
+    fn backspace(&self, text_view: &Self::Type) {
+        self.parent_backspace(text_view)
+    }
+
+    fn cut_clipboard(&self, text_view: &Self::Type) {
+        self.parent_cut_clipboard(text_view)
+    }
+
+    fn copy_clipboard(&self, text_view: &Self::Type) {
+        self.parent_copy_clipboard(text_view)
+    }
+
+    fn paste_clipboard(&self, text_view: &Self::Type) {
+        self.parent_paste_clipboard(text_view)
+    }
+
+    fn toggle_overwrite(&self, text_view: &Self::Type) {
+        self.parent_toggle_overwrite(text_view)
+    }
+
+    /// Creates the buffer of the text view if none was set.
+    fn create_buffer(&self, text_view: &Self::Type) -> TextBuffer {
+        self.parent_create_buffer(text_view)
+    }
+
+    fn draw_layer(&self, text_view: &Self::Type, layer: TextViewLayer, cr: &cairo::Context) {
+        self.parent_draw_layer(text_view, layer, cr)
+    }
+
+    #[cfg(any(feature = "v3_16", feature = "dox"))]
+    /// Sets `start` and `end` to the bounds of the selection of `granularity` around
+    /// `location`, returns `true` to stop other handlers from being invoked.
+    fn extend_selection(
+        &self,
+        text_view: &Self::Type,
+        granularity: TextExtendSelection,
+        location: &TextIter,
+        start: &mut TextIter,
+        end: &mut TextIter,
+    ) -> bool {
+        self.parent_extend_selection(text_view, granularity, location, start, end)
+    }
+
+    fn insert_emoji(&self, text_view: &Self::Type) {
+        self.parent_insert_emoji(text_view)
+    }
+}
+
+pub trait TextViewImplExt: ObjectSubclass {
+    fn parent_populate_popup(&self, text_view: &Self::Type, popup: &Widget);
+    fn parent_move_cursor(
+        &self,
+        text_view: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    );
+    fn parent_set_anchor(&self, text_view: &Self::Type);
+    fn parent_insert_at_cursor(&self, text_view: &Self::Type, text: &str);
+    fn parent_delete_from_cursor(&self, text_view: &Self::Type, type_: DeleteType, count: i32);
+    fn parent_backspace(&self, text_view: &Self::Type);
+    fn parent_cut_clipboard(&self, text_view: &Self::Type);
+    fn parent_copy_clipboard(&self, text_view: &Self::Type);
+    fn parent_paste_clipboard(&self, text_view: &Self::Type);
+    fn parent_toggle_overwrite(&self, text_view: &Self::Type);
+    fn parent_create_buffer(&self, text_view: &Self::Type) -> TextBuffer;
+    fn parent_draw_layer(&self, text_view: &Self::Type, layer: TextViewLayer, cr: &cairo::Context);
+    #[cfg(any(feature = "v3_16", feature = "dox"))]
+    fn parent_extend_selection(
+        &self,
+        text_view: &Self::Type,
+        granularity: TextExtendSelection,
+        location: &TextIter,
+        start: &mut TextIter,
+        end: &mut TextIter,
+    ) -> bool;
+    fn parent_insert_emoji(&self, text_view: &Self::Type);
+}
+
+impl<T: TextViewImpl> TextViewImplExt for T {
+    fn parent_populate_popup(&self, text_view: &Self::Type, popup: &Widget) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).populate_popup {
+                f(
+                    text_view.unsafe_cast_ref::<TextView>().to_glib_none().0,
+                    popup.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_move_cursor(
+        &self,
+        text_view: &Self::Type,
+        step: MovementStep,
+        count: i32,
+        extend_selection: bool,
+    ) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).move_cursor {
+                f(
+                    text_view.unsafe_cast_ref::<TextView>().to_glib_none().0,
+                    step.to_glib(),
+                    count,
+                    extend_selection.to_glib(),
+                )
+            }
+        }
+    }
+
+    fn parent_set_anchor(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).set_anchor {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_insert_at_cursor(&self, text_view: &Self::Type, text: &str) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).insert_at_cursor {
+                f(
+                    text_view.unsafe_cast_ref::<TextView>().to_glib_none().0,
+                    text.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_delete_from_cursor(&self, text_view: &Self::Type, type_: DeleteType, count: i32) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).delete_from_cursor {
+                f(
+                    text_view.unsafe_cast_ref::<TextView>().to_glib_none().0,
+                    type_.to_glib(),
+                    count,
+                )
+            }
+        }
+    }
+
+    fn parent_backspace(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).backspace {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_cut_clipboard(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).cut_clipboard {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_copy_clipboard(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).copy_clipboard {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_paste_clipboard(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).paste_clipboard {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_toggle_overwrite(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).toggle_overwrite {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_create_buffer(&self, text_view: &Self::Type) -> TextBuffer {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            let f = (*parent_class)
+                .create_buffer
+                .expect("No parent class impl for \"create_buffer\"");
+            from_glib_full(f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0))
+        }
+    }
+
+    fn parent_draw_layer(&self, text_view: &Self::Type, layer: TextViewLayer, cr: &cairo::Context) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).draw_layer {
+                f(
+                    text_view.unsafe_cast_ref::<TextView>().to_glib_none().0,
+                    layer.to_glib(),
+                    cr.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    #[cfg(any(feature = "v3_16", feature = "dox"))]
+    fn parent_extend_selection(
+        &self,
+        text_view: &Self::Type,
+        granularity: TextExtendSelection,
+        location: &TextIter,
+        start: &mut TextIter,
+        end: &mut TextIter,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).extend_selection {
+                from_glib(f(
+                    text_view.unsafe_cast_ref::<TextView>().to_glib_none().0,
+                    granularity.to_glib(),
+                    location.to_glib_none().0,
+                    start.to_glib_none_mut().0,
+                    end.to_glib_none_mut().0,
+                ))
+            } else {
+                false
+            }
+        }
+    }
+
+    fn parent_insert_emoji(&self, text_view: &Self::Type) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GtkTextViewClass;
+            if let Some(f) = (*parent_class).insert_emoji {
+                f(text_view.unsafe_cast_ref::<TextView>().to_glib_none().0)
+            }
+        }
+    }
+}
+
+unsafe impl<T: TextViewImpl> IsSubclassable<T> for TextView {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        <Container as IsSubclassable<T>>::class_init(class);
+
+        let klass = class.as_mut();
+        klass.populate_popup = Some(text_view_populate_popup::<T>);
+        klass.move_cursor = Some(text_view_move_cursor::<T>);
+        klass.set_anchor = Some(text_view_set_anchor::<T>);
+        klass.insert_at_cursor = Some(text_view_insert_at_cursor::<T>);
+        klass.delete_from_cursor = Some(text_view_delete_from_cursor::<T>);
+        klass.backspace = Some(text_view_backspace::<T>);
+        klass.cut_clipboard = Some(text_view_cut_clipboard::<T>);
+        klass.copy_clipboard = Some(text_view_copy_clipboard::<T>);
+        klass.paste_clipboard = Some(text_view_paste_clipboard::<T>);
+        klass.toggle_overwrite = Some(text_view_toggle_overwrite::<T>);
+        klass.create_buffer = Some(text_view_create_buffer::<T>);
+        klass.draw_layer = Some(text_view_draw_layer::<T>);
+        #[cfg(any(feature = "v3_16", feature = "dox"))]
+        klass.extend_selection = Some(text_view_extend_selection::<T>);
+        klass.insert_emoji = Some(text_view_insert_emoji::<T>);
+    }
+
+    fn instance_init(instance: &mut glib::subclass::InitializingObject<T>) {
+        <Container as IsSubclassable<T>>::instance_init(instance);
+    }
+}
+
+unsafe extern "C" fn text_view_populate_popup<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+    popupptr: *mut ffi::GtkWidget,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+    let popup: Borrowed<Widget> = from_glib_borrow(popupptr);
+
+    imp.populate_popup(wrap.unsafe_cast_ref(), &popup)
+}
+
+unsafe extern "C" fn text_view_move_cursor<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+    step: ffi::GtkMovementStep,
+    count: c_int,
+    extend_selection: glib::ffi::gboolean,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.move_cursor(
+        wrap.unsafe_cast_ref(),
+        from_glib(step),
+        count,
+        from_glib(extend_selection),
+    )
+}
+
+unsafe extern "C" fn text_view_set_anchor<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.set_anchor(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn text_view_insert_at_cursor<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+    textptr: *const c_char,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.insert_at_cursor(wrap.unsafe_cast_ref(), &GString::from_glib_borrow(textptr))
+}
+
+unsafe extern "C" fn text_view_delete_from_cursor<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+    type_: ffi::GtkDeleteType,
+    count: c_int,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.delete_from_cursor(wrap.unsafe_cast_ref(), from_glib(type_), count)
+}
+
+unsafe extern "C" fn text_view_backspace<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.backspace(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn text_view_cut_clipboard<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.cut_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn text_view_copy_clipboard<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.copy_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn text_view_paste_clipboard<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.paste_clipboard(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn text_view_toggle_overwrite<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.toggle_overwrite(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn text_view_create_buffer<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+) -> *mut ffi::GtkTextBuffer {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.create_buffer(wrap.unsafe_cast_ref()).to_glib_full()
+}
+
+unsafe extern "C" fn text_view_draw_layer<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+    layer: ffi::GtkTextViewLayer,
+    cr_ptr: *mut cairo::ffi::cairo_t,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+    let cr: Borrowed<cairo::Context> = from_glib_borrow(cr_ptr);
+
+    imp.draw_layer(wrap.unsafe_cast_ref(), from_glib(layer), &cr)
+}
+
+#[cfg(any(feature = "v3_16", feature = "dox"))]
+unsafe extern "C" fn text_view_extend_selection<T: TextViewImpl>(
+    ptr: *mut ffi::GtkTextView,
+    granularity: ffi::GtkTextExtendSelection,
+    locationptr: *const ffi::GtkTextIter,
+    startptr: *mut ffi::GtkTextIter,
+    endptr: *mut ffi::GtkTextIter,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+    let location: Borrowed<TextIter> = from_glib_borrow(locationptr);
+    let mut start: TextIter = from_glib_none(startptr);
+    let mut end: TextIter = from_glib_none(endptr);
+
+    let ret = imp.extend_selection(
+        wrap.unsafe_cast_ref(),
+        from_glib(granularity),
+        &location,
+        &mut start,
+        &mut end,
+    );
+
+    // The iters are copies, write them back to the ones passed by GTK
+    *startptr = *start.to_glib_none().0;
+    *endptr = *end.to_glib_none().0;
+
+    ret.to_glib()
+}
+
+unsafe extern "C" fn text_view_insert_emoji<T: TextViewImpl>(ptr: *mut ffi::GtkTextView) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Borrowed<TextView> = from_glib_borrow(ptr);
+
+    imp.insert_emoji(wrap.unsafe_cast_ref())
+}
@@ -0,0 +1,519 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::{Cast, IsA, ObjectExt};
+use once_cell::sync::Lazy;
+
+use crate::TreeIter;
+use crate::TreeModel;
+use crate::TreeModelFlags;
+use crate::TreePath;
+
+/// Data stored in a [`TreeIter`](crate::TreeIter) by a [`TreeModelImpl`].
+///
+/// A `TreeIter` has room for three pointer-sized values and is freely copied by GTK without
+/// ever being freed, so the data has to be plain `Copy` data like row indices or database keys
+/// that can be looked up again in the model.
+pub trait TreeIterData: Copy + 'static {
+    fn to_raw(self) -> [usize; 3];
+    fn from_raw(raw: [usize; 3]) -> Self;
+}
+
+impl TreeIterData for () {
+    fn to_raw(self) -> [usize; 3] {
+        [0; 3]
+    }
+
+    fn from_raw(_raw: [usize; 3]) -> Self {}
+}
+
+macro_rules! tree_iter_data_int {
+    ($($name:ty),+) => {
+        $(
+            impl TreeIterData for $name {
+                fn to_raw(self) -> [usize; 3] {
+                    [self as usize, 0, 0]
+                }
+
+                fn from_raw(raw: [usize; 3]) -> Self {
+                    raw[0] as $name
+                }
+            }
+        )+
+    };
+}
+
+tree_iter_data_int!(u8, i8, u16, i16, u32, i32, usize, isize);
+
+impl TreeIterData for u64 {
+    fn to_raw(self) -> [usize; 3] {
+        [self as usize, (self >> 32) as usize, 0]
+    }
+
+    fn from_raw(raw: [usize; 3]) -> Self {
+        if std::mem::size_of::<usize>() >= 8 {
+            raw[0] as u64
+        } else {
+            (raw[0] as u32 as u64) | ((raw[1] as u64) << 32)
+        }
+    }
+}
+
+impl TreeIterData for i64 {
+    fn to_raw(self) -> [usize; 3] {
+        (self as u64).to_raw()
+    }
+
+    fn from_raw(raw: [usize; 3]) -> Self {
+        u64::from_raw(raw) as i64
+    }
+}
+
+impl TreeIterData for (usize, usize) {
+    fn to_raw(self) -> [usize; 3] {
+        [self.0, self.1, 0]
+    }
+
+    fn from_raw(raw: [usize; 3]) -> Self {
+        (raw[0], raw[1])
+    }
+}
+
+impl TreeIterData for (usize, usize, usize) {
+    fn to_raw(self) -> [usize; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    fn from_raw(raw: [usize; 3]) -> Self {
+        (raw[0], raw[1], raw[2])
+    }
+}
+
+pub trait TreeModelImpl: ObjectImpl {
+    /// The data identifying a row of the model.
+    type Iter: TreeIterData;
+
+    fn get_flags(&self, _tree_model: &Self::Type) -> TreeModelFlags {
+        TreeModelFlags::empty()
+    }
+
+    fn get_n_columns(&self, tree_model: &Self::Type) -> i32;
+
+    fn get_column_type(&self, tree_model: &Self::Type, index: i32) -> glib::Type;
+
+    fn get_iter(&self, tree_model: &Self::Type, path: &TreePath) -> Option<Self::Iter>;
+
+    fn get_path(&self, tree_model: &Self::Type, iter: Self::Iter) -> TreePath;
+
+    fn get_value(&self, tree_model: &Self::Type, iter: Self::Iter, column: i32) -> glib::Value;
+
+    fn iter_next(&self, tree_model: &Self::Type, iter: Self::Iter) -> Option<Self::Iter>;
+
+    fn iter_previous(&self, tree_model: &Self::Type, iter: Self::Iter) -> Option<Self::Iter> {
+        let mut path = self.get_path(tree_model, iter);
+        if path.prev() {
+            self.get_iter(tree_model, &path)
+        } else {
+            None
+        }
+    }
+
+    fn iter_children(
+        &self,
+        tree_model: &Self::Type,
+        parent: Option<Self::Iter>,
+    ) -> Option<Self::Iter> {
+        self.iter_nth_child(tree_model, parent, 0)
+    }
+
+    fn iter_has_child(&self, tree_model: &Self::Type, iter: Self::Iter) -> bool {
+        self.iter_n_children(tree_model, Some(iter)) > 0
+    }
+
+    /// Returns the number of children of `iter`, or the number of top-level rows if `iter` is
+    /// `None`.
+    fn iter_n_children(&self, tree_model: &Self::Type, iter: Option<Self::Iter>) -> i32;
+
+    /// Returns the `n`-th child of `parent`, or the `n`-th top-level row if `parent` is `None`.
+    fn iter_nth_child(
+        &self,
+        tree_model: &Self::Type,
+        parent: Option<Self::Iter>,
+        n: i32,
+    ) -> Option<Self::Iter>;
+
+    fn iter_parent(&self, _tree_model: &Self::Type, _child: Self::Iter) -> Option<Self::Iter> {
+        None
+    }
+
+    fn ref_node(&self, _tree_model: &Self::Type, _iter: Self::Iter) {}
+
+    fn unref_node(&self, _tree_model: &Self::Type, _iter: Self::Iter) {}
+}
+
+/// Conversions between [`TreeModelImpl::Iter`] and [`TreeIter`](crate::TreeIter), e.g. for
+/// emitting the `row-changed` signal.
+pub trait TreeModelImplExt: TreeModelImpl {
+    /// Wraps `iter` in a `TreeIter` that is valid until [`invalidate_iters`] is called.
+    ///
+    /// [`invalidate_iters`]: Self::invalidate_iters
+    fn to_tree_iter(&self, tree_model: &Self::Type, iter: Self::Iter) -> TreeIter;
+
+    /// Extracts the data from a `TreeIter` of this model.
+    ///
+    /// Returns `None` if `iter` does not belong to this model or was invalidated.
+    fn from_tree_iter(&self, tree_model: &Self::Type, iter: &TreeIter) -> Option<Self::Iter>;
+
+    /// Invalidates all `TreeIter`s handed out by this model so far.
+    ///
+    /// This has to be called whenever a change of the model makes existing iters point to
+    /// different rows, unless the model has the `ITERS_PERSIST` flag.
+    fn invalidate_iters(&self, tree_model: &Self::Type);
+}
+
+impl<T: TreeModelImpl> TreeModelImplExt for T
+where
+    <T as ObjectSubclass>::Type: IsA<TreeModel>,
+{
+    fn to_tree_iter(&self, tree_model: &Self::Type, iter: Self::Iter) -> TreeIter {
+        unsafe {
+            let mut raw = std::mem::MaybeUninit::<ffi::GtkTreeIter>::zeroed();
+            set_iter::<T>(tree_model.upcast_ref(), raw.as_mut_ptr(), Some(iter));
+            from_glib_none(raw.as_ptr())
+        }
+    }
+
+    fn from_tree_iter(&self, tree_model: &Self::Type, iter: &TreeIter) -> Option<Self::Iter> {
+        unsafe { get_iter::<T>(tree_model.upcast_ref(), iter.to_glib_none().0) }
+    }
+
+    fn invalidate_iters(&self, tree_model: &Self::Type) {
+        unsafe {
+            let tree_model = tree_model.upcast_ref::<TreeModel>();
+            let stamp = get_stamp(tree_model);
+            tree_model.set_qdata(
+                *TREE_MODEL_STAMP_QUARK,
+                (stamp.wrapping_add(1) & 0x7fff_ffff).max(1),
+            );
+        }
+    }
+}
+
+unsafe impl<T: TreeModelImpl> IsImplementable<T> for TreeModel
+where
+    <T as ObjectSubclass>::Type: IsA<glib::Object>,
+{
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.get_flags = Some(tree_model_get_flags::<T>);
+        iface.get_n_columns = Some(tree_model_get_n_columns::<T>);
+        iface.get_column_type = Some(tree_model_get_column_type::<T>);
+        iface.get_iter = Some(tree_model_get_iter::<T>);
+        iface.get_path = Some(tree_model_get_path::<T>);
+        iface.get_value = Some(tree_model_get_value::<T>);
+        iface.iter_next = Some(tree_model_iter_next::<T>);
+        iface.iter_previous = Some(tree_model_iter_previous::<T>);
+        iface.iter_children = Some(tree_model_iter_children::<T>);
+        iface.iter_has_child = Some(tree_model_iter_has_child::<T>);
+        iface.iter_n_children = Some(tree_model_iter_n_children::<T>);
+        iface.iter_nth_child = Some(tree_model_iter_nth_child::<T>);
+        iface.iter_parent = Some(tree_model_iter_parent::<T>);
+        iface.ref_node = Some(tree_model_ref_node::<T>);
+        iface.unref_node = Some(tree_model_unref_node::<T>);
+    }
+
+    fn instance_init(_instance: &mut glib::subclass::InitializingObject<T>) {}
+}
+
+static TREE_MODEL_STAMP_QUARK: Lazy<glib::Quark> =
+    Lazy::new(|| glib::Quark::from_string("gtk-rs-subclass-tree-model-stamp"));
+
+// The stamp identifies iters of one model instance and is changed by `invalidate_iters`. It
+// is always positive as unset iters are marked with a zero stamp.
+unsafe fn get_stamp(tree_model: &TreeModel) -> i32 {
+    match tree_model.get_qdata::<i32>(*TREE_MODEL_STAMP_QUARK) {
+        Some(stamp) => *stamp.as_ref(),
+        None => {
+            let stamp = ((glib::ffi::g_random_int() & 0x7fff_ffff) as i32) | 1;
+            tree_model.set_qdata(*TREE_MODEL_STAMP_QUARK, stamp);
+            stamp
+        }
+    }
+}
+
+unsafe fn set_iter<T: TreeModelImpl>(
+    tree_model: &TreeModel,
+    iter: *mut ffi::GtkTreeIter,
+    data: Option<T::Iter>,
+) -> glib::ffi::gboolean {
+    match data {
+        Some(data) => {
+            let raw = data.to_raw();
+            (*iter).stamp = get_stamp(tree_model);
+            (*iter).user_data = raw[0] as glib::ffi::gpointer;
+            (*iter).user_data2 = raw[1] as glib::ffi::gpointer;
+            (*iter).user_data3 = raw[2] as glib::ffi::gpointer;
+            glib::ffi::GTRUE
+        }
+        None => {
+            // Iters that are not set must be marked invalid.
+            (*iter).stamp = 0;
+            glib::ffi::GFALSE
+        }
+    }
+}
+
+unsafe fn get_iter<T: TreeModelImpl>(
+    tree_model: &TreeModel,
+    iter: *const ffi::GtkTreeIter,
+) -> Option<T::Iter> {
+    if iter.is_null() || (*iter).stamp != get_stamp(tree_model) {
+        return None;
+    }
+
+    Some(T::Iter::from_raw([
+        (*iter).user_data as usize,
+        (*iter).user_data2 as usize,
+        (*iter).user_data3 as usize,
+    ]))
+}
+
+unsafe fn get_valid_iter<T: TreeModelImpl>(
+    tree_model: &TreeModel,
+    iter: *const ffi::GtkTreeIter,
+) -> Option<T::Iter> {
+    let ret = get_iter::<T>(tree_model, iter);
+    if ret.is_none() {
+        glib::g_critical!(
+            "Gtk",
+            "TreeIter passed to {} is invalid or does not belong to it",
+            tree_model.get_type()
+        );
+    }
+    ret
+}
+
+unsafe extern "C" fn tree_model_get_flags<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+) -> ffi::GtkTreeModelFlags {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    imp.get_flags(wrap.unsafe_cast_ref()).to_glib()
+}
+
+unsafe extern "C" fn tree_model_get_n_columns<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+) -> libc::c_int {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    imp.get_n_columns(wrap.unsafe_cast_ref())
+}
+
+unsafe extern "C" fn tree_model_get_column_type<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    index: libc::c_int,
+) -> glib::ffi::GType {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    imp.get_column_type(wrap.unsafe_cast_ref(), index).to_glib()
+}
+
+unsafe extern "C" fn tree_model_get_iter<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+    path: *mut ffi::GtkTreePath,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let ret = imp.get_iter(
+        wrap.unsafe_cast_ref(),
+        &from_glib_borrow::<_, TreePath>(path),
+    );
+    set_iter::<T>(&wrap, iter, ret)
+}
+
+unsafe extern "C" fn tree_model_get_path<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) -> *mut ffi::GtkTreePath {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    match get_valid_iter::<T>(&wrap, iter) {
+        Some(iter) => imp.get_path(wrap.unsafe_cast_ref(), iter).to_glib_full(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn tree_model_get_value<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+    column: libc::c_int,
+    value: *mut glib::gobject_ffi::GValue,
+) {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let type_ = imp.get_column_type(wrap.unsafe_cast_ref(), column);
+    let ret = match get_valid_iter::<T>(&wrap, iter) {
+        Some(iter) => {
+            let ret = imp.get_value(wrap.unsafe_cast_ref(), iter, column);
+            assert!(
+                ret.type_().is_a(type_),
+                "TreeModel value of type {} doesn't match column type {}",
+                ret.type_(),
+                type_
+            );
+            ret
+        }
+        None => glib::Value::from_type(type_),
+    };
+    *value = ret.into_raw();
+}
+
+unsafe extern "C" fn tree_model_iter_next<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let ret = get_valid_iter::<T>(&wrap, iter)
+        .and_then(|data| imp.iter_next(wrap.unsafe_cast_ref(), data));
+    set_iter::<T>(&wrap, iter, ret)
+}
+
+unsafe extern "C" fn tree_model_iter_previous<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let ret = get_valid_iter::<T>(&wrap, iter)
+        .and_then(|data| imp.iter_previous(wrap.unsafe_cast_ref(), data));
+    set_iter::<T>(&wrap, iter, ret)
+}
+
+unsafe extern "C" fn tree_model_iter_children<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+    parent: *mut ffi::GtkTreeIter,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let ret = if parent.is_null() {
+        imp.iter_children(wrap.unsafe_cast_ref(), None)
+    } else {
+        get_valid_iter::<T>(&wrap, parent)
+            .and_then(|parent| imp.iter_children(wrap.unsafe_cast_ref(), Some(parent)))
+    };
+    set_iter::<T>(&wrap, iter, ret)
+}
+
+unsafe extern "C" fn tree_model_iter_has_child<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    get_valid_iter::<T>(&wrap, iter)
+        .map_or(false, |iter| {
+            imp.iter_has_child(wrap.unsafe_cast_ref(), iter)
+        })
+        .to_glib()
+}
+
+unsafe extern "C" fn tree_model_iter_n_children<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) -> libc::c_int {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    if iter.is_null() {
+        imp.iter_n_children(wrap.unsafe_cast_ref(), None)
+    } else {
+        get_valid_iter::<T>(&wrap, iter).map_or(0, |iter| {
+            imp.iter_n_children(wrap.unsafe_cast_ref(), Some(iter))
+        })
+    }
+}
+
+unsafe extern "C" fn tree_model_iter_nth_child<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+    parent: *mut ffi::GtkTreeIter,
+    n: libc::c_int,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let ret = if parent.is_null() {
+        imp.iter_nth_child(wrap.unsafe_cast_ref(), None, n)
+    } else {
+        get_valid_iter::<T>(&wrap, parent)
+            .and_then(|parent| imp.iter_nth_child(wrap.unsafe_cast_ref(), Some(parent), n))
+    };
+    set_iter::<T>(&wrap, iter, ret)
+}
+
+unsafe extern "C" fn tree_model_iter_parent<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+    child: *mut ffi::GtkTreeIter,
+) -> glib::ffi::gboolean {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    let ret = get_valid_iter::<T>(&wrap, child)
+        .and_then(|child| imp.iter_parent(wrap.unsafe_cast_ref(), child));
+    set_iter::<T>(&wrap, iter, ret)
+}
+
+unsafe extern "C" fn tree_model_ref_node<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    if let Some(iter) = get_valid_iter::<T>(&wrap, iter) {
+        imp.ref_node(wrap.unsafe_cast_ref(), iter);
+    }
+}
+
+unsafe extern "C" fn tree_model_unref_node<T: TreeModelImpl>(
+    tree_model: *mut ffi::GtkTreeModel,
+    iter: *mut ffi::GtkTreeIter,
+) {
+    let instance = &*(tree_model as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap = from_glib_borrow::<_, TreeModel>(tree_model);
+
+    if let Some(iter) = get_valid_iter::<T>(&wrap, iter) {
+        imp.unref_node(wrap.unsafe_cast_ref(), iter);
+    }
+}
@@ -1,6 +1,8 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use libc::c_int;
+use libc::{c_char, c_int};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::mem;
 
 use glib::translate::*;
@@ -1718,10 +1720,231 @@ pub unsafe trait WidgetClassSubclassExt: ClassStruct {
             private_offset + (offset.get_byte_offset() as isize),
         )
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers `callback` as the handler named `name` for the signals connected in the
+    /// template of this class.
+    ///
+    /// `callback` is called with the template instance and the values of the signal
+    /// arguments, followed by the `object` of the `<signal>` element if there is one. If the
+    /// element is `swapped`, the first and the last value trade places. Errors and return
+    /// values that don't match the signal are reported with `g_critical`.
+    ///
+    /// Handlers of the template that are not registered this way are still looked up among
+    /// the symbols bound with `gtk_widget_class_bind_template_callback_full`. This is usually
+    /// done by [`TemplateCallbacks`].
+    fn bind_template_callback<F>(&mut self, name: &str, callback: F)
+    where
+        F: Fn(&Object, &[glib::Value]) -> Result<Option<glib::Value>, glib::BoolError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        unsafe {
+            let type_class = self as *mut _ as *mut glib::gobject_ffi::GTypeClass;
+            let widget_class =
+                glib::gobject_ffi::g_type_check_class_cast(type_class, ffi::gtk_widget_get_type())
+                    as *mut ffi::GtkWidgetClass;
+            let type_ = (*type_class).g_type;
+
+            let mut callbacks =
+                glib::gobject_ffi::g_type_get_qdata(type_, TEMPLATE_CALLBACKS_QUARK.to_glib())
+                    as *mut TemplateCallbacksMap;
+            if callbacks.is_null() {
+                // Lives as long as the class
+                callbacks = Box::into_raw(Box::new(TemplateCallbacksMap::new()));
+                glib::gobject_ffi::g_type_set_qdata(
+                    type_,
+                    TEMPLATE_CALLBACKS_QUARK.to_glib(),
+                    callbacks as glib::ffi::gpointer,
+                );
+                ffi::gtk_widget_class_set_connect_func(
+                    widget_class,
+                    Some(template_connect_func),
+                    type_ as glib::ffi::gpointer,
+                    None,
+                );
+            }
+            (*callbacks).insert(name.to_string(), Box::new(callback));
+        }
+    }
 }
 
 unsafe impl<T: ClassStruct> WidgetClassSubclassExt for T where T::Type: WidgetImpl {}
 
+type TemplateCallback =
+    dyn Fn(&Object, &[glib::Value]) -> Result<Option<glib::Value>, glib::BoolError> + Send + Sync;
+type TemplateCallbacksMap = HashMap<String, Box<TemplateCallback>>;
+
+static TEMPLATE_CALLBACKS_QUARK: Lazy<glib::Quark> =
+    Lazy::new(|| glib::Quark::from_string("gtk-rs-template-callbacks"));
+
+unsafe extern "C" fn template_connect_func(
+    builder: *mut ffi::GtkBuilder,
+    object: *mut glib::gobject_ffi::GObject,
+    signal_name: *const c_char,
+    handler_name: *const c_char,
+    connect_object: *mut glib::gobject_ffi::GObject,
+    flags: glib::gobject_ffi::GConnectFlags,
+    user_data: glib::ffi::gpointer,
+) {
+    let type_: glib::Type = from_glib(user_data as glib::ffi::GType);
+    let signal: Borrowed<glib::GString> = from_glib_borrow(signal_name);
+    let handler: Borrowed<glib::GString> = from_glib_borrow(handler_name);
+
+    // The template instance is exposed in the builder under the name of its type, and is the
+    // user data of handlers without `object`.
+    let template = ffi::gtk_builder_get_object(builder, type_.name().to_glib_none().0);
+    if template.is_null() {
+        glib::g_critical!("Gtk", "No template instance of {} in the builder", type_);
+        return;
+    }
+
+    let callbacks =
+        &*(glib::gobject_ffi::g_type_get_qdata(type_.to_glib(), TEMPLATE_CALLBACKS_QUARK.to_glib())
+            as *const TemplateCallbacksMap);
+    let callback: &'static TemplateCallback = match callbacks.get(handler.as_str()) {
+        Some(callback) => &**callback,
+        None => {
+            // Like the default of `GtkBuilder`, without the lookup in the symbols of the program
+            let func = ffi::gtk_builder_lookup_callback_symbol(builder, handler_name);
+            if func.is_none() {
+                glib::g_critical!(
+                    "Gtk",
+                    "Could not find signal handler '{}' of the template of {}",
+                    handler.as_str(),
+                    type_
+                );
+            } else if !connect_object.is_null() {
+                glib::gobject_ffi::g_signal_connect_object(
+                    object as glib::ffi::gpointer,
+                    signal_name,
+                    func,
+                    connect_object as glib::ffi::gpointer,
+                    flags,
+                );
+            } else {
+                glib::gobject_ffi::g_signal_connect_data(
+                    object as glib::ffi::gpointer,
+                    signal_name,
+                    func,
+                    template as glib::ffi::gpointer,
+                    None,
+                    flags,
+                );
+            }
+            return;
+        }
+    };
+
+    let object: Borrowed<Object> = from_glib_borrow(object);
+    let return_type: glib::Type =
+        match glib::subclass::SignalId::parse_name(signal.as_str(), object.get_type(), false) {
+            Some((signal_id, _)) => signal_id.query().return_type().into(),
+            None => {
+                glib::g_critical!(
+                    "Gtk",
+                    "Signal '{}' of type '{}' not found",
+                    signal.as_str(),
+                    object.get_type()
+                );
+                return;
+            }
+        };
+
+    let template: Object = from_glib_none(template);
+    let template = template.downgrade();
+    let connect_object = Option::<Object>::from_glib_none(connect_object).map(|o| o.downgrade());
+    let swapped = flags & glib::gobject_ffi::G_CONNECT_SWAPPED != 0;
+    let after = flags & glib::gobject_ffi::G_CONNECT_AFTER != 0;
+    let handler_name = handler.to_string();
+    let signal_name = signal.to_string();
+
+    let res = object.connect_local(signal.as_str(), after, move |values| {
+        let default = if return_type == glib::Type::UNIT {
+            None
+        } else {
+            Some(glib::Value::from_type(return_type))
+        };
+
+        let template = match template.upgrade() {
+            Some(template) => template,
+            None => return default,
+        };
+        let mut args = values.to_vec();
+        if let Some(ref connect_object) = connect_object {
+            match connect_object.upgrade() {
+                Some(connect_object) => args.push(connect_object.to_value()),
+                None => return default,
+            }
+        } else if swapped {
+            // Without `object` the template instance is the user data that gets swapped
+            args.push(template.to_value());
+        }
+        if swapped {
+            let last = args.len() - 1;
+            args.swap(0, last);
+        }
+
+        match callback(&template, &args) {
+            Ok(None) if return_type == glib::Type::UNIT => None,
+            Ok(Some(ret)) if return_type != glib::Type::UNIT && ret.type_().is_a(return_type) => {
+                Some(ret)
+            }
+            Ok(ret) => {
+                glib::g_critical!(
+                    "Gtk",
+                    "Template callback '{}' returned {} for signal '{}' which expects {}",
+                    handler_name,
+                    ret.map_or(glib::Type::UNIT, |ret| ret.type_()),
+                    signal_name,
+                    return_type
+                );
+                default
+            }
+            Err(err) => {
+                glib::g_critical!(
+                    "Gtk",
+                    "Template callback '{}' for signal '{}' failed: {}",
+                    handler_name,
+                    signal_name,
+                    err
+                );
+                default
+            }
+        }
+    });
+
+    if let Err(err) = res {
+        glib::g_critical!("Gtk", "Failed to connect template callback: {}", err);
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Gets argument `index` of a template callback from the signal values, for the
+/// [`template_callbacks`](crate::template_callbacks) attribute macro.
+#[doc(hidden)]
+pub fn template_callback_arg<'a, T: glib::value::FromValueOptional<'a>>(
+    values: &'a [glib::Value],
+    index: usize,
+) -> Result<Option<T>, glib::BoolError> {
+    let value = values.get(index).ok_or_else(|| {
+        glib::bool_error!(
+            "Expected at least {} arguments but got {}",
+            index + 1,
+            values.len()
+        )
+    })?;
+    value.get::<T>().map_err(|_| {
+        glib::bool_error!(
+            "Expected {} for argument {} but got {}",
+            T::static_type(),
+            index,
+            value.type_()
+        )
+    })
+}
+
 #[derive(Debug, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct TemplateChild<T>
@@ -1780,3 +2003,10 @@ where
 pub trait CompositeTemplate: WidgetImpl {
     fn bind_template(klass: &mut Self::Class);
 }
+
+// rustdoc-stripper-ignore-next
+/// Template callbacks of a widget, usually implemented with the
+/// [`template_callbacks`](crate::template_callbacks) attribute macro.
+pub trait TemplateCallbacks: 'static {
+    fn bind_template_callbacks<T: WidgetClassSubclassExt>(klass: &mut T);
+}
@@ -0,0 +1,174 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+// Collects the text of the `<item>` elements of an `<items>` tag
+#[derive(Debug, Default)]
+struct ItemsParser {
+    items: Vec<String>,
+    in_item: bool,
+}
+
+impl BuildableParser for ItemsParser {
+    fn start_element(
+        &mut self,
+        element_name: &str,
+        _attributes: &[(&str, &str)],
+    ) -> Result<(), glib::Error> {
+        match element_name {
+            "items" => Ok(()),
+            "item" => {
+                self.in_item = true;
+                self.items.push(String::new());
+                Ok(())
+            }
+            _ => Err(glib::Error::new(
+                gtk::BuilderError::InvalidTag,
+                &format!("Unexpected element {}", element_name),
+            )),
+        }
+    }
+
+    fn end_element(&mut self, _element_name: &str) -> Result<(), glib::Error> {
+        self.in_item = false;
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), glib::Error> {
+        if self.in_item {
+            self.items.last_mut().unwrap().push_str(text);
+        }
+        Ok(())
+    }
+}
+
+mod imp {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Debug, Default)]
+    pub struct TestBuildable {
+        pub label: RefCell<Option<gtk::Label>>,
+        pub items: RefCell<Vec<String>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestBuildable {
+        const NAME: &'static str = "TestBuildable";
+        type Type = super::TestBuildable;
+        type ParentType = gtk::Box;
+        type Interfaces = (gtk::Buildable,);
+    }
+
+    impl ObjectImpl for TestBuildable {
+        fn constructed(&self, obj: &Self::Type) {
+            self.parent_constructed(obj);
+
+            let label = gtk::Label::new(None);
+            obj.add(&label);
+            *self.label.borrow_mut() = Some(label);
+        }
+    }
+
+    impl WidgetImpl for TestBuildable {}
+    impl ContainerImpl for TestBuildable {}
+    impl BoxImpl for TestBuildable {}
+
+    impl BuildableImpl for TestBuildable {
+        fn custom_tag_start(
+            &self,
+            _buildable: &Self::Type,
+            _builder: &gtk::Builder,
+            child: Option<&glib::Object>,
+            tagname: &str,
+        ) -> Option<Box<dyn BuildableParser>> {
+            if child.is_none() && tagname == "items" {
+                Some(Box::new(ItemsParser::default()))
+            } else {
+                None
+            }
+        }
+
+        fn custom_finished(
+            &self,
+            _buildable: &Self::Type,
+            _builder: &gtk::Builder,
+            _child: Option<&glib::Object>,
+            _tagname: &str,
+            parser: Box<dyn BuildableParser>,
+        ) {
+            let parser = parser.downcast::<ItemsParser>().expect("Unexpected parser");
+            *self.items.borrow_mut() = parser.items;
+        }
+
+        fn get_internal_child(
+            &self,
+            buildable: &Self::Type,
+            builder: &gtk::Builder,
+            childname: &str,
+        ) -> Option<glib::Object> {
+            if childname == "label" {
+                self.label.borrow().clone().map(|label| label.upcast())
+            } else {
+                self.parent_get_internal_child(buildable, builder, childname)
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct TestBuildable(ObjectSubclass<imp::TestBuildable>)
+        @extends gtk::Widget, gtk::Container, gtk::Box, @implements gtk::Buildable;
+}
+
+#[test]
+fn builder() {
+    gtk::init().expect("Failed to initialize GTK");
+    TestBuildable::static_type();
+
+    let builder = gtk::Builder::from_string(
+        r#"
+<interface>
+  <object class="TestBuildable" id="buildable">
+    <child internal-child="label">
+      <object class="GtkLabel">
+        <property name="label">Internal</property>
+      </object>
+    </child>
+    <child>
+      <object class="GtkButton" id="button"/>
+      <packing>
+        <property name="padding">3</property>
+      </packing>
+    </child>
+    <items>
+      <item>first</item>
+      <item>second</item>
+    </items>
+  </object>
+</interface>
+"#,
+    );
+
+    let buildable: TestBuildable = builder.get_object("buildable").unwrap();
+    let button: gtk::Button = builder.get_object("button").unwrap();
+    let imp = imp::TestBuildable::from_instance(&buildable);
+
+    // The custom tag is handled by the subclass
+    assert_eq!(*imp.items.borrow(), vec!["first", "second"]);
+
+    // The internal child is configured by the builder
+    let label = imp.label.borrow().clone().unwrap();
+    assert_eq!(label.get_text(), "Internal");
+
+    // Children and their `<packing>` tags are still handled by the parent implementation
+    assert_eq!(buildable.get_children().len(), 2);
+    assert_eq!(
+        buildable
+            .child_get_property(&button, "padding")
+            .get_some::<u32>(),
+        Ok(3)
+    );
+}
@@ -0,0 +1,125 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+mod imp {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::cell::RefCell;
+
+    #[derive(Debug, Default)]
+    pub struct TestContainer {
+        pub priorities: RefCell<Vec<(gtk::Widget, i32)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestContainer {
+        const NAME: &'static str = "TestContainer";
+        type Type = super::TestContainer;
+        type ParentType = gtk::Box;
+    }
+
+    impl ObjectImpl for TestContainer {}
+    impl WidgetImpl for TestContainer {}
+
+    impl ContainerImpl for TestContainer {
+        fn child_properties() -> &'static [glib::ParamSpec] {
+            static PROPERTIES: Lazy<Vec<glib::ParamSpec>> = Lazy::new(|| {
+                vec![glib::ParamSpec::int(
+                    "priority",
+                    "Priority",
+                    "Priority of the child",
+                    i32::MIN,
+                    i32::MAX,
+                    0,
+                    glib::ParamFlags::READWRITE,
+                )]
+            });
+
+            PROPERTIES.as_ref()
+        }
+
+        fn set_child_property(
+            &self,
+            _container: &Self::Type,
+            child: &gtk::Widget,
+            id: usize,
+            value: &glib::Value,
+            _pspec: &glib::ParamSpec,
+        ) {
+            assert_eq!(id, 1);
+            let priority = value.get_some::<i32>().unwrap();
+            let mut priorities = self.priorities.borrow_mut();
+            match priorities.iter_mut().find(|(widget, _)| widget == child) {
+                Some((_, p)) => *p = priority,
+                None => priorities.push((child.clone(), priority)),
+            }
+        }
+
+        fn get_child_property(
+            &self,
+            _container: &Self::Type,
+            child: &gtk::Widget,
+            id: usize,
+            _pspec: &glib::ParamSpec,
+        ) -> glib::Value {
+            assert_eq!(id, 1);
+            self.priorities
+                .borrow()
+                .iter()
+                .find(|(widget, _)| widget == child)
+                .map_or(0, |&(_, priority)| priority)
+                .to_value()
+        }
+    }
+
+    impl BoxImpl for TestContainer {}
+}
+
+glib::wrapper! {
+    pub struct TestContainer(ObjectSubclass<imp::TestContainer>)
+        @extends gtk::Widget, gtk::Container, gtk::Box;
+}
+
+#[test]
+fn child_properties() {
+    gtk::init().expect("Failed to initialize GTK");
+
+    let container = glib::Object::new::<TestContainer>(&[]).unwrap();
+    let label = gtk::Label::new(None);
+    container.add(&label);
+
+    assert!(container.find_child_property("priority").is_some());
+    assert_eq!(
+        container
+            .child_get_property(&label, "priority")
+            .get_some::<i32>(),
+        Ok(0)
+    );
+
+    container.child_set_property(&label, "priority", &5);
+    assert_eq!(
+        container
+            .child_get_property(&label, "priority")
+            .get_some::<i32>(),
+        Ok(5)
+    );
+    assert_eq!(
+        imp::TestContainer::from_instance(&container)
+            .priorities
+            .borrow()
+            .len(),
+        1
+    );
+
+    // The child properties of the parent class are still handled by it
+    container.child_set_property(&label, "padding", &3u32);
+    assert_eq!(
+        container
+            .child_get_property(&label, "padding")
+            .get_some::<u32>(),
+        Ok(3)
+    );
+}
@@ -0,0 +1,154 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+mod imp {
+    use super::*;
+
+    // Byte index of the character at `position`, or the end of the text for `-1`
+    fn byte_index(text: &str, position: i32) -> usize {
+        if position < 0 {
+            return text.len();
+        }
+        text.char_indices()
+            .nth(position as usize)
+            .map_or(text.len(), |(index, _)| index)
+    }
+
+    #[derive(Debug, Default)]
+    pub struct TestEditable {
+        pub text: RefCell<String>,
+        pub position: Cell<i32>,
+        pub inserted: RefCell<Vec<(String, i32)>>,
+        pub deleted: RefCell<Vec<(i32, i32)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestEditable {
+        const NAME: &'static str = "TestEditable";
+        type Type = super::TestEditable;
+        type ParentType = gtk::Widget;
+        type Interfaces = (gtk::Editable,);
+    }
+
+    impl ObjectImpl for TestEditable {}
+    impl WidgetImpl for TestEditable {}
+
+    impl EditableImpl for TestEditable {
+        fn insert_text(&self, editable: &Self::Type, text: &str, position: &mut i32) {
+            self.inserted
+                .borrow_mut()
+                .push((text.to_string(), *position));
+            {
+                let mut buffer = self.text.borrow_mut();
+                let index = byte_index(&buffer, *position);
+                buffer.insert_str(index, text);
+            }
+            *position += text.chars().count() as i32;
+            editable.emit_by_name("changed", &[]).unwrap();
+        }
+
+        fn delete_text(&self, editable: &Self::Type, start_pos: i32, end_pos: i32) {
+            self.deleted.borrow_mut().push((start_pos, end_pos));
+            {
+                let mut buffer = self.text.borrow_mut();
+                let start = byte_index(&buffer, start_pos);
+                let end = byte_index(&buffer, end_pos);
+                buffer.replace_range(start..end, "");
+            }
+            editable.emit_by_name("changed", &[]).unwrap();
+        }
+
+        fn get_chars(&self, _editable: &Self::Type, start_pos: i32, end_pos: i32) -> glib::GString {
+            let buffer = self.text.borrow();
+            let start = byte_index(&buffer, start_pos);
+            let end = byte_index(&buffer, end_pos);
+            buffer[start..end].to_string().into()
+        }
+
+        fn set_selection_bounds(&self, _editable: &Self::Type, _start_pos: i32, end_pos: i32) {
+            self.position.set(end_pos);
+        }
+
+        fn get_selection_bounds(&self, _editable: &Self::Type) -> Option<(i32, i32)> {
+            None
+        }
+
+        fn set_position(&self, _editable: &Self::Type, position: i32) {
+            self.position.set(position);
+        }
+
+        fn get_position(&self, _editable: &Self::Type) -> i32 {
+            self.position.get()
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct TestEditable(ObjectSubclass<imp::TestEditable>)
+        @extends gtk::Widget, @implements gtk::Editable;
+}
+
+#[test]
+fn insert_and_delete_text() {
+    gtk::init().expect("Failed to initialize GTK");
+
+    let editable = glib::Object::new::<TestEditable>(&[]).unwrap();
+    let imp = imp::TestEditable::from_instance(&editable);
+
+    let signals = Rc::new(RefCell::new(Vec::new()));
+    let signals_clone = signals.clone();
+    editable.connect_insert_text(move |_, text, position| {
+        signals_clone
+            .borrow_mut()
+            .push(format!("insert-text {} {}", text, position));
+    });
+    let signals_clone = signals.clone();
+    editable.connect_delete_text(move |_, start_pos, end_pos| {
+        signals_clone
+            .borrow_mut()
+            .push(format!("delete-text {} {}", start_pos, end_pos));
+    });
+    let signals_clone = signals.clone();
+    editable.connect_changed(move |_| {
+        signals_clone.borrow_mut().push("changed".to_string());
+    });
+
+    // Inserting emits `insert-text`, whose default handler inserts the text
+    let mut position = 0;
+    editable.insert_text("Hello", &mut position);
+    assert_eq!(position, 5);
+    editable.insert_text(" wörld", &mut position);
+    assert_eq!(position, 11);
+    assert_eq!(*imp.text.borrow(), "Hello wörld");
+    assert_eq!(
+        *imp.inserted.borrow(),
+        vec![("Hello".to_string(), 0), (" wörld".to_string(), 5)]
+    );
+
+    assert_eq!(editable.get_chars(0, 5).as_deref(), Some("Hello"));
+    assert_eq!(editable.get_chars(6, -1).as_deref(), Some("wörld"));
+
+    // Deleting emits `delete-text`, whose default handler deletes the text
+    editable.delete_text(5, -1);
+    assert_eq!(*imp.text.borrow(), "Hello");
+    assert_eq!(*imp.deleted.borrow(), vec![(5, -1)]);
+    assert_eq!(editable.get_chars(0, -1).as_deref(), Some("Hello"));
+
+    assert_eq!(
+        *signals.borrow(),
+        vec![
+            "insert-text Hello 0",
+            "changed",
+            "insert-text  wörld 5",
+            "changed",
+            "delete-text 5 -1",
+            "changed",
+        ]
+    );
+}
@@ -0,0 +1,202 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gtk::glib;
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{Adjustment, Orientation, ScrollablePolicy};
+
+mod imp {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Debug)]
+    pub struct TestScrollable {
+        pub label: RefCell<Option<String>>,
+        pub orientation: Cell<Orientation>,
+        pub hadjustment: RefCell<Option<Adjustment>>,
+        pub vadjustment: RefCell<Option<Adjustment>>,
+        pub hscroll_policy: Cell<ScrollablePolicy>,
+        pub vscroll_policy: Cell<ScrollablePolicy>,
+    }
+
+    impl Default for TestScrollable {
+        fn default() -> Self {
+            TestScrollable {
+                label: RefCell::new(None),
+                orientation: Cell::new(Orientation::Horizontal),
+                hadjustment: RefCell::new(None),
+                vadjustment: RefCell::new(None),
+                hscroll_policy: Cell::new(ScrollablePolicy::Minimum),
+                vscroll_policy: Cell::new(ScrollablePolicy::Minimum),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestScrollable {
+        const NAME: &'static str = "TestScrollable";
+        type Type = super::TestScrollable;
+        type ParentType = gtk::Widget;
+        type Interfaces = (gtk::Orientable, gtk::Scrollable);
+    }
+
+    impl ObjectImpl for TestScrollable {
+        fn properties() -> &'static [glib::ParamSpec] {
+            static PROPERTIES: Lazy<Vec<glib::ParamSpec>> = Lazy::new(|| {
+                vec![glib::ParamSpec::string(
+                    "label",
+                    "Label",
+                    "Label",
+                    None,
+                    glib::ParamFlags::READWRITE,
+                )]
+            });
+
+            PROPERTIES.as_ref()
+        }
+
+        fn set_property(
+            &self,
+            _obj: &Self::Type,
+            _id: usize,
+            value: &glib::Value,
+            pspec: &glib::ParamSpec,
+        ) {
+            match pspec.get_name() {
+                "label" => *self.label.borrow_mut() = value.get().unwrap(),
+                name => panic!("Unexpected property {}", name),
+            }
+        }
+
+        fn get_property(
+            &self,
+            _obj: &Self::Type,
+            _id: usize,
+            pspec: &glib::ParamSpec,
+        ) -> glib::Value {
+            match pspec.get_name() {
+                "label" => self.label.borrow().to_value(),
+                name => panic!("Unexpected property {}", name),
+            }
+        }
+    }
+
+    impl WidgetImpl for TestScrollable {}
+
+    impl OrientableImpl for TestScrollable {
+        fn get_orientation(&self, _orientable: &Self::Type) -> Orientation {
+            self.orientation.get()
+        }
+
+        fn set_orientation(&self, _orientable: &Self::Type, orientation: Orientation) {
+            self.orientation.set(orientation);
+        }
+    }
+
+    impl ScrollableImpl for TestScrollable {
+        fn get_hadjustment(&self, _scrollable: &Self::Type) -> Option<Adjustment> {
+            self.hadjustment.borrow().clone()
+        }
+
+        fn set_hadjustment(&self, _scrollable: &Self::Type, hadjustment: Option<Adjustment>) {
+            *self.hadjustment.borrow_mut() = hadjustment;
+        }
+
+        fn get_vadjustment(&self, _scrollable: &Self::Type) -> Option<Adjustment> {
+            self.vadjustment.borrow().clone()
+        }
+
+        fn set_vadjustment(&self, _scrollable: &Self::Type, vadjustment: Option<Adjustment>) {
+            *self.vadjustment.borrow_mut() = vadjustment;
+        }
+
+        fn get_hscroll_policy(&self, _scrollable: &Self::Type) -> ScrollablePolicy {
+            self.hscroll_policy.get()
+        }
+
+        fn set_hscroll_policy(&self, _scrollable: &Self::Type, policy: ScrollablePolicy) {
+            self.hscroll_policy.set(policy);
+        }
+
+        fn get_vscroll_policy(&self, _scrollable: &Self::Type) -> ScrollablePolicy {
+            self.vscroll_policy.get()
+        }
+
+        fn set_vscroll_policy(&self, _scrollable: &Self::Type, policy: ScrollablePolicy) {
+            self.vscroll_policy.set(policy);
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct TestScrollable(ObjectSubclass<imp::TestScrollable>)
+        @extends gtk::Widget, @implements gtk::Orientable, gtk::Scrollable;
+}
+
+#[test]
+fn interface_properties() {
+    gtk::init().expect("Failed to initialize GTK");
+
+    let widget = glib::Object::new::<TestScrollable>(&[]).unwrap();
+    let imp = imp::TestScrollable::from_instance(&widget);
+
+    // Own property
+    widget.set_property("label", "label").unwrap();
+    assert_eq!(imp.label.borrow().as_deref(), Some("label"));
+    assert_eq!(
+        widget.get_property("label").unwrap().get::<String>(),
+        Ok(Some("label".to_string()))
+    );
+
+    // Overridden interface properties
+    widget
+        .set_property("orientation", Orientation::Vertical)
+        .unwrap();
+    assert_eq!(imp.orientation.get(), Orientation::Vertical);
+    assert_eq!(widget.get_orientation(), Orientation::Vertical);
+    assert_eq!(
+        widget
+            .get_property("orientation")
+            .unwrap()
+            .get_some::<Orientation>(),
+        Ok(Orientation::Vertical)
+    );
+
+    widget
+        .set_property("vscroll-policy", ScrollablePolicy::Natural)
+        .unwrap();
+    assert_eq!(imp.vscroll_policy.get(), ScrollablePolicy::Natural);
+    assert_eq!(widget.get_vscroll_policy(), ScrollablePolicy::Natural);
+    assert_eq!(widget.get_hscroll_policy(), ScrollablePolicy::Minimum);
+
+    let adjustment = Adjustment::new(0.0, 0.0, 100.0, 1.0, 10.0, 10.0);
+    widget.set_hadjustment(Some(&adjustment));
+    assert_eq!(imp.hadjustment.borrow().as_ref(), Some(&adjustment));
+    assert_eq!(
+        widget
+            .get_property("hadjustment")
+            .unwrap()
+            .get::<Adjustment>(),
+        Ok(Some(adjustment))
+    );
+}
+
+#[test]
+fn scrolled_window_sets_adjustments() {
+    gtk::init().expect("Failed to initialize GTK");
+
+    let widget = glib::Object::new::<TestScrollable>(&[]).unwrap();
+    let imp = imp::TestScrollable::from_instance(&widget);
+    let window = gtk::ScrolledWindow::new(gtk::NONE_ADJUSTMENT, gtk::NONE_ADJUSTMENT);
+    window.add(&widget);
+
+    assert_eq!(
+        imp.hadjustment.borrow().as_ref(),
+        Some(&window.get_hadjustment())
+    );
+    assert_eq!(
+        imp.vadjustment.borrow().as_ref(),
+        Some(&window.get_vadjustment())
+    );
+}
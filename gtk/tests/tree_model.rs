@@ -0,0 +1,103 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{glib, TreePath};
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct TestListModel;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestListModel {
+        const NAME: &'static str = "TestListModel";
+        type Type = super::TestListModel;
+        type ParentType = glib::Object;
+        type Interfaces = (gtk::TreeModel,);
+    }
+
+    impl ObjectImpl for TestListModel {}
+
+    impl TreeModelImpl for TestListModel {
+        type Iter = usize;
+
+        fn get_flags(&self, _tree_model: &Self::Type) -> gtk::TreeModelFlags {
+            gtk::TreeModelFlags::LIST_ONLY
+        }
+
+        fn get_n_columns(&self, _tree_model: &Self::Type) -> i32 {
+            1
+        }
+
+        fn get_column_type(&self, _tree_model: &Self::Type, _index: i32) -> glib::Type {
+            glib::Type::U32
+        }
+
+        fn get_iter(&self, _tree_model: &Self::Type, path: &TreePath) -> Option<usize> {
+            match path.get_indices()[..] {
+                [index] if (0..3).contains(&index) => Some(index as usize),
+                _ => None,
+            }
+        }
+
+        fn get_path(&self, _tree_model: &Self::Type, iter: usize) -> TreePath {
+            TreePath::from_indicesv(&[iter as i32])
+        }
+
+        fn get_value(&self, _tree_model: &Self::Type, iter: usize, _column: i32) -> glib::Value {
+            (iter as u32).to_value()
+        }
+
+        fn iter_next(&self, _tree_model: &Self::Type, iter: usize) -> Option<usize> {
+            Some(iter + 1).filter(|&next| next < 3)
+        }
+
+        fn iter_n_children(&self, _tree_model: &Self::Type, iter: Option<usize>) -> i32 {
+            if iter.is_none() {
+                3
+            } else {
+                0
+            }
+        }
+
+        fn iter_nth_child(
+            &self,
+            _tree_model: &Self::Type,
+            parent: Option<usize>,
+            n: i32,
+        ) -> Option<usize> {
+            match parent {
+                None if (0..3).contains(&n) => Some(n as usize),
+                _ => None,
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct TestListModel(ObjectSubclass<imp::TestListModel>)
+        @implements gtk::TreeModel;
+}
+
+#[test]
+fn iters_of_other_models_are_rejected() {
+    gtk::init().expect("Failed to initialize GTK");
+
+    let model = glib::Object::new::<TestListModel>(&[]).unwrap();
+    let other = glib::Object::new::<TestListModel>(&[]).unwrap();
+    let imp = imp::TestListModel::from_instance(&model);
+    let other_imp = imp::TestListModel::from_instance(&other);
+
+    let iter = model.get_iter_first().unwrap();
+    assert_eq!(imp.from_tree_iter(&model, &iter), Some(0));
+    assert_eq!(other_imp.from_tree_iter(&other, &iter), None);
+
+    let iter = imp.to_tree_iter(&model, 2);
+    assert_eq!(model.get_value(&iter, 0).get_some::<u32>(), Ok(2));
+    assert_eq!(other_imp.from_tree_iter(&other, &iter), None);
+
+    imp.invalidate_iters(&model);
+    assert_eq!(imp.from_tree_iter(&model, &iter), None);
+}
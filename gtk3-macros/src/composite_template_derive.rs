@@ -1,13 +1,14 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 use proc_macro2::TokenStream;
-use proc_macro_error::{abort, abort_call_site};
+use proc_macro_error::{abort, abort_call_site, emit_call_site_error};
 use quote::quote;
 use syn::Data;
 
 use std::string::ToString;
 
 use crate::attribute_parser::*;
+use crate::template_validation::*;
 use crate::util::*;
 
 fn gen_set_template(source: TemplateSource) -> TokenStream {
@@ -25,12 +26,25 @@ fn gen_set_template(source: TemplateSource) -> TokenStream {
     }
 }
 
-fn gen_template_child_bindings(fields: &syn::Fields) -> TokenStream {
+// `ObjectSubclass::NAME` is only known once the types are resolved, so the template's `class`
+// is checked when the class is initialized.
+fn gen_template_class_check(template: &Template) -> TokenStream {
+    let crate_ident = crate_ident_new();
+    let class = &template.class;
+
+    quote! {
+        let name = <Self as #crate_ident::glib::subclass::prelude::ObjectSubclass>::NAME;
+        assert!(
+            name == #class,
+            "The class of the template is '{}' but the type is named '{}'",
+            #class,
+            name,
+        );
+    }
+}
+
+fn gen_template_child_bindings(attributed_fields: &[AttributedField]) -> TokenStream {
     let crate_ident = crate_ident_new();
-    let attributed_fields = match parse_fields(&fields) {
-        Ok(fields) => fields,
-        Err(err) => abort!(err.span(), err.to_string()),
-    };
 
     let recurse = attributed_fields.iter().map(|field| match field.attr.ty {
         FieldAttributeType::TemplateChild => {
@@ -68,18 +82,39 @@ pub fn impl_composite_template(input: &syn::DeriveInput) -> TokenStream {
         ),
     };
 
-    let set_template = gen_set_template(source);
-
     let fields = match input.data {
         Data::Struct(ref s) => &s.fields,
         _ => abort_call_site!("derive(CompositeTemplate) only supports structs"),
     };
+    let attributed_fields = match parse_fields(&fields) {
+        Ok(fields) => fields,
+        Err(err) => abort!(err.span(), err.to_string()),
+    };
 
-    let template_children = gen_template_child_bindings(&fields);
+    let (checks, class_check) = match template_contents(&source).map(|xml| parse_template(&xml)) {
+        Some(Ok(template)) => {
+            validate_template_children(&template, &attributed_fields);
+            (
+                gen_template_checks(&template, name, &attributed_fields),
+                gen_template_class_check(&template),
+            )
+        }
+        Some(Err(err)) => {
+            emit_call_site_error!("invalid template: {}", err);
+            (TokenStream::new(), TokenStream::new())
+        }
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+
+    let set_template = gen_set_template(source);
+    let template_children = gen_template_child_bindings(&attributed_fields);
 
     quote! {
+        #checks
+
         impl #crate_ident::subclass::widget::CompositeTemplate for #name {
             fn bind_template(klass: &mut Self::Class) {
+                #class_check
                 #set_template
 
                 unsafe {
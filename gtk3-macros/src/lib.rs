@@ -2,11 +2,13 @@
 
 mod attribute_parser;
 mod composite_template_derive;
+mod template_callbacks_attribute;
+mod template_validation;
 mod util;
 
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, ItemImpl};
 
 /// Derive macro for using a composite template in a widget.
 ///
@@ -16,6 +18,13 @@ use syn::{parse_macro_input, DeriveInput};
 /// The `template_child` attribute is used to mark all internal widgets
 /// we need to have programmatic access to.
 ///
+/// Templates given as a `string`, or as a `file` found relative to the crate
+/// root or its `src` directory, are checked at compile time: every
+/// `template_child` must exist in the template, its GTK class must be a
+/// subtype of the child's type, and the template's `parent` must match
+/// `ObjectSubclass::ParentType`. The template's `class` is checked against
+/// `ObjectSubclass::NAME` when the class is initialized.
+///
 /// # Example
 ///
 /// Specify that `MyWidget` is using a composite template and load the
@@ -61,3 +70,57 @@ pub fn composite_template_derive(input: TokenStream) -> TokenStream {
     let gen = composite_template_derive::impl_composite_template(&input);
     gen.into()
 }
+
+/// Attribute macro for registering the signal handlers of a composite template.
+///
+/// Put it on an `impl` block of the widget's implementation struct and mark
+/// the handlers with `#[template_callback]`. The handler is named after the
+/// method, unless a different one is given with
+/// `#[template_callback(name = "...")]`.
+///
+/// Each handler is connected with a closure, so its arguments are taken out
+/// of the values of the signal, starting with the emitter and followed by the
+/// `object` of the `<signal>` element, if any. For `swapped` signals the emitter
+/// is exchanged with the `object`, or with the template instance if there is
+/// none. Objects, boxed types and strings are taken as `&T`, or as
+/// `Option<&T>` if they can be `NULL`; other types are taken by value.
+/// Trailing values can be left out. The return value, if any, is converted
+/// with `ToValue`.
+///
+/// Handlers taking `&self` are called on the template instance. Arguments of
+/// the wrong type and return values that don't match the signal are reported
+/// with a critical warning when the signal is emitted.
+///
+/// # Example
+///
+/// ```compile_fail
+/// #[gtk::template_callbacks]
+/// impl MyWidget {
+///     #[template_callback]
+///     fn button_clicked(&self, button: &gtk::Button) {
+///         button.set_label("Clicked");
+///     }
+///
+///     #[template_callback(name = "entry_changed")]
+///     fn on_changed(entry: &gtk::Entry) {
+///         println!("{}", entry.get_text());
+///     }
+/// }
+/// ```
+///
+/// The callbacks are then registered in `class_init`, after binding the
+/// template.
+///
+/// ```compile_fail
+/// fn class_init(klass: &mut Self::Class) {
+///     Self::bind_template(klass);
+///     Self::bind_template_callbacks(klass);
+/// }
+/// ```
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn template_callbacks(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemImpl);
+    let gen = template_callbacks_attribute::impl_template_callbacks(input);
+    gen.into()
+}
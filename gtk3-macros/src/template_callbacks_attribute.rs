@@ -0,0 +1,263 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{FnArg, ImplItem, ImplItemMethod, ItemImpl, Lit, Meta, NestedMeta, ReturnType, Type};
+
+use crate::util::*;
+
+// parse the optional `name = "..."` of a #[template_callback] attribute
+fn parse_callback_name(attr: &syn::Attribute) -> Option<String> {
+    if attr.tokens.is_empty() {
+        return None;
+    }
+
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        _ => abort!(
+            attr,
+            "expected #[template_callback] or #[template_callback(name = \"...\")]"
+        ),
+    };
+
+    let mut name = None;
+    for nested in &list.nested {
+        match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => match &nv.lit {
+                Lit::Str(s) => name = Some(s.value()),
+                lit => abort!(lit, "the callback name must be a string"),
+            },
+            meta => abort!(meta, "unknown template_callback argument"),
+        }
+    }
+
+    name
+}
+
+// How an argument is taken out of the signal values and passed on to the handler.
+enum ArgKind {
+    Owned(Type),
+    Ref(Type),
+    OptionRef(Type),
+}
+
+fn is_str(ty: &Type) -> bool {
+    matches!(ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident("str"))
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn arg_kind(ty: &Type) -> ArgKind {
+    match ty {
+        Type::Reference(r) => {
+            if r.mutability.is_some() {
+                abort!(r, "template callback arguments can't be mutable references");
+            }
+            ArgKind::Ref((*r.elem).clone())
+        }
+        _ => match option_inner(ty) {
+            Some(Type::Reference(r)) => {
+                if r.mutability.is_some() {
+                    abort!(r, "template callback arguments can't be mutable references");
+                }
+                ArgKind::OptionRef((*r.elem).clone())
+            }
+            Some(_) => abort!(
+                ty,
+                "only references can be optional in template callback arguments"
+            ),
+            None => ArgKind::Owned(ty.clone()),
+        },
+    }
+}
+
+// Generates the registration of a handler, which extracts the arguments from the values of
+// the signal and converts the return value.
+fn gen_callback(method: &ImplItemMethod, name: &str) -> TokenStream {
+    let crate_ident = crate_ident_new();
+    let sig = &method.sig;
+    let method_ident = &sig.ident;
+
+    if sig.asyncness.is_some() {
+        abort!(sig.asyncness, "template callbacks can't be async");
+    }
+    if !sig.generics.params.is_empty() {
+        abort!(sig.generics, "template callbacks can't be generic");
+    }
+
+    let mut has_self = false;
+    let mut extract = Vec::new();
+    let mut call_args = Vec::new();
+
+    for input in &sig.inputs {
+        let ty = match input {
+            FnArg::Receiver(receiver) => {
+                if receiver.reference.is_none() || receiver.mutability.is_some() {
+                    abort!(receiver, "template callbacks can only take `&self`");
+                }
+                has_self = true;
+                continue;
+            }
+            FnArg::Typed(pat) => &*pat.ty,
+        };
+
+        let index = call_args.len();
+        let arg = format_ident!("arg{}", index);
+        // Strings are borrowed from the values, other types are taken out of them
+        let (value_type, non_null, pass) = match arg_kind(ty) {
+            ArgKind::Owned(ty) => (quote! { #ty }, true, quote! { #arg }),
+            ArgKind::Ref(ty) if is_str(&ty) => (quote! { &str }, true, quote! { #arg }),
+            ArgKind::Ref(ty) => (quote! { #ty }, true, quote! { &#arg }),
+            ArgKind::OptionRef(ty) if is_str(&ty) => (quote! { &str }, false, quote! { #arg }),
+            ArgKind::OptionRef(ty) => (quote! { #ty }, false, quote! { #arg.as_ref() }),
+        };
+
+        let get = quote! {
+            #crate_ident::subclass::widget::template_callback_arg::<#value_type>(values, #index)?
+        };
+        extract.push(if non_null {
+            quote! {
+                let #arg = #get.ok_or_else(|| {
+                    #crate_ident::glib::bool_error!("Unexpected NULL for argument {}", #index)
+                })?;
+            }
+        } else {
+            quote! {
+                let #arg = #get;
+            }
+        });
+        call_args.push(pass);
+    }
+
+    let call = if has_self {
+        quote! {
+            let template = #crate_ident::glib::Cast::downcast_ref::<
+                <Self as #crate_ident::glib::subclass::prelude::ObjectSubclass>::Type,
+            >(template)
+            .ok_or_else(|| {
+                #crate_ident::glib::bool_error!(
+                    "Called for an instance of the wrong type {}",
+                    #crate_ident::glib::ObjectExt::get_type(template)
+                )
+            })?;
+            let imp = <Self as #crate_ident::glib::subclass::prelude::ObjectSubclassExt>::from_instance(template);
+            imp.#method_ident(#(#call_args),*)
+        }
+    } else {
+        quote! {
+            Self::#method_ident(#(#call_args),*)
+        }
+    };
+
+    let returns_value = match &sig.output {
+        ReturnType::Default => false,
+        ReturnType::Type(_, ty) => !matches!(&**ty, Type::Tuple(t) if t.elems.is_empty()),
+    };
+    let ret = if returns_value {
+        quote! {
+            let ret = { #call };
+            Ok(Some(#crate_ident::glib::ToValue::to_value(&ret)))
+        }
+    } else {
+        quote! {
+            #call;
+            Ok(None)
+        }
+    };
+
+    // Avoid unused variable warnings in the generated closure
+    let template = if has_self {
+        format_ident!("template")
+    } else {
+        format_ident!("_template")
+    };
+    let values = if extract.is_empty() {
+        format_ident!("_values")
+    } else {
+        format_ident!("values")
+    };
+
+    quote! {
+        <T as #crate_ident::subclass::widget::WidgetClassSubclassExt>::bind_template_callback(
+            klass,
+            #name,
+            |#template: &#crate_ident::glib::Object, #values: &[#crate_ident::glib::Value]| {
+                #(#extract)*
+                #ret
+            },
+        );
+    }
+}
+
+pub fn impl_template_callbacks(mut input: ItemImpl) -> TokenStream {
+    let crate_ident = crate_ident_new();
+
+    if let Some((_, path, _)) = &input.trait_ {
+        abort!(
+            path,
+            "#[template_callbacks] must be used on an inherent impl block"
+        );
+    }
+
+    let mut callbacks = Vec::new();
+    for item in input.items.iter_mut() {
+        let method = match item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+
+        let pos = match method
+            .attrs
+            .iter()
+            .position(|a| a.path.is_ident("template_callback"))
+        {
+            Some(pos) => pos,
+            None => continue,
+        };
+        let attr = method.attrs.remove(pos);
+        let name = parse_callback_name(&attr).unwrap_or_else(|| method.sig.ident.to_string());
+
+        if callbacks.iter().any(|(n, _)| n == &name) {
+            abort!(
+                attr.span(),
+                "template callback '{}' is registered more than once",
+                name
+            );
+        }
+
+        let callback = gen_callback(method, &name);
+        callbacks.push((name, callback));
+    }
+
+    let self_ty = &input.self_ty;
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let binds = callbacks.iter().map(|(_, bind)| bind);
+
+    quote! {
+        #input
+
+        impl #impl_generics #crate_ident::subclass::widget::TemplateCallbacks for #self_ty #where_clause {
+            fn bind_template_callbacks<T: #crate_ident::subclass::widget::WidgetClassSubclassExt>(klass: &mut T) {
+                #(#binds)*
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::{emit_call_site_error, emit_error};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use quote::{format_ident, quote_spanned};
+use syn::{GenericArgument, Ident, PathArguments, Type};
+
+use crate::attribute_parser::*;
+use crate::util::*;
+
+pub struct Template {
+    pub class: String,
+    pub parent: Option<String>,
+    // object id -> class
+    pub objects: HashMap<String, String>,
+}
+
+fn get_attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>> {
+    for attr in element.attributes() {
+        let attr = attr?;
+        if attr.key == name {
+            let value = attr.unescaped_value()?;
+            return Ok(Some(String::from_utf8(value.into_owned())?));
+        }
+    }
+    Ok(None)
+}
+
+pub fn parse_template(xml: &str) -> Result<Template> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut template = None;
+    let mut objects = HashMap::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf)? {
+            Event::Start(ref e) | Event::Empty(ref e) => match e.name() {
+                b"template" => {
+                    let class = match get_attribute(e, b"class")? {
+                        Some(class) => class,
+                        None => bail!("<template> is missing the 'class' attribute"),
+                    };
+                    template = Some((class, get_attribute(e, b"parent")?));
+                }
+                b"object" => {
+                    if let Some(id) = get_attribute(e, b"id")? {
+                        let class = match get_attribute(e, b"class")? {
+                            Some(class) => class,
+                            None => {
+                                bail!("<object id=\"{}\"> is missing the 'class' attribute", id)
+                            }
+                        };
+                        objects.insert(id, class);
+                    }
+                }
+                _ => (),
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    match template {
+        Some((class, parent)) => Ok(Template {
+            class,
+            parent,
+            objects,
+        }),
+        None => bail!("no <template> element found"),
+    }
+}
+
+// Returns the contents of the template if it is available at compile time. `file` templates
+// are looked up relative to the crate root and its `src` directory, as the path of the source
+// file `include_bytes!` resolves against is not known to the macro. Templates that aren't found
+// there are left to `include_bytes!` and not validated.
+pub fn template_contents(source: &TemplateSource) -> Option<String> {
+    match source {
+        TemplateSource::String(template) => Some(template.clone()),
+        TemplateSource::File(file) => {
+            let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR")?);
+            let path = [manifest_dir.join(file), manifest_dir.join("src").join(file)]
+                .iter()
+                .find(|path| path.is_file())?
+                .clone();
+            match std::fs::read_to_string(&path) {
+                Ok(template) => Some(template),
+                Err(err) => {
+                    emit_call_site_error!(
+                        "failed to read template file '{}': {}",
+                        path.display(),
+                        err
+                    );
+                    None
+                }
+            }
+        }
+        TemplateSource::Resource(_) => None,
+    }
+}
+
+fn template_child_id(field: &AttributedField) -> String {
+    let mut id = field.ident.to_string();
+    field.attr.args.iter().for_each(|arg| match arg {
+        FieldAttributeArg::Id(value) => {
+            id = value.clone();
+        }
+    });
+    id
+}
+
+// Template children that are missing from the template. Their types are checked by the code
+// generated in `gen_template_checks`, as they can also be supertypes or interfaces of the classes
+// in the template.
+fn check_template_children(template: &Template, fields: &[AttributedField]) -> Vec<(Span, String)> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let FieldAttributeType::TemplateChild = field.attr.ty;
+
+            let id = template_child_id(field);
+            if template.objects.contains_key(&id) {
+                None
+            } else {
+                Some((
+                    field.attr.span,
+                    format!(
+                        "template child '{}' does not exist in the template of '{}'",
+                        id, template.class
+                    ),
+                ))
+            }
+        })
+        .collect()
+}
+
+pub fn validate_template_children(template: &Template, fields: &[AttributedField]) {
+    for (span, message) in check_template_children(template, fields) {
+        emit_error!(span, "{}", message);
+    }
+}
+
+// Type `T` of a `TemplateChild<T>` field.
+fn template_child_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(p) => p.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// The Rust type of the GTK classes in a template, `GtkFoo` being `gtk::Foo`.
+fn gtk_type(class: &str) -> Option<Ident> {
+    match class.strip_prefix("Gtk") {
+        Some(name) if !name.is_empty() => Some(format_ident!("{}", name)),
+        _ => None,
+    }
+}
+
+// Checks of the template's `parent` against `ObjectSubclass::ParentType`, and of the template
+// children against the classes of their objects, which are only known once the types are
+// resolved.
+pub fn gen_template_checks(
+    template: &Template,
+    name: &Ident,
+    fields: &[AttributedField],
+) -> TokenStream {
+    let crate_ident = crate_ident_new();
+    let span = Span::call_site();
+
+    let mut checks = match template.parent.as_deref().and_then(gtk_type) {
+        Some(parent) => quote_spanned! {span=>
+            // Fails to type check if the template parent is not `ObjectSubclass::ParentType`.
+            const _: fn() = || {
+                let _: std::marker::PhantomData<#crate_ident::#parent> = std::marker::PhantomData::<
+                    <#name as #crate_ident::glib::subclass::prelude::ObjectSubclass>::ParentType,
+                >;
+            };
+        },
+        None => TokenStream::new(),
+    };
+
+    for field in fields {
+        let FieldAttributeType::TemplateChild = field.attr.ty;
+
+        let class = match template
+            .objects
+            .get(&template_child_id(field))
+            .and_then(|class| gtk_type(class))
+        {
+            Some(class) => class,
+            None => continue,
+        };
+        let ty = match template_child_type(&field.ty) {
+            Some(ty) => ty,
+            None => continue,
+        };
+
+        checks.extend(quote_spanned! {field.attr.span=>
+            // Fails to type check if the object in the template is not a `T`.
+            const _: fn() = || {
+                fn assert_is_a<T: #crate_ident::glib::IsA<U>, U: #crate_ident::glib::ObjectType>() {}
+                assert_is_a::<#crate_ident::#class, #ty>();
+            };
+        });
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"
+<interface>
+  <template class="MyDialog" parent="GtkDialog">
+    <child internal-child="vbox">
+      <object class="GtkBox">
+        <child>
+          <object class="GtkCheckButton" id="check_button"/>
+        </child>
+        <child>
+          <object class="GtkButtonBox" id="button_box"/>
+        </child>
+      </object>
+    </child>
+  </template>
+</interface>
+"#;
+
+    fn template_fields(input: syn::DeriveInput) -> Vec<AttributedField> {
+        match input.data {
+            syn::Data::Struct(s) => parse_fields(&s.fields).unwrap(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn errors(fields: syn::DeriveInput) -> Vec<String> {
+        let template = parse_template(TEMPLATE).unwrap();
+        check_template_children(&template, &template_fields(fields))
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect()
+    }
+
+    #[test]
+    fn parse() {
+        let template = parse_template(TEMPLATE).unwrap();
+        assert_eq!(template.class, "MyDialog");
+        assert_eq!(template.parent.as_deref(), Some("GtkDialog"));
+        assert_eq!(template.objects.len(), 2);
+        assert_eq!(template.objects["check_button"], "GtkCheckButton");
+        assert_eq!(template.objects["button_box"], "GtkButtonBox");
+
+        assert!(parse_template("<interface/>").is_err());
+        assert!(parse_template(r#"<interface><template parent="GtkBox"/></interface>"#).is_err());
+    }
+
+    #[test]
+    fn matching_children() {
+        assert!(errors(syn::parse_quote! {
+            struct MyDialog {
+                #[template_child]
+                check_button: TemplateChild<gtk::CheckButton>,
+                #[template_child(id = "button_box")]
+                buttons: TemplateChild<gtk::ButtonBox>,
+            }
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn supertype_children() {
+        assert!(errors(syn::parse_quote! {
+            struct MyDialog {
+                #[template_child]
+                check_button: TemplateChild<gtk::ToggleButton>,
+                #[template_child]
+                button_box: TemplateChild<gtk::Orientable>,
+            }
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn missing_children() {
+        assert_eq!(
+            errors(syn::parse_quote! {
+                struct MyDialog {
+                    #[template_child]
+                    check_button: TemplateChild<gtk::CheckButton>,
+                    #[template_child(id = "label")]
+                    button_box: TemplateChild<gtk::Label>,
+                    #[template_child]
+                    entry: TemplateChild<gtk::Entry>,
+                }
+            }),
+            vec![
+                "template child 'label' does not exist in the template of 'MyDialog'",
+                "template child 'entry' does not exist in the template of 'MyDialog'",
+            ]
+        );
+    }
+
+    #[test]
+    fn child_types() {
+        assert_eq!(gtk_type("GtkCheckButton").unwrap(), "CheckButton");
+        assert!(gtk_type("Gtk").is_none());
+        assert!(gtk_type("MyWidget").is_none());
+
+        let ty: Type = syn::parse_quote!(TemplateChild<gtk::CheckButton>);
+        let inner = template_child_type(&ty).unwrap();
+        assert_eq!(quote::quote!(#inner).to_string(), "gtk :: CheckButton");
+    }
+}
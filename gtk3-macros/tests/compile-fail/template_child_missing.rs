@@ -0,0 +1,21 @@
+use gtk::subclass::prelude::*;
+use gtk::CompositeTemplate;
+
+#[derive(Debug, Default, CompositeTemplate)]
+#[template(string = r#"
+<interface>
+  <template class="MyWidget" parent="GtkBox">
+    <child>
+      <object class="GtkLabel" id="label"/>
+    </child>
+  </template>
+</interface>
+"#)]
+struct MyWidget {
+    #[template_child]
+    label: TemplateChild<gtk::Label>,
+    #[template_child(id = "entry")]
+    text: TemplateChild<gtk::Entry>,
+}
+
+fn main() {}
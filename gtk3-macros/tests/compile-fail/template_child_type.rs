@@ -0,0 +1,36 @@
+use gtk::subclass::prelude::*;
+use gtk::{glib, CompositeTemplate};
+
+#[derive(Debug, Default, CompositeTemplate)]
+#[template(string = r#"
+<interface>
+  <template class="MyWidget" parent="GtkBox">
+    <child>
+      <object class="GtkLabel" id="label"/>
+    </child>
+  </template>
+</interface>
+"#)]
+pub struct MyWidget {
+    #[template_child]
+    label: TemplateChild<gtk::Entry>,
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for MyWidget {
+    const NAME: &'static str = "MyWidget";
+    type Type = MyWidgetType;
+    type ParentType = gtk::Box;
+}
+
+impl ObjectImpl for MyWidget {}
+impl WidgetImpl for MyWidget {}
+impl ContainerImpl for MyWidget {}
+impl BoxImpl for MyWidget {}
+
+glib::wrapper! {
+    pub struct MyWidgetType(ObjectSubclass<MyWidget>)
+        @extends gtk::Widget, gtk::Container, gtk::Box;
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile-fail/*.rs");
+}
@@ -0,0 +1,104 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{glib, CompositeTemplate};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static ENTRY_CHANGES: AtomicU32 = AtomicU32::new(0);
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(string = r#"
+<interface>
+  <template class="TestCallbackBox" parent="GtkBox">
+    <child>
+      <object class="GtkButton" id="button">
+        <signal name="clicked" handler="button_clicked"/>
+        <signal name="clicked" handler="button_swapped" object="entry" swapped="yes"/>
+      </object>
+    </child>
+    <child>
+      <object class="GtkEntry" id="entry">
+        <signal name="changed" handler="entry_changed"/>
+      </object>
+    </child>
+  </template>
+</interface>
+"#)]
+    pub struct TestCallbackBox {
+        #[template_child]
+        pub button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub entry: TemplateChild<gtk::Entry>,
+        pub clicks: Cell<u32>,
+        pub swapped_clicks: Cell<u32>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TestCallbackBox {
+        const NAME: &'static str = "TestCallbackBox";
+        type Type = super::TestCallbackBox;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+            Self::bind_template_callbacks(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[gtk::template_callbacks]
+    impl TestCallbackBox {
+        #[template_callback]
+        fn button_clicked(&self, button: &gtk::Button) {
+            assert_eq!(button, &*self.button);
+            self.clicks.set(self.clicks.get() + 1);
+        }
+
+        #[template_callback]
+        fn button_swapped(&self, entry: &gtk::Entry, button: &gtk::Button) {
+            assert_eq!(entry, &*self.entry);
+            assert_eq!(button, &*self.button);
+            self.swapped_clicks.set(self.swapped_clicks.get() + 1);
+        }
+
+        #[template_callback]
+        fn entry_changed(entry: &gtk::Entry) {
+            assert_eq!(entry.get_text().as_str(), "changed");
+            ENTRY_CHANGES.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl ObjectImpl for TestCallbackBox {}
+    impl WidgetImpl for TestCallbackBox {}
+    impl ContainerImpl for TestCallbackBox {}
+    impl BoxImpl for TestCallbackBox {}
+}
+
+glib::wrapper! {
+    pub struct TestCallbackBox(ObjectSubclass<imp::TestCallbackBox>)
+        @extends gtk::Widget, gtk::Container, gtk::Box;
+}
+
+#[test]
+fn template_callbacks() {
+    gtk::init().expect("Failed to initialize GTK");
+
+    let widget = glib::Object::new::<TestCallbackBox>(&[]).unwrap();
+    let imp = imp::TestCallbackBox::from_instance(&widget);
+
+    imp.button.clicked();
+    imp.button.clicked();
+    assert_eq!(imp.clicks.get(), 2);
+    assert_eq!(imp.swapped_clicks.get(), 2);
+
+    imp.entry.set_text("changed");
+    assert_eq!(ENTRY_CHANGES.load(Ordering::SeqCst), 1);
+}